@@ -1,12 +1,202 @@
 use anyhow::{Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{backend::Backend, Terminal};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufWriter, Write};
 use std::time::{Duration, Instant};
-use tracing::{debug, error, info};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, info_span, warn, Instrument, Span};
 
-use crate::cloud::CloudClient;
+use crate::clipboard;
+use crate::cloud::{
+    export, CloudClient, CreateSpec, GcloudVersion, Instance, InstanceCommand, InstanceListing,
+    LoginKind, MIN_GCLOUD_VERSION,
+};
 use crate::config::Config;
-use crate::ui::{self, Action, UiState};
+use crate::error::GciError;
+use crate::keymap::{KeyAction, Keymap};
+use crate::logging;
+use crate::ui::{
+    self, AccountsView, Action, BulkItemState, Column, CreateView, LoadingState, NotificationLevel,
+    RegionScope, RegionsView, StatusAge, UiState, DEFAULT_LOG_LIMIT,
+};
+
+/// Number of lines scrolled by PageUp/PageDown in popups
+const POPUP_PAGE_SIZE: u16 = 10;
+
+/// Maximum refresh jitter, in percent of the refresh interval
+const MAX_REFRESH_JITTER: u8 = 50;
+
+/// Range of refresh intervals reachable with +/-, in seconds
+const REFRESH_INTERVAL_RANGE: std::ops::RangeInclusive<u64> = 1..=300;
+
+/// Smoothing factor applied to the instance count history
+const HISTORY_SMOOTHING: f64 = 0.5;
+
+/// Bounded, exponentially smoothed history of instance counts
+struct CountHistory {
+    /// Maximum number of samples kept
+    capacity: usize,
+    /// Smoothed total instance counts
+    total: VecDeque<f64>,
+    /// Smoothed running instance counts
+    running: VecDeque<f64>,
+}
+
+impl CountHistory {
+    /// Create an empty history holding at most `capacity` samples
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            total: VecDeque::with_capacity(capacity),
+            running: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record a new sample, dropping the oldest one if the history is full
+    fn push(&mut self, total: usize, running: usize) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        Self::push_smoothed(&mut self.total, total as f64, self.capacity);
+        Self::push_smoothed(&mut self.running, running as f64, self.capacity);
+    }
+
+    /// Push a value smoothed against the previous sample
+    fn push_smoothed(samples: &mut VecDeque<f64>, value: f64, capacity: usize) {
+        let smoothed = match samples.back() {
+            Some(previous) => HISTORY_SMOOTHING * value + (1.0 - HISTORY_SMOOTHING) * previous,
+            None => value,
+        };
+
+        if samples.len() == capacity {
+            samples.pop_front();
+        }
+        samples.push_back(smoothed);
+    }
+
+    /// Get the total counts as sparkline data
+    fn total_data(&self) -> Vec<u64> {
+        self.total.iter().map(|v| v.round() as u64).collect()
+    }
+
+    /// Get the running counts as sparkline data
+    fn running_data(&self) -> Vec<u64> {
+        self.running.iter().map(|v| v.round() as u64).collect()
+    }
+}
+
+/// Session-scoped record of when each instance entered its current status
+#[derive(Default)]
+struct StatusTracker {
+    /// Last seen status and its age, by instance ID
+    statuses: HashMap<String, (String, StatusAge)>,
+}
+
+impl StatusTracker {
+    /// Record the status of an instance, restarting its timer if it changed
+    fn observe(&mut self, instance: &Instance) {
+        match self.statuses.get_mut(&instance.id) {
+            Some((status, age)) if *status != instance.status => {
+                *status = instance.status.clone();
+                *age = StatusAge {
+                    since: Instant::now(),
+                    exact: true,
+                };
+            }
+            Some(_) => {}
+            // The status may have changed long before this session started
+            None => {
+                self.statuses.insert(
+                    instance.id.clone(),
+                    (
+                        instance.status.clone(),
+                        StatusAge {
+                            since: Instant::now(),
+                            exact: false,
+                        },
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Record the statuses of a full listing, forgetting instances that are gone
+    fn observe_all(&mut self, instances: &[Instance]) {
+        self.statuses
+            .retain(|id, _| instances.iter().any(|instance| &instance.id == id));
+        for instance in instances {
+            self.observe(instance);
+        }
+    }
+
+    /// Get the status ages, by instance ID
+    fn ages(&self) -> HashMap<String, StatusAge> {
+        self.statuses
+            .iter()
+            .map(|(id, (_, age))| (id.clone(), *age))
+            .collect()
+    }
+}
+
+/// How long a raw describe output is reused before describing the instance again
+const RAW_JSON_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Result of an instance action run in the background
+struct ActionOutcome {
+    /// Instance the action was performed on
+    instance_id: String,
+    /// Action that was performed
+    action: Action,
+    /// Result of the cloud call
+    result: Result<()>,
+    /// Span carrying the correlation ID of the action
+    span: Span,
+}
+
+/// Result of an instance creation run in the background
+struct CreateOutcome {
+    /// Settings of the created instance
+    spec: CreateSpec,
+    /// Result of the cloud call
+    result: Result<()>,
+    /// Span carrying the correlation ID of the creation
+    span: Span,
+}
+
+/// Action run on several marked instances, refreshed once after all of them
+///
+/// The action runs on at most `max_bulk_actions` instances at a time, the
+/// others are queued and can be cancelled until they start.
+struct BulkAction {
+    /// Action run on every instance
+    action: Action,
+    /// Instances waiting for a free slot, in list order
+    queued: VecDeque<String>,
+    /// Instances the action is still running on
+    remaining: HashSet<String>,
+    /// Number of instances of the action
+    total: usize,
+    /// Number of instances the action failed on, or could not start on
+    failed: usize,
+    /// Number of queued instances cancelled before they started
+    cancelled: usize,
+    /// Whether the action ran on any instance, so the list must be refreshed
+    ran: bool,
+}
+
+impl BulkAction {
+    /// Check if the action is over on every instance
+    fn is_finished(&self) -> bool {
+        self.queued.is_empty() && self.remaining.is_empty()
+    }
+}
 
 /// Main application state
 pub struct App {
@@ -14,12 +204,68 @@ pub struct App {
     config: Config,
     /// Cloud API client
     cloud_client: CloudClient,
+    /// gcloud CLI version, checked once at startup
+    cli_version: String,
+    /// Active gcloud account, fetched at startup and after switching account or logging in
+    account: Option<String>,
     /// UI state
     ui_state: UiState,
     /// Whether the app should exit
     should_quit: bool,
+    /// Whether the app should suspend to the shell
+    should_suspend: bool,
+    /// Whether a manual refresh runs once its progress has been drawn
+    manual_refresh_pending: bool,
+    /// Login flow to run outside of the UI, chosen after the credentials expired
+    pending_login: Option<LoginKind>,
+    /// Instance to open an ssh session to, outside of the UI
+    pending_ssh: Option<Instance>,
+    /// Whether the user declined to log in again, until a refresh succeeds
+    reauth_declined: bool,
+    /// Action or refresh error ending the app, with `exit_on_error`
+    fatal_error: Option<anyhow::Error>,
     /// Last refresh time
     last_refresh: Instant,
+    /// Delay between the last refresh and the next one, jittered
+    refresh_delay: Duration,
+    /// Instance count history for the overview sparkline
+    count_history: CountHistory,
+    /// When each instance entered its current status
+    status_tracker: StatusTracker,
+    /// Key bindings of the main view
+    keymap: Keymap,
+    /// Preferences changed during the session and not saved yet, by config key
+    unsaved_settings: BTreeMap<&'static str, toml::Value>,
+    /// Regions available to the project, listed once per session
+    regions: Option<Vec<String>>,
+    /// Zones of the project, listed when the region switcher is first opened
+    zones: Option<Vec<String>>,
+    /// First instance listing, run in the background while the UI is drawn
+    initial_listing: Option<JoinHandle<Result<InstanceListing>>>,
+    /// Recent raw describe outputs, by instance ID, with when they were fetched
+    raw_json_cache: HashMap<String, (Instant, String)>,
+    /// Frame counter driving UI animations
+    frame_count: usize,
+    /// Whether the UI changed since it was last drawn
+    dirty: bool,
+    /// Actions still running in the background, by instance ID
+    actions_in_flight: HashMap<String, Action>,
+    /// Instances being created in the background, by name
+    creations_in_flight: HashSet<String>,
+    /// Bulk action still running on marked instances
+    bulk_action: Option<BulkAction>,
+    /// Sender handed to background action tasks
+    action_tx: UnboundedSender<ActionOutcome>,
+    /// Receiver for completed background actions
+    action_rx: UnboundedReceiver<ActionOutcome>,
+    /// Sender handed to background details refresh tasks
+    details_tx: UnboundedSender<Result<Instance>>,
+    /// Receiver for refreshed instance details
+    details_rx: UnboundedReceiver<Result<Instance>>,
+    /// Sender handed to background instance creation tasks
+    create_tx: UnboundedSender<CreateOutcome>,
+    /// Receiver for completed instance creations
+    create_rx: UnboundedReceiver<CreateOutcome>,
 }
 
 impl App {
@@ -30,40 +276,176 @@ impl App {
             .await
             .context("Failed to initialize cloud client")?;
 
-        // Create initial UI state
-        let ui_state = UiState::new();
-
-        // Initialize UI state with cloud client info
-        let mut app = Self {
-            config,
-            cloud_client,
-            ui_state,
-            should_quit: false,
-            last_refresh: Instant::now(),
+        // Check the gcloud CLI version once, it doesn't change during a session
+        let cli_version = match cloud_client.get_cli_version() {
+            Ok(version) => version,
+            Err(e) => {
+                error!("Failed to get CLI version: {}", e);
+                "Unknown".to_string()
+            }
         };
 
+        let mut app = Self::with_client(config, cloud_client, cli_version)?;
+
         // Update UI state with cloud client info
+        app.load_active_account();
         app.update_ui_info();
 
-        // Initial data fetch
-        app.refresh_data().await?;
+        // List instances in the background, so the first frame is drawn right away
+        let cloud_client = app.cloud_client.clone();
+        app.initial_listing = Some(tokio::spawn(
+            async move { cloud_client.list_instances().await },
+        ));
 
         Ok(app)
     }
 
+    /// Create the application state around a cloud client, without running gcloud
+    fn with_client(config: Config, cloud_client: CloudClient, cli_version: String) -> Result<Self> {
+        // Create initial UI state
+        let mut ui_state = UiState::new(&config);
+        ui_state.set_cli_version_warning(cli_version_warning(&cli_version));
+        ui_state.set_impersonated_account(
+            cloud_client
+                .get_impersonated_service_account()
+                .map(str::to_string),
+        );
+
+        // Build the key bindings of the configured preset and keys
+        let keymap = config.keymap()?;
+
+        // Create the instance count history
+        let count_history = CountHistory::new(config.history_length);
+
+        // Channel used by background actions to report their outcome
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+
+        // Channel used by background details refreshes to report the instance
+        let (details_tx, details_rx) = mpsc::unbounded_channel();
+
+        // Channel used by background instance creations to report their outcome
+        let (create_tx, create_rx) = mpsc::unbounded_channel();
+
+        Ok(Self {
+            config,
+            cloud_client,
+            cli_version,
+            account: None,
+            ui_state,
+            should_quit: false,
+            should_suspend: false,
+            manual_refresh_pending: false,
+            pending_login: None,
+            pending_ssh: None,
+            reauth_declined: false,
+            fatal_error: None,
+            last_refresh: Instant::now(),
+            refresh_delay: Duration::ZERO,
+            count_history,
+            status_tracker: StatusTracker::default(),
+            keymap,
+            unsaved_settings: BTreeMap::new(),
+            regions: None,
+            zones: None,
+            initial_listing: None,
+            raw_json_cache: HashMap::new(),
+            frame_count: 0,
+            dirty: true,
+            actions_in_flight: HashMap::new(),
+            creations_in_flight: HashSet::new(),
+            bulk_action: None,
+            action_tx,
+            action_rx,
+            details_tx,
+            details_rx,
+            create_tx,
+            create_rx,
+        })
+    }
+
     /// Run the application main loop
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         // Main event loop
         while !self.should_quit {
-            // Draw UI
-            terminal.draw(|frame| ui::render(frame, &self.ui_state))?;
+            // Draw UI only when it changed or a spinner turns, to avoid flicker over slow links
+            if self.dirty || self.ui_state.is_animating() {
+                self.frame_count = self.frame_count.wrapping_add(1);
+                self.ui_state.set_animation_frame(self.frame_count);
+                terminal.draw(|frame| ui::render(frame, &self.ui_state))?;
+                self.dirty = false;
+            }
+
+            // Run a manual refresh now that its progress is on screen
+            if self.manual_refresh_pending {
+                self.manual_refresh_pending = false;
+                self.manual_refresh().await;
+                self.dirty = true;
+            }
 
             // Handle events
             self.handle_events().await?;
 
+            // Collect the outcome of finished background actions
+            self.process_action_outcomes().await;
+
+            // Hide the notifications shown long enough
+            if self.ui_state.expire_toasts() {
+                self.dirty = true;
+            }
+
+            // Update the details popup with refreshed instances
+            self.process_details_refreshes();
+
+            // Select the instances created in the background
+            self.process_instance_creations().await;
+
+            // Suspend to the shell if requested, then repaint everything on resume
+            if self.should_suspend {
+                self.should_suspend = false;
+                ui::suspend_terminal(self.config.mouse)?;
+                terminal.clear()?;
+                self.dirty = true;
+            }
+
+            // Log in again with the terminal released, then repaint everything
+            if let Some(kind) = self.pending_login.take() {
+                let client = self.cloud_client.clone();
+                let result =
+                    ui::run_outside_terminal(self.config.mouse, async { client.login(kind) })
+                        .await?;
+                terminal.clear()?;
+                self.dirty = true;
+                self.finish_login(kind, result).await;
+            }
+
+            // Open an ssh session with the terminal released, then repaint everything
+            if let Some(instance) = self.pending_ssh.take() {
+                let result = ui::run_outside_terminal(
+                    self.config.mouse,
+                    self.cloud_client.ssh_instance(&instance),
+                )
+                .await?;
+                terminal.clear()?;
+                self.dirty = true;
+                if let Err(e) = result {
+                    error!("{:#}", e);
+                    self.ui_state
+                        .notify(NotificationLevel::Error, format!("{:#}", e));
+                }
+            }
+
+            // Show the first listing once done, later refreshes waiting for it
+            self.process_initial_listing().await;
+
             // Check if we need to refresh data
-            if self.last_refresh.elapsed() >= Duration::from_secs(self.config.refresh_interval) {
-                self.refresh_data().await?;
+            if self.initial_listing.is_none() && self.last_refresh.elapsed() >= self.refresh_delay {
+                self.refresh_or_notify().await;
+                self.dirty = true;
+            }
+
+            // Exit on the first failure if configured to
+            if let Some(e) = self.fatal_error.take() {
+                return Err(e);
             }
         }
 
@@ -73,141 +455,2343 @@ impl App {
     /// Handle terminal events
     async fn handle_events(&mut self) -> Result<()> {
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                self.handle_key_event(key).await?;
+            match event::read()? {
+                Event::Key(key) => {
+                    self.dirty = true;
+                    self.handle_key_event(key).await;
+                }
+                // Redraw with the new layout, which measures the list height used for paging
+                Event::Resize(width, height) => {
+                    debug!("Terminal resized to {}x{}", width, height);
+                    self.ui_state.resize_terminal(height);
+                    self.dirty = true;
+                }
+                Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+                _ => {}
             }
         }
 
         Ok(())
     }
 
+    /// Handle a mouse event on the instance list, without redrawing on every motion
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        // Popups are only driven by the keyboard
+        if self.ui_state.has_popup() {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.ui_state.click_instance(mouse.column, mouse.row) {
+                    return;
+                }
+            }
+            MouseEventKind::ScrollUp => self.ui_state.previous_item(),
+            MouseEventKind::ScrollDown => self.ui_state.next_item(),
+            _ => return,
+        }
+        self.dirty = true;
+    }
+
     /// Handle a key event
-    async fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
+    async fn handle_key_event(&mut self, key: KeyEvent) {
         debug!("Key event: {:?}", key);
 
-        match key.code {
-            // Quit
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.should_quit = true
+        // The quit confirmation only accepts an explicit yes, anything else cancels
+        if self.ui_state.is_quit_confirmation_open() {
+            self.ui_state.cancel_quit_confirmation();
+            if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+                self.quit_or_ask_to_save();
+            }
+            return;
+        }
+
+        // Unsaved preferences are saved or discarded explicitly, anything else cancels
+        if self.ui_state.is_save_confirmation_open() {
+            self.ui_state.cancel_save_confirmation();
+            match key.code {
+                KeyCode::Char('s') => {
+                    self.save_settings();
+                    self.should_quit = true;
+                }
+                KeyCode::Char('d') => self.should_quit = true,
+                _ => {}
+            }
+            return;
+        }
+
+        // Actions are only confirmed by an explicit yes, anything else cancels
+        if let Some(confirmation) = self.ui_state.take_confirmation() {
+            if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+                if confirmation.bulk {
+                    self.start_bulk_action(confirmation.action, confirmation.instance_ids)
+                        .await;
+                } else if let Some(instance_id) = confirmation.instance_ids.into_iter().next() {
+                    self.start_action(confirmation.action, instance_id).await;
+                }
+            }
+            return;
+        }
+
+        // Logging in again is chosen explicitly, anything else dismisses until a refresh succeeds
+        if self.ui_state.is_reauth_open() {
+            self.ui_state.cancel_reauth();
+            match key.code {
+                KeyCode::Char('l') => self.pending_login = Some(LoginKind::User),
+                KeyCode::Char('a') => self.pending_login = Some(LoginKind::ApplicationDefault),
+                _ => self.reauth_declined = true,
+            }
+            return;
+        }
+
+        // The key after 'y' picks the gcloud command to copy
+        if self.ui_state.is_copy_mode() {
+            self.ui_state.end_copy_mode();
+            if key.code == KeyCode::Char('m') {
+                self.copy_markdown_table();
+                return;
+            }
+            if key.code == KeyCode::Char('i') {
+                self.copy_ip();
+                return;
+            }
+            let command = match key.code {
+                KeyCode::Char('s') => Some(InstanceCommand::Start),
+                KeyCode::Char('S') => Some(InstanceCommand::Stop),
+                KeyCode::Char('R') => Some(InstanceCommand::Reset),
+                KeyCode::Char('d') => Some(InstanceCommand::Delete),
+                KeyCode::Char('x') => Some(InstanceCommand::Ssh),
+                _ => None,
+            };
+            if let Some(command) = command {
+                self.copy_command(command);
+            }
+            return;
+        }
+
+        // The logs popup captures every key except quitting
+        if self.ui_state.is_logs_open() && !self.keymap.is_quit(&key) {
+            self.handle_logs_key_event(key).await;
+            return;
+        }
+
+        // The notification history captures every key except quitting
+        if self.ui_state.is_notifications_open() && !self.keymap.is_quit(&key) {
+            self.handle_notifications_key_event(key);
+            return;
+        }
+
+        // The bulk action progress captures every key except quitting
+        if self.ui_state.is_bulk_progress_open() && !self.keymap.is_quit(&key) {
+            self.handle_bulk_progress_key_event(key).await;
+            return;
+        }
+
+        // The region switcher captures every key except quitting
+        if self.ui_state.is_regions_open() && !self.keymap.is_quit(&key) {
+            self.handle_regions_key_event(key).await;
+            return;
+        }
+
+        // The account switcher captures every key except quitting
+        if self.ui_state.is_accounts_open() && !self.keymap.is_quit(&key) {
+            self.handle_accounts_key_event(key).await;
+            return;
+        }
+
+        // The instance picker captures every key, as it takes text input
+        if self.ui_state.is_palette_open() {
+            self.handle_palette_key_event(key);
+            return;
+        }
+
+        // The help popup captures every key, as it takes search input
+        if self.ui_state.is_help_open() {
+            self.handle_help_key_event(key);
+            return;
+        }
+
+        // The gcloud command popup captures every key, as it takes text input
+        if self.ui_state.is_command_open() {
+            self.handle_command_key_event(key).await;
+            return;
+        }
+
+        // The machine type change popup captures every key, as it takes text input
+        if self.ui_state.is_resize_open() {
+            self.handle_resize_key_event(key).await;
+            return;
+        }
+
+        // The instance creation form captures every key, as it takes text input
+        if self.ui_state.is_create_open() {
+            self.handle_create_key_event(key);
+            return;
+        }
+
+        // The network tags popup captures every key, as it takes text input
+        if self.ui_state.is_tags_open() {
+            self.handle_tags_key_event(key).await;
+            return;
+        }
+
+        // The column picker captures every key except quitting
+        if self.ui_state.is_column_picker_open() && !self.keymap.is_quit(&key) {
+            self.handle_column_picker_key_event(key);
+            return;
+        }
+
+        // The raw describe popup captures every key except quitting
+        if self.ui_state.is_raw_json_open() && !self.keymap.is_quit(&key) {
+            self.handle_raw_json_key_event(key);
+            return;
+        }
+
+        // The firewall rules popup captures every key except quitting
+        if self.ui_state.is_firewalls_open() && !self.keymap.is_quit(&key) {
+            self.handle_firewalls_key_event(key).await;
+            return;
+        }
+
+        // The serial console popup captures every key except quitting
+        if self.ui_state.is_serial_console_open() && !self.keymap.is_quit(&key) {
+            self.handle_serial_console_key_event(key).await;
+            return;
+        }
+
+        // The filter and search text capture typed keys, so they can contain bound keys
+        if self.ui_state.is_input_mode() && !self.keymap.is_quit_while_typing(&key) {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                self.ui_state.finish_input();
+                return;
             }
+            if self.ui_state.handle_input(key) {
+                return;
+            }
+        }
 
-            // Help
-            KeyCode::Char('?') => self.ui_state.toggle_help(),
+        let Some(action) = self.keymap.action(&key) else {
+            return;
+        };
 
-            // Navigation
-            KeyCode::Up | KeyCode::Char('k') => self.ui_state.previous_item(),
-            KeyCode::Down | KeyCode::Char('j') => self.ui_state.next_item(),
-            KeyCode::Enter => self.ui_state.show_details(),
-            KeyCode::Esc => self.ui_state.close_popup(),
+        match action {
+            KeyAction::Quit => self.request_quit(),
+            KeyAction::Suspend => self.should_suspend = true,
+            KeyAction::Help => self.ui_state.open_help(self.keymap.help_entries()),
 
-            // Refresh
-            KeyCode::Char('r') => {
-                self.refresh_data().await?;
+            // Navigation, scrolling the network interfaces when the details popup is open
+            KeyAction::Up if self.ui_state.is_details_open() => {
+                self.ui_state.scroll_interfaces_up()
+            }
+            KeyAction::Down if self.ui_state.is_details_open() => {
+                self.ui_state.scroll_interfaces_down()
+            }
+            KeyAction::Up => self.ui_state.previous_item(),
+            KeyAction::Down => self.ui_state.next_item(),
+            KeyAction::Top => self.ui_state.select_first(),
+            KeyAction::Bottom => self.ui_state.select_last(),
+            KeyAction::PageUp => self.ui_state.page_up(),
+            KeyAction::PageDown => self.ui_state.page_down(),
+            KeyAction::Details => self.ui_state.show_details(),
+            KeyAction::RevealSecrets => self.ui_state.toggle_reveal_sensitive(),
+            KeyAction::RawJson if self.ui_state.is_details_open() => {
+                self.ui_state.open_raw_json();
+                self.load_raw_json().await;
             }
+            KeyAction::RawJson => {}
+            KeyAction::ClosePopup => self.ui_state.close_popup(),
 
-            // Instance actions
-            KeyCode::Char('s') => {
+            // Refresh, only the shown instance when the details popup is open
+            KeyAction::Refresh if self.ui_state.is_details_open() => self.refresh_details(),
+            KeyAction::Refresh => self.request_manual_refresh(),
+
+            // Instance actions, on the marked instances if any
+            KeyAction::Mark => self.ui_state.toggle_marked(),
+            KeyAction::Start if self.ui_state.has_marked() => {
+                self.perform_bulk_action(Action::Start).await
+            }
+            KeyAction::Stop if self.ui_state.has_marked() => {
+                self.perform_bulk_action(Action::Stop).await
+            }
+            KeyAction::Restart if self.ui_state.has_marked() => {
+                self.perform_bulk_action(Action::Restart).await
+            }
+            KeyAction::Delete if self.ui_state.has_marked() => {
+                self.perform_bulk_action(Action::Delete).await
+            }
+            KeyAction::BulkProgress => {
+                if !self.ui_state.open_bulk_progress() {
+                    self.ui_state.notify(
+                        NotificationLevel::Info,
+                        "No bulk action to show, mark instances first".to_string(),
+                    );
+                }
+            }
+            KeyAction::Start => {
                 if let Some(instance_id) = self.ui_state.selected_instance_id() {
-                    self.perform_action(Action::Start, instance_id).await?;
+                    self.perform_action(Action::Start, instance_id).await;
                 }
             }
-            KeyCode::Char('S') => {
+            KeyAction::Stop => {
                 if let Some(instance_id) = self.ui_state.selected_instance_id() {
-                    self.perform_action(Action::Stop, instance_id).await?;
+                    self.perform_action(Action::Stop, instance_id).await;
                 }
             }
-            KeyCode::Char('R') => {
+            KeyAction::Restart => {
                 if let Some(instance_id) = self.ui_state.selected_instance_id() {
-                    self.perform_action(Action::Restart, instance_id).await?;
+                    self.perform_action(Action::Restart, instance_id).await;
                 }
             }
-
-            // Filter
-            KeyCode::Char('f') => self.ui_state.toggle_filter_mode(),
-            KeyCode::Char('/') => self.ui_state.toggle_search_mode(),
-
-            // Handle filter/search input
-            _ => {
-                if self.ui_state.is_input_mode() {
-                    self.ui_state.handle_input(key);
+            KeyAction::Delete => {
+                if let Some(instance_id) = self.ui_state.selected_instance_id() {
+                    self.perform_action(Action::Delete, instance_id).await;
                 }
             }
+            KeyAction::Resize => self.ui_state.open_resize(),
+            KeyAction::Tags => self.ui_state.open_tags(),
+            KeyAction::Ssh => self.request_ssh(),
+
+            // Popups
+            KeyAction::Logs => {
+                self.ui_state.open_logs(DEFAULT_LOG_LIMIT);
+                self.load_logs().await;
+            }
+            KeyAction::Firewalls => {
+                self.ui_state.open_firewalls();
+                self.load_firewalls().await;
+            }
+            KeyAction::SerialConsole => {
+                self.ui_state.open_serial_console();
+                self.load_serial_console().await;
+            }
+            KeyAction::Notifications => self.ui_state.toggle_notifications(),
+            KeyAction::Palette => self.ui_state.open_palette(),
+            KeyAction::Regions => self.open_regions().await,
+            KeyAction::Accounts => self.open_accounts(),
+            KeyAction::Create => self.open_create().await,
+            KeyAction::CountFocus => self.toggle_count_focus(),
+            KeyAction::Dense => self.toggle_dense(),
+            KeyAction::Theme => self.toggle_theme(),
+            KeyAction::FasterRefresh => self.set_refresh_interval(self.config.refresh_interval / 2),
+            KeyAction::SlowerRefresh => {
+                self.set_refresh_interval(self.config.refresh_interval.saturating_mul(2))
+            }
+            KeyAction::CycleSort => self.cycle_sort(),
+            KeyAction::SortDirection => self.toggle_sort_direction(),
+            KeyAction::Columns => self.ui_state.open_column_picker(),
+            KeyAction::Command => self.ui_state.open_command(),
+            KeyAction::CopyCommand => self.ui_state.start_copy_mode(),
+            KeyAction::Export => self.export_csv(),
+
+            // Filter
+            KeyAction::Filter => self.ui_state.toggle_filter_mode(),
+            KeyAction::OnlyMine => self.ui_state.toggle_only_mine(),
+            KeyAction::OnlyPublic => self.ui_state.toggle_only_public(),
+            KeyAction::StatusFilter => self.ui_state.cycle_status_filter(),
+            KeyAction::Search => self.ui_state.toggle_search_mode(),
+            KeyAction::NextMatch => self.ui_state.select_next_match(),
+            KeyAction::PreviousMatch => self.ui_state.select_previous_match(),
         }
+    }
 
-        Ok(())
+    /// Open an ssh session to the selected instance once the terminal is released
+    fn request_ssh(&mut self) {
+        let Some(instance) = self.ui_state.selected_instance() else {
+            return;
+        };
+
+        let error = if !self.config.use_ssh {
+            Some("SSH is disabled by use_ssh = false in the configuration".to_string())
+        } else if instance.status != "RUNNING" {
+            Some(format!("{} is not running, can't ssh to it", instance.name))
+        } else {
+            None
+        };
+
+        match error {
+            Some(error) => self.ui_state.notify(NotificationLevel::Error, error),
+            None => self.pending_ssh = Some(instance.clone()),
+        }
     }
 
-    /// Refresh data from Google Cloud
-    async fn refresh_data(&mut self) -> Result<()> {
-        info!("Refreshing instance data...");
+    /// Get a cloud client acting on the project of an instance
+    fn client_for(&self, instance_id: &str) -> CloudClient {
+        match self.ui_state.instance(instance_id) {
+            Some(instance) => self.cloud_client.for_project(&instance.project),
+            None => self.cloud_client.clone(),
+        }
+    }
 
-        // Get instances from cloud
-        let instances = self
-            .cloud_client
-            .list_instances()
-            .await
-            .context("Failed to fetch instances")?;
+    /// Quit immediately, or ask for confirmation if actions are still running
+    fn request_quit(&mut self) {
+        if self.actions_in_flight.is_empty() && self.creations_in_flight.is_empty() {
+            self.quit_or_ask_to_save();
+        } else {
+            self.ui_state.request_quit_confirmation();
+        }
+    }
 
-        // Update UI state with new data
-        self.ui_state.update_instances(instances);
+    /// Quit immediately, or ask to save the preferences changed during the session
+    fn quit_or_ask_to_save(&mut self) {
+        if self.unsaved_settings.is_empty() {
+            self.should_quit = true;
+        } else {
+            let settings = self
+                .unsaved_settings
+                .keys()
+                .map(|key| key.to_string())
+                .collect();
+            self.ui_state.request_save_confirmation(settings);
+        }
+    }
 
-        // Make sure we have a valid selection after updating instances
-        if !self.ui_state.has_valid_selection() {
-            self.ui_state.reset_selection();
+    /// Save the preferences changed during the session to the configuration file
+    fn save_settings(&mut self) {
+        for (key, value) in std::mem::take(&mut self.unsaved_settings) {
+            if let Err(e) = self.config.save_setting(key, value) {
+                error!("Failed to save {}: {:#}", key, e);
+            }
         }
+    }
 
-        // Update refresh time
-        self.last_refresh = Instant::now();
+    /// Copy the gcloud command acting on the selected instance to the clipboard
+    fn copy_command(&mut self, command: InstanceCommand) {
+        let Some(instance) = self.ui_state.selected_instance() else {
+            return;
+        };
 
-        // Update UI info (region, project, version)
-        self.update_ui_info();
+        let command_line = self.cloud_client.instance_command_line(command, instance);
+        self.copy_command_line(command_line);
+    }
 
-        Ok(())
+    /// Get the gcloud command lines performing an action on an instance, without running them
+    fn action_command_line(&self, action: &Action, instance: &Instance) -> String {
+        match action {
+            Action::Start => self
+                .cloud_client
+                .instance_command_line(InstanceCommand::Start, instance),
+            Action::Stop => self
+                .cloud_client
+                .instance_command_line(InstanceCommand::Stop, instance),
+            Action::Restart => self
+                .cloud_client
+                .instance_command_line(InstanceCommand::Reset, instance),
+            Action::Delete => self
+                .cloud_client
+                .instance_command_line(InstanceCommand::Delete, instance),
+            Action::Resize {
+                machine_type,
+                restart,
+            } => self
+                .cloud_client
+                .resize_command_line(instance, machine_type, *restart),
+            Action::UpdateTags { add, remove } => {
+                self.cloud_client.tags_command_line(instance, add, remove)
+            }
+        }
     }
 
-    /// Update UI state with cloud client information
-    fn update_ui_info(&mut self) {
-        // Set project ID
-        let project_id = self.cloud_client.get_project_id().to_string();
+    /// Copy the gcloud command lines of an action on an instance to the clipboard
+    fn copy_action_command(&mut self, action: &Action, instance_id: &str) {
+        let Some(instance) = self.ui_state.instance(instance_id) else {
+            return;
+        };
 
-        // Set region
-        let region = self.cloud_client.get_region().to_string();
+        let command_line = self.action_command_line(action, instance);
+        self.copy_command_line(command_line);
+    }
 
-        // Try to get CLI version
-        let cli_version = match self.cloud_client.get_cli_version() {
-            Ok(version) => version,
+    /// Copy a gcloud command line to the clipboard, for runbooks or another shell
+    fn copy_command_line(&mut self, command_line: String) {
+        match clipboard::copy(&command_line) {
+            Ok(()) => self.ui_state.notify(
+                NotificationLevel::Success,
+                format!("Copied: {}", command_line),
+            ),
             Err(e) => {
-                error!("Failed to get CLI version: {}", e);
-                "Unknown".to_string()
+                error!("Failed to copy command to the clipboard: {:#}", e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("Failed to copy to the clipboard: {}", command_line),
+                );
             }
+        }
+    }
+
+    /// Copy the IP of the selected instance to the clipboard
+    ///
+    /// The external IP is preferred, as it is the one reachable from other tools.
+    fn copy_ip(&mut self) {
+        let Some(instance) = self.ui_state.selected_instance() else {
+            return;
         };
 
-        // Update UI state
-        self.ui_state
-            .update_cloud_info(project_id, region, cli_version);
+        let Some(ip) = instance
+            .external_ip
+            .clone()
+            .or_else(|| instance.internal_ip.clone())
+        else {
+            self.ui_state.notify(
+                NotificationLevel::Info,
+                format!("{} has no IP address to copy", instance.name),
+            );
+            return;
+        };
+
+        match clipboard::copy(&ip) {
+            Ok(()) => self.ui_state.notify(
+                NotificationLevel::Success,
+                format!("Copied {} to clipboard", ip),
+            ),
+            Err(e) => {
+                error!("Failed to copy {} to the clipboard: {:#}", ip, e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("Failed to copy to the clipboard: {:#}", e),
+                );
+            }
+        }
+    }
+
+    /// Copy the shown instances as a Markdown table to the clipboard
+    fn copy_markdown_table(&mut self) {
+        let table = self.ui_state.markdown_table();
+        match clipboard::copy(&table) {
+            Ok(()) => self.ui_state.notify(
+                NotificationLevel::Success,
+                format!(
+                    "Copied a Markdown table of {} instance(s)",
+                    table.lines().count().saturating_sub(2)
+                ),
+            ),
+            Err(e) => {
+                error!(
+                    "Failed to copy the Markdown table to the clipboard: {:#}",
+                    e
+                );
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("Failed to copy to the clipboard: {:#}", e),
+                );
+            }
+        }
     }
 
-    /// Perform an action on an instance
-    async fn perform_action(&mut self, action: Action, instance_id: String) -> Result<()> {
-        info!("Performing action on instance {}", instance_id);
+    /// Write the shown instances, filtered and sorted, to a CSV file in the working directory
+    fn export_csv(&mut self) {
+        let path = format!(
+            "g1c-export-{}.csv",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let count = self.ui_state.shown_instances().len();
 
-        // Perform the action
-        match action {
-            Action::Start => {
-                self.cloud_client.start_instance(&instance_id).await?;
+        let result = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path))
+            .and_then(|file| {
+                let mut writer = BufWriter::new(file);
+                export::to_csv(self.ui_state.shown_instances(), &mut writer)?;
+                writer
+                    .flush()
+                    .with_context(|| format!("Failed to write {}", path))
+            });
+
+        match result {
+            Ok(()) => {
+                info!("Exported {} instance(s) to {}", count, path);
+                self.ui_state.notify(
+                    NotificationLevel::Success,
+                    format!("Exported {} instance(s) to {}", count, path),
+                );
+            }
+            Err(e) => {
+                error!("Failed to export instances: {:#}", e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("Failed to export instances: {:#}", e),
+                );
+            }
+        }
+    }
+
+    /// Handle a key event while the logs popup is open
+    async fn handle_logs_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.logs_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_logs(),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Char('+') => {
+                view.increase_limit();
+                self.load_logs().await;
             }
-            Action::Stop => {
-                self.cloud_client.stop_instance(&instance_id).await?;
+            KeyCode::Char('-') => {
+                view.decrease_limit();
+                self.load_logs().await;
             }
-            Action::Restart => {
-                self.cloud_client.restart_instance(&instance_id).await?;
+            KeyCode::Char('r') => self.load_logs().await,
+            _ => {}
+        }
+    }
+
+    /// Load log entries for the instance shown in the logs popup
+    async fn load_logs(&mut self) {
+        let Some((instance_id, limit)) = self
+            .ui_state
+            .logs_view_mut()
+            .map(|view| (view.instance_id.clone(), view.limit))
+        else {
+            return;
+        };
+
+        let result = self
+            .client_for(&instance_id)
+            .read_instance_logs(&instance_id, limit)
+            .await;
+
+        if let Some(view) = self.ui_state.logs_view_mut() {
+            match result {
+                Ok(entries) => view.set_entries(entries),
+                Err(e) => {
+                    error!("Failed to read logs for instance {}: {:#}", instance_id, e);
+                    view.set_error(format!("{:#}", e));
+                }
             }
         }
+    }
+
+    /// Handle a key event while the notification history is open
+    fn handle_notifications_key_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('N') => self.ui_state.toggle_notifications(),
+            KeyCode::Up | KeyCode::Char('k') => self.ui_state.notifications_mut().scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => self.ui_state.notifications_mut().scroll_down(1),
+            KeyCode::PageUp => self.ui_state.notifications_mut().scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => self
+                .ui_state
+                .notifications_mut()
+                .scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Char('c') => self.ui_state.notifications_mut().clear(),
+            _ => {}
+        }
+    }
 
-        // Refresh data after action
-        self.refresh_data().await?;
+    /// Handle a key event while the bulk action progress is shown
+    async fn handle_bulk_progress_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.bulk_progress_mut() else {
+            return;
+        };
 
-        Ok(())
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('b') => self.ui_state.close_bulk_progress(),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Char('c') => self.cancel_bulk_action().await,
+            _ => {}
+        }
+    }
+
+    /// Switch the overview count emphasis, offering to save it when quitting
+    fn toggle_count_focus(&mut self) {
+        let focus = self.ui_state.toggle_count_focus();
+        self.config.count_focus = focus;
+
+        // Saved when quitting, if confirmed
+        match toml::Value::try_from(focus) {
+            Ok(value) => {
+                self.unsaved_settings.insert("count_focus", value);
+            }
+            Err(e) => error!("Failed to serialize count focus: {:#}", e),
+        }
+    }
+
+    /// Switch the instance list layout, offering to save it when quitting
+    fn toggle_dense(&mut self) {
+        let dense = self.ui_state.toggle_dense();
+        self.config.dense = dense;
+        self.unsaved_settings
+            .insert("dense", toml::Value::Boolean(dense));
+    }
+
+    /// Switch between the dark and light themes, offering to save it when quitting
+    fn toggle_theme(&mut self) {
+        let Some(theme) = self.ui_state.toggle_theme() else {
+            self.ui_state.notify(
+                NotificationLevel::Info,
+                "Colors are disabled (--no-color or NO_COLOR)".to_string(),
+            );
+            return;
+        };
+        self.config.theme = theme.to_string();
+        self.unsaved_settings
+            .insert("theme", toml::Value::String(theme.to_string()));
+    }
+
+    /// Sort the instance list by the next key, offering to save it when quitting
+    fn cycle_sort(&mut self) {
+        let sort_key = self.ui_state.cycle_sort();
+        self.config.sort_key = sort_key;
+        self.unsaved_settings.insert(
+            "sort_key",
+            toml::Value::String(sort_key.config_name().to_string()),
+        );
+    }
+
+    /// Reverse the instance list order, offering to save it when quitting
+    fn toggle_sort_direction(&mut self) {
+        let ascending = self.ui_state.toggle_sort_direction();
+        self.config.sort_ascending = ascending;
+        self.unsaved_settings
+            .insert("sort_ascending", toml::Value::Boolean(ascending));
+    }
+
+    /// List zones once per session, leaving them unknown if they cannot be listed
+    async fn load_zones(&mut self) {
+        if self.zones.is_none() {
+            match self.cloud_client.list_zones().await {
+                Ok(zones) => self.zones = Some(zones),
+                Err(e) => warn!("Failed to list zones: {:#}", e),
+            }
+        }
+    }
+
+    /// Open the region switcher, listing regions once per session
+    async fn open_regions(&mut self) {
+        if self.regions.is_none() {
+            match self.cloud_client.list_regions().await {
+                Ok(regions) => self.regions = Some(regions),
+                Err(e) => {
+                    error!("Failed to list regions: {:#}", e);
+                    self.ui_state
+                        .open_regions(RegionsView::with_error(format!("{:#}", e)));
+                    return;
+                }
+            }
+        }
+
+        // The switcher still lists regions if zones cannot be listed
+        self.load_zones().await;
+
+        let regions = self.regions.clone().unwrap_or_default();
+        let zones = self.zones.clone().unwrap_or_default();
+        let current = match self.cloud_client.get_zone() {
+            Some(zone) => RegionScope::Zone(zone.to_string()),
+            None if self.cloud_client.is_region_scoped() => {
+                RegionScope::Region(self.cloud_client.get_region().to_string())
+            }
+            None => RegionScope::All,
+        };
+        self.ui_state
+            .open_regions(RegionsView::new(regions, zones, current));
+    }
+
+    /// Handle a key event while the region switcher is open
+    async fn handle_regions_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.regions_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_regions(),
+            KeyCode::Up | KeyCode::Char('k') => view.previous(),
+            KeyCode::Down | KeyCode::Char('j') => view.next(),
+            KeyCode::Char('a') => view.toggle_all_zones(),
+            KeyCode::Enter => {
+                let scope = view.selected_scope();
+                self.ui_state.close_regions();
+                match scope {
+                    RegionScope::All => self.cloud_client.set_region(None),
+                    RegionScope::Region(region) => self.cloud_client.set_region(Some(region)),
+                    RegionScope::Zone(zone) => self.cloud_client.set_zone(zone),
+                }
+                self.ui_state.forget_vanished_instances();
+                self.update_ui_info();
+                self.refresh_or_notify().await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the account switcher, listing the accounts gcloud has credentials for
+    fn open_accounts(&mut self) {
+        let view = match self.cloud_client.list_accounts() {
+            Ok(accounts) => AccountsView::new(accounts),
+            Err(e) => {
+                error!("Failed to list accounts: {:#}", e);
+                AccountsView::with_error(format!("{:#}", e))
+            }
+        };
+        self.ui_state.open_accounts(view);
+    }
+
+    /// Handle a key event while the account switcher is open
+    async fn handle_accounts_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.accounts_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_accounts(),
+            KeyCode::Up | KeyCode::Char('k') => view.previous(),
+            KeyCode::Down | KeyCode::Char('j') => view.next(),
+            KeyCode::Enter => {
+                let account = view.selected_account().cloned();
+                self.ui_state.close_accounts();
+                match account {
+                    Some(account) if !account.active => self.switch_account(&account.email).await,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Make an account the active gcloud account, then list instances as that account
+    async fn switch_account(&mut self, email: &str) {
+        match self.cloud_client.set_account(email) {
+            Ok(()) => {
+                self.ui_state.notify(
+                    NotificationLevel::Success,
+                    format!("Switched to account {}", email),
+                );
+                self.reauth_declined = false;
+                self.ui_state.forget_vanished_instances();
+                self.load_active_account();
+                self.update_ui_info();
+                self.refresh_or_notify().await;
+            }
+            Err(e) => {
+                error!("{:#}", e);
+                self.ui_state
+                    .notify(NotificationLevel::Error, format!("{:#}", e));
+            }
+        }
+    }
+
+    /// Open the instance creation form, in the default zone
+    async fn open_create(&mut self) {
+        self.load_zones().await;
+
+        // The fallback "-a" zone doesn't exist in every region, use a listed zone of the region then
+        let mut zone = self.cloud_client.get_default_zone();
+        if let Some(zones) = self.zones.as_ref().filter(|zones| !zones.contains(&zone)) {
+            let region = self.cloud_client.get_region();
+            if let Some(listed) = zones.iter().find(|zone| {
+                zone.strip_prefix(region)
+                    .is_some_and(|suffix| suffix.starts_with('-'))
+            }) {
+                zone = listed.clone();
+            }
+        }
+        self.ui_state
+            .open_create(CreateView::new(&CreateSpec::new(zone)));
+    }
+
+    /// Handle a key event while the instance creation form is open
+    fn handle_create_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.create_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_create(),
+            KeyCode::Tab | KeyCode::Down => view.next_field(),
+            KeyCode::BackTab | KeyCode::Up => view.previous_field(),
+            // Copy the command instead of running it, the form stays open
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let spec = view.spec();
+                match spec.validate() {
+                    Ok(()) => {
+                        let command_line = self.cloud_client.create_command_line(&spec);
+                        self.copy_command_line(command_line);
+                    }
+                    Err(e) => view.set_error(format!("{:#}", e)),
+                }
+            }
+            KeyCode::Enter => {
+                let spec = view.spec();
+                match spec.validate() {
+                    Ok(()) => {
+                        self.ui_state.close_create();
+                        self.create_instance(spec);
+                    }
+                    Err(e) => view.set_error(format!("{:#}", e)),
+                }
+            }
+            _ => {
+                view.handle_key(key);
+            }
+        }
+    }
+
+    /// Create an instance in the background, selecting it once created
+    fn create_instance(&mut self, spec: CreateSpec) {
+        let span = info_span!(
+            "create_instance",
+            correlation_id = %logging::correlation_id(),
+            instance = %spec.name,
+        );
+        span.in_scope(|| info!("Creating instance {} in zone {}", spec.name, spec.zone));
+        self.ui_state.notify(
+            NotificationLevel::Info,
+            format!("{}: create started", spec.name),
+        );
+        self.creations_in_flight.insert(spec.name.clone());
+        self.show_actions_in_flight();
+
+        let cloud_client = self.cloud_client.clone();
+        let create_tx = self.create_tx.clone();
+        tokio::spawn(
+            async move {
+                let result = cloud_client.create_instance(spec.clone()).await;
+
+                // The receiver only goes away when the app is shutting down
+                let _ = create_tx.send(CreateOutcome {
+                    spec,
+                    result,
+                    span: Span::current(),
+                });
+            }
+            .instrument(span),
+        );
+    }
+
+    /// Handle a key event while the column picker is open
+    fn handle_column_picker_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.column_picker_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('C') => self.ui_state.close_column_picker(),
+            KeyCode::Up | KeyCode::Char('k') => view.previous(),
+            KeyCode::Down | KeyCode::Char('j') => view.next(),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                view.toggle_selected();
+                let enabled = view.enabled();
+                self.set_columns(enabled);
+            }
+            _ => {}
+        }
+    }
+
+    /// Show the enabled columns, offering to save them when quitting
+    fn set_columns(&mut self, enabled: Vec<Column>) {
+        let names = enabled
+            .iter()
+            .map(|column| toml::Value::String(column.config_name().to_string()))
+            .collect();
+        self.unsaved_settings
+            .insert("columns", toml::Value::Array(names));
+        self.config.columns = enabled.clone();
+        self.ui_state.set_enabled_columns(enabled);
+    }
+
+    /// Handle a key event while the help popup is open
+    fn handle_help_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.help_view_mut() else {
+            return;
+        };
+
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            // Esc clears the search first, so a second one closes the popup
+            KeyCode::Esc if view.has_query() => view.clear_query(),
+            KeyCode::Esc => self.ui_state.close_help(),
+            KeyCode::Char('c') if control => self.request_quit(),
+            KeyCode::Up => view.scroll_up(1),
+            KeyCode::Down => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(10),
+            KeyCode::PageDown => view.scroll_down(10),
+            KeyCode::Backspace => view.pop_char(),
+            KeyCode::Char(c) if !control => view.push_char(c),
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the instance picker is open
+    fn handle_palette_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.palette_view_mut() else {
+            return;
+        };
+
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_palette(),
+            KeyCode::Enter => {
+                let instance_id = view.selected_instance_id().map(str::to_string);
+                self.ui_state.close_palette();
+                if let Some(instance_id) = instance_id {
+                    self.ui_state.jump_to_instance(&instance_id);
+                }
+            }
+            KeyCode::Up => view.previous(),
+            KeyCode::Down => view.next(),
+            KeyCode::Char('p') if control => view.previous(),
+            KeyCode::Char('n') if control => view.next(),
+            KeyCode::Backspace => view.pop_char(),
+            KeyCode::Char(c) if !control => view.push_char(c),
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the machine type change popup is open
+    async fn handle_resize_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.resize_view_mut() else {
+            return;
+        };
+
+        // The stop-resize-start sequence only runs on an explicit yes
+        if view.is_confirming() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    let action = Action::Resize {
+                        machine_type: view.target().to_string(),
+                        restart: true,
+                    };
+                    let instance_id = view.instance_id.clone();
+                    self.ui_state.close_resize();
+                    self.perform_action(action, instance_id).await;
+                }
+                KeyCode::Esc => self.ui_state.close_resize(),
+                _ => view.cancel_confirmation(),
+            }
+            return;
+        }
+
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_resize(),
+            KeyCode::Backspace => view.pop_char(),
+            // Copy the commands instead of running them, the popup stays open
+            KeyCode::Char('y') if control => {
+                let Some(target) = view.checked_target() else {
+                    return;
+                };
+                let action = Action::Resize {
+                    machine_type: target,
+                    restart: view.status == "RUNNING",
+                };
+                let instance_id = view.instance_id.clone();
+                self.copy_action_command(&action, &instance_id);
+            }
+            KeyCode::Enter => {
+                let Some(target) = view.checked_target() else {
+                    return;
+                };
+
+                match view.status.as_str() {
+                    "TERMINATED" => {
+                        let action = Action::Resize {
+                            machine_type: target,
+                            restart: false,
+                        };
+                        let instance_id = view.instance_id.clone();
+                        self.ui_state.close_resize();
+                        self.perform_action(action, instance_id).await;
+                    }
+                    "RUNNING" => view.request_confirmation(),
+                    status => view.set_error(format!(
+                        "The instance is {}: wait for it to be RUNNING or TERMINATED before resizing",
+                        status
+                    )),
+                }
+            }
+            KeyCode::Char(c) if !control => view.push_char(c),
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the network tags popup is open
+    async fn handle_tags_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.tags_view_mut() else {
+            return;
+        };
+
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_tags(),
+            // Copy the commands instead of running them, the popup stays open
+            KeyCode::Char('y') if control => {
+                let Some((add, remove)) = view.checked_changes() else {
+                    return;
+                };
+                if add.is_empty() && remove.is_empty() {
+                    view.set_error("No tag changes to copy".to_string());
+                    return;
+                }
+                let instance_id = view.instance_id.clone();
+                self.copy_action_command(&Action::UpdateTags { add, remove }, &instance_id);
+            }
+            KeyCode::Enter => {
+                let Some((add, remove)) = view.checked_changes() else {
+                    return;
+                };
+
+                let instance_id = view.instance_id.clone();
+                self.ui_state.close_tags();
+                if add.is_empty() && remove.is_empty() {
+                    return;
+                }
+                self.perform_action(Action::UpdateTags { add, remove }, instance_id)
+                    .await;
+            }
+            _ => {
+                view.handle_key(key);
+            }
+        }
+    }
+
+    /// Handle a key event while the firewall rules popup is open
+    async fn handle_firewalls_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.firewalls_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_firewalls(),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Char('r') => self.load_firewalls().await,
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the serial console popup is open
+    async fn handle_serial_console_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.serial_console_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_serial_console(),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Char('r') => self.load_serial_console().await,
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the raw describe popup is open
+    fn handle_raw_json_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.raw_json_view_mut() else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_raw_json(),
+            KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+            KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            _ => {}
+        }
+    }
+
+    /// Handle a key event while the gcloud command popup is open
+    async fn handle_command_key_event(&mut self, key: KeyEvent) {
+        let Some(view) = self.ui_state.command_view_mut() else {
+            return;
+        };
+
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.ui_state.close_command(),
+            KeyCode::Enter => self.run_command().await,
+            KeyCode::Up => view.scroll_up(1),
+            KeyCode::Down => view.scroll_down(1),
+            KeyCode::PageUp => view.scroll_up(POPUP_PAGE_SIZE),
+            KeyCode::PageDown => view.scroll_down(POPUP_PAGE_SIZE),
+            KeyCode::Backspace => view.pop_char(),
+            KeyCode::Char(c) if !control => view.push_char(c),
+            _ => {}
+        }
+    }
+
+    /// Run the command typed in the gcloud command popup, then refresh the list
+    async fn run_command(&mut self) {
+        let Some(input) = self
+            .ui_state
+            .command_view_mut()
+            .map(|view| view.input().trim().to_string())
+            .filter(|input| !input.is_empty())
+        else {
+            return;
+        };
+
+        let span = info_span!("command", correlation_id = %logging::correlation_id());
+        let result = self.cloud_client.run_command(&input).instrument(span).await;
+
+        if let Some(view) = self.ui_state.command_view_mut() {
+            match result {
+                Ok(output) => view.set_output(output),
+                Err(e) => {
+                    error!("Failed to run gcloud command: {:#}", e);
+                    view.set_error(format!("{:#}", e));
+                }
+            }
+        }
+
+        // The command may have changed instances
+        self.refresh_or_notify().await;
+    }
+
+    /// Load the raw describe output of the selected instance, reusing a recent one
+    async fn load_raw_json(&mut self) {
+        let Some(instance) = self.ui_state.selected_instance().cloned() else {
+            return;
+        };
+
+        self.raw_json_cache
+            .retain(|_, (fetched, _)| fetched.elapsed() < RAW_JSON_CACHE_TTL);
+
+        let result = match self.raw_json_cache.get(&instance.id) {
+            Some((_, json)) => Ok(json.clone()),
+            None => self.cloud_client.describe_instance_raw(&instance).await,
+        };
+
+        match result {
+            Ok(json) => {
+                self.ui_state.set_raw_json(&json);
+                self.raw_json_cache
+                    .entry(instance.id)
+                    .or_insert_with(|| (Instant::now(), json));
+            }
+            Err(e) => {
+                error!("Failed to describe instance {}: {:#}", instance.name, e);
+                if let Some(view) = self.ui_state.raw_json_view_mut() {
+                    view.set_error(format!("{:#}", e));
+                }
+            }
+        }
+    }
+
+    /// Load the effective firewall rules for the instance shown in the popup
+    async fn load_firewalls(&mut self) {
+        let Some((instance_name, zone)) = self
+            .ui_state
+            .firewalls_view_mut()
+            .map(|view| (view.instance_name.clone(), view.zone.clone()))
+        else {
+            return;
+        };
+
+        // The popup is shown for the selected instance
+        let cloud_client = match self.ui_state.selected_instance() {
+            Some(instance) => self.cloud_client.for_project(&instance.project),
+            None => self.cloud_client.clone(),
+        };
+        let result = cloud_client
+            .get_effective_firewalls(&instance_name, &zone)
+            .await;
+
+        if let Some(view) = self.ui_state.firewalls_view_mut() {
+            match result {
+                Ok(rules) => view.set_rules(rules),
+                Err(e) => {
+                    error!(
+                        "Failed to get firewall rules for instance {}: {:#}",
+                        instance_name, e
+                    );
+                    view.set_error(format!("{:#}", e));
+                }
+            }
+        }
+    }
+
+    /// Load the serial port output of the instance shown in the serial console popup
+    async fn load_serial_console(&mut self) {
+        let Some((instance_name, zone)) = self
+            .ui_state
+            .serial_console_view_mut()
+            .map(|view| (view.instance_name.clone(), view.zone.clone()))
+        else {
+            return;
+        };
+
+        // The popup is shown for the selected instance, a stopped one having no output
+        let (cloud_client, terminated) = match self.ui_state.selected_instance() {
+            Some(instance) => (
+                self.cloud_client.for_project(&instance.project),
+                instance.status == "TERMINATED",
+            ),
+            None => (self.cloud_client.clone(), false),
+        };
+        let result = if terminated {
+            Ok(String::new())
+        } else {
+            cloud_client.get_serial_output(&instance_name, &zone).await
+        };
+
+        if let Some(view) = self.ui_state.serial_console_view_mut() {
+            match result {
+                Ok(output) => view.set_output(&output),
+                Err(e) => {
+                    error!(
+                        "Failed to get serial port output of instance {}: {:#}",
+                        instance_name, e
+                    );
+                    view.set_error(format!("{:#}", e));
+                }
+            }
+        }
+    }
+
+    /// Refresh data from Google Cloud, returning the number of instances found
+    async fn refresh_data(&mut self) -> Result<usize> {
+        info!("Refreshing instance data...");
+
+        // This listing supersedes the first one, if still running
+        if let Some(handle) = self.initial_listing.take() {
+            handle.abort();
+        }
+
+        // Get instances from cloud, showing the error until instances are listed once
+        let listing = match self
+            .cloud_client
+            .list_instances()
+            .await
+            .context("Failed to fetch instances")
+        {
+            Ok(listing) => listing,
+            Err(e) => {
+                if !self.ui_state.is_loaded() {
+                    self.ui_state
+                        .set_loading_state(LoadingState::Error(format!("{:#}", e)));
+                }
+                return Err(e);
+            }
+        };
+
+        Ok(self.apply_listing(listing))
+    }
+
+    /// Show the instances of a listing, returning the number of instances found
+    fn apply_listing(&mut self, listing: InstanceListing) -> usize {
+        // Keep the instances of the other projects or zones when one fails
+        for (scope, e) in &listing.failures {
+            warn!("Failed to list instances in {}: {:#}", scope, e);
+            self.ui_state.notify(
+                NotificationLevel::Error,
+                format!("Failed to list instances in {}: {:#}", scope, e),
+            );
+        }
+        let instances = listing.instances;
+
+        let count = instances.len();
+
+        // Record instance counts for the sparkline
+        if self.config.show_sparkline {
+            let running = instances.iter().filter(|i| i.status == "RUNNING").count();
+            self.count_history.push(instances.len(), running);
+            self.ui_state.update_count_history(
+                self.count_history.total_data(),
+                self.count_history.running_data(),
+            );
+        }
+
+        // Track how long each instance has been in its status
+        self.status_tracker.observe_all(&instances);
+        self.ui_state.set_status_ages(self.status_tracker.ages());
+
+        // Update UI state with new data
+        self.ui_state.update_instances(instances);
+
+        // Make sure we have a valid selection after updating instances
+        if !self.ui_state.has_valid_selection() {
+            self.ui_state.reset_selection();
+        }
+
+        // Update refresh time
+        self.schedule_next_refresh();
+
+        // Update UI info (region, project, version, account)
+        self.update_ui_info();
+        self.ui_state.set_loading_state(LoadingState::Loaded);
+
+        count
+    }
+
+    /// Show the outcome of the first instance listing once it is done
+    async fn process_initial_listing(&mut self) {
+        if !self
+            .initial_listing
+            .as_ref()
+            .is_some_and(JoinHandle::is_finished)
+        {
+            return;
+        }
+        let Some(handle) = self.initial_listing.take() else {
+            return;
+        };
+        self.dirty = true;
+
+        let result = match handle.await {
+            Ok(result) => result.context("Failed to fetch instances"),
+            Err(e) => Err(anyhow::anyhow!("Instance listing task failed: {}", e)),
+        };
+        match result {
+            Ok(listing) => {
+                self.apply_listing(listing);
+            }
+            Err(e) => {
+                error!("{:#}", e);
+                self.schedule_next_refresh();
+                self.ui_state
+                    .set_loading_state(LoadingState::Error(format!("{:#}", e)));
+                self.ui_state
+                    .notify(NotificationLevel::Error, format!("{:#}", e));
+                self.offer_reauth_if_expired(&e);
+                self.fail_if_fatal(e);
+            }
+        }
+    }
+
+    /// Fetch the active gcloud account, which only changes on account switch or login
+    fn load_active_account(&mut self) {
+        self.account = match self.cloud_client.get_active_account() {
+            Ok(account) => account,
+            Err(e) => {
+                error!("Failed to get active account: {}", e);
+                None
+            }
+        };
+    }
+
+    /// Update UI state with cloud client information
+    fn update_ui_info(&mut self) {
+        // Set project ID, or every listed project
+        let project_id = if self.cloud_client.is_multi_project() {
+            self.cloud_client.get_projects().join(", ")
+        } else {
+            self.cloud_client.get_project_id().to_string()
+        };
+
+        // Set region, noting when instances are listed in a single zone or every region
+        let region = if let Some(zone) = self.cloud_client.get_zone() {
+            format!("{} (only zone {})", self.cloud_client.get_region(), zone)
+        } else if self.cloud_client.is_region_scoped() {
+            self.cloud_client.get_region().to_string()
+        } else {
+            format!("{} (listing all regions)", self.cloud_client.get_region())
+        };
+
+        // Use the CLI version checked at startup
+        let cli_version = self.cli_version.clone();
+
+        // Update UI state
+        self.ui_state
+            .update_cloud_info(project_id, region, cli_version, self.account.clone());
+        self.ui_state
+            .set_default_zone(self.cloud_client.get_default_zone());
+    }
+
+    /// Perform an action on an instance, once confirmed if it is destructive
+    ///
+    /// Stop, restart and delete ask for confirmation first, unless
+    /// `confirm_destructive` is disabled.
+    async fn perform_action(&mut self, action: Action, instance_id: String) {
+        if self.config.confirm_destructive && action.is_destructive() {
+            self.ui_state.request_confirmation(action, instance_id);
+            return;
+        }
+        self.start_action(action, instance_id).await;
+    }
+
+    /// Start an action on an instance in the background
+    ///
+    /// Failures are reported as notifications, and only end the application
+    /// when `exit_on_error` is set.
+    async fn start_action(&mut self, action: Action, instance_id: String) {
+        // Only one action at a time per instance
+        if self.actions_in_flight.contains_key(&instance_id) {
+            info!(
+                "Action already in progress on instance {}, ignoring {:?}",
+                instance_id, action
+            );
+            return;
+        }
+
+        // Don't act on an instance that may be gone, or replaced by one with the same name
+        if self.ui_state.is_vanished(&instance_id) {
+            self.ui_state.notify(
+                NotificationLevel::Error,
+                format!(
+                    "{} is no longer listed, refresh before acting on it",
+                    self.ui_state
+                        .instance_name(&instance_id)
+                        .unwrap_or(&instance_id)
+                ),
+            );
+            return;
+        }
+
+        // Every log event of the action, including its follow-up refresh, shares this ID
+        let span = info_span!(
+            "action",
+            correlation_id = %logging::correlation_id(),
+            action = action.description(),
+            instance_id = %instance_id,
+        );
+        let _entered = span.enter();
+
+        info!("Performing action {:?} on instance {}", action, instance_id);
+
+        let instance = self
+            .ui_state
+            .instance_name(&instance_id)
+            .unwrap_or(&instance_id)
+            .to_string();
+        self.ui_state.notify(
+            NotificationLevel::Info,
+            format!("{}: {} started", instance, action.description()),
+        );
+
+        self.actions_in_flight
+            .insert(instance_id.clone(), action.clone());
+        self.show_actions_in_flight();
+
+        // The listed instance already has its zone, saving a listing to find it
+        let location = self
+            .ui_state
+            .instance(&instance_id)
+            .filter(|instance| !instance.zone.is_empty())
+            .map(|instance| (instance.name.clone(), instance.zone.clone()));
+
+        let cloud_client = self.client_for(&instance_id);
+        let action_tx = self.action_tx.clone();
+        let task_span = span.clone();
+        tokio::spawn(
+            async move {
+                let result = match &location {
+                    Some((name, zone)) => match &action {
+                        Action::Start => cloud_client.start_instance_in_zone(name, zone).await,
+                        Action::Stop => cloud_client.stop_instance_in_zone(name, zone).await,
+                        Action::Restart => cloud_client.restart_instance_in_zone(name, zone).await,
+                        Action::Delete => cloud_client.delete_instance_in_zone(name, zone).await,
+                        Action::Resize {
+                            machine_type,
+                            restart,
+                        } => {
+                            cloud_client
+                                .resize_instance_in_zone(name, zone, machine_type, *restart)
+                                .await
+                        }
+                        Action::UpdateTags { add, remove } => {
+                            cloud_client
+                                .update_tags_in_zone(name, zone, add, remove)
+                                .await
+                        }
+                    },
+                    None => match &action {
+                        Action::Start => cloud_client.start_instance(&instance_id).await,
+                        Action::Stop => cloud_client.stop_instance(&instance_id).await,
+                        Action::Restart => cloud_client.restart_instance(&instance_id).await,
+                        Action::Delete => cloud_client.delete_instance(&instance_id).await,
+                        Action::Resize {
+                            machine_type,
+                            restart,
+                        } => {
+                            cloud_client
+                                .resize_instance(&instance_id, machine_type, *restart)
+                                .await
+                        }
+                        Action::UpdateTags { add, remove } => {
+                            cloud_client.update_tags(&instance_id, add, remove).await
+                        }
+                    },
+                };
+
+                // The receiver only goes away when the app is shutting down
+                let _ = action_tx.send(ActionOutcome {
+                    instance_id,
+                    action,
+                    result,
+                    span: Span::current(),
+                });
+            }
+            .instrument(task_span),
+        );
+    }
+
+    /// Perform an action on every marked instance, once confirmed if it is destructive
+    ///
+    /// Stop, restart and delete ask for one confirmation listing the marked
+    /// instances, unless `confirm_destructive` is disabled.
+    async fn perform_bulk_action(&mut self, action: Action) {
+        if self.bulk_action.is_some() {
+            self.ui_state.notify(
+                NotificationLevel::Error,
+                "A bulk action is still running, wait for it to complete".to_string(),
+            );
+            self.ui_state.open_bulk_progress();
+            return;
+        }
+
+        if self.config.confirm_destructive && action.is_destructive() {
+            self.ui_state.request_bulk_confirmation(action);
+            return;
+        }
+        let instance_ids = self.ui_state.marked_instance_ids();
+        self.start_bulk_action(action, instance_ids).await;
+    }
+
+    /// Start an action on every given instance, refreshing once all of them are done
+    ///
+    /// Progress is shown per instance in a popup, from which the instances
+    /// still queued can be cancelled.
+    async fn start_bulk_action(&mut self, action: Action, instance_ids: Vec<String>) {
+        self.ui_state.clear_marked();
+        if instance_ids.is_empty() {
+            return;
+        }
+
+        info!(
+            "Bulk action {} started on {} instance(s)",
+            action.description(),
+            instance_ids.len()
+        );
+        self.ui_state
+            .start_bulk_progress(action.description(), &instance_ids);
+        self.bulk_action = Some(BulkAction {
+            action,
+            total: instance_ids.len(),
+            queued: instance_ids.into(),
+            remaining: HashSet::new(),
+            failed: 0,
+            cancelled: 0,
+            ran: false,
+        });
+
+        self.start_queued_bulk_actions().await;
+        if self.finish_bulk_action() {
+            self.refresh_or_notify().await;
+        }
+    }
+
+    /// Start the queued instances of the bulk action while fewer than `max_bulk_actions` run
+    async fn start_queued_bulk_actions(&mut self) {
+        let max_running = self.config.max_bulk_actions.max(1);
+        loop {
+            let Some(bulk) = self.bulk_action.as_mut() else {
+                return;
+            };
+            if bulk.remaining.len() >= max_running {
+                return;
+            }
+            let Some(instance_id) = bulk.queued.pop_front() else {
+                return;
+            };
+            let action = bulk.action.clone();
+
+            // start_action skips instances already busy or no longer listed
+            let busy = self.actions_in_flight.contains_key(&instance_id);
+            if !busy {
+                self.start_action(action, instance_id.clone()).await;
+            }
+            let state = if busy {
+                BulkItemState::Failed("another action is running".to_string())
+            } else if self.actions_in_flight.contains_key(&instance_id) {
+                BulkItemState::InProgress
+            } else {
+                BulkItemState::Failed("no longer listed".to_string())
+            };
+
+            if let Some(bulk) = self.bulk_action.as_mut() {
+                if state == BulkItemState::InProgress {
+                    bulk.remaining.insert(instance_id.clone());
+                    bulk.ran = true;
+                } else {
+                    bulk.failed += 1;
+                }
+            }
+            self.ui_state.set_bulk_state(&instance_id, state);
+        }
+    }
+
+    /// Cancel the queued instances of the bulk action, letting the running ones complete
+    async fn cancel_bulk_action(&mut self) {
+        let Some(bulk) = self.bulk_action.as_mut() else {
+            return;
+        };
+        let cancelled: Vec<String> = bulk.queued.drain(..).collect();
+        if cancelled.is_empty() {
+            return;
+        }
+        bulk.cancelled += cancelled.len();
+
+        info!(
+            "Bulk action {} cancelled on {} queued instance(s)",
+            bulk.action.description(),
+            cancelled.len()
+        );
+        for instance_id in &cancelled {
+            self.ui_state
+                .set_bulk_state(instance_id, BulkItemState::Cancelled);
+        }
+        self.ui_state.notify(
+            NotificationLevel::Info,
+            format!(
+                "Cancelled {} queued instance(s), the running ones complete",
+                cancelled.len()
+            ),
+        );
+
+        if self.finish_bulk_action() {
+            self.refresh_or_notify().await;
+        }
+    }
+
+    /// Sum up the bulk action once it is over on every instance
+    ///
+    /// Returns whether the action ran on any instance, so the list must be refreshed.
+    fn finish_bulk_action(&mut self) -> bool {
+        let Some(bulk) = self.bulk_action.take_if(|bulk| bulk.is_finished()) else {
+            return false;
+        };
+
+        let succeeded = bulk.total - bulk.failed - bulk.cancelled;
+        info!(
+            "Bulk action {} completed: {} succeeded, {} failed, {} cancelled",
+            bulk.action.description(),
+            succeeded,
+            bulk.failed,
+            bulk.cancelled
+        );
+        let mut message = format!(
+            "Bulk {}: {} of {} instance(s) succeeded",
+            bulk.action.description(),
+            succeeded,
+            bulk.total
+        );
+        if bulk.cancelled > 0 {
+            message.push_str(&format!(", {} cancelled", bulk.cancelled));
+        }
+        self.ui_state.notify(
+            if bulk.failed == 0 {
+                NotificationLevel::Success
+            } else {
+                NotificationLevel::Error
+            },
+            message,
+        );
+        bulk.ran
+    }
+
+    /// Describe the instance shown in the details popup again, in the background
+    fn refresh_details(&mut self) {
+        let Some(instance) = self.ui_state.selected_instance().cloned() else {
+            return;
+        };
+
+        let span = info_span!(
+            "refresh_details",
+            correlation_id = %logging::correlation_id(),
+            instance_id = %instance.id,
+        );
+        span.in_scope(|| info!("Refreshing details of instance {}", instance.name));
+        self.ui_state.set_details_refreshing(true);
+
+        let cloud_client = self.cloud_client.clone();
+        let details_tx = self.details_tx.clone();
+        tokio::spawn(
+            async move {
+                let result = cloud_client.describe_instance(&instance).await;
+
+                // The receiver only goes away when the app is shutting down
+                let _ = details_tx.send(result);
+            }
+            .instrument(span),
+        );
+    }
+
+    /// Apply instance details refreshed in the background
+    fn process_details_refreshes(&mut self) {
+        while let Ok(result) = self.details_rx.try_recv() {
+            self.dirty = true;
+            self.ui_state.set_details_refreshing(false);
+            match result {
+                Ok(instance) => {
+                    self.status_tracker.observe(&instance);
+                    self.ui_state.set_status_ages(self.status_tracker.ages());
+                    self.ui_state.update_instance(instance);
+                }
+                Err(e) => {
+                    error!("Failed to refresh instance details: {:#}", e);
+                    self.ui_state.notify(
+                        NotificationLevel::Error,
+                        format!("Failed to refresh details: {:#}", e),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Refresh once instances created in the background exist, and select them
+    async fn process_instance_creations(&mut self) {
+        while let Ok(outcome) = self.create_rx.try_recv() {
+            self.dirty = true;
+            let span = outcome.span.clone();
+            let Some((name, zone)) = self.apply_create_outcome(outcome) else {
+                continue;
+            };
+
+            // Select the new instance once listed, the refresh logged under the creation
+            self.refresh_or_notify().instrument(span).await;
+            if let Some(instance_id) = self.ui_state.instance_id_by_name(&name, &zone) {
+                self.ui_state.jump_to_instance(&instance_id);
+            }
+        }
+    }
+
+    /// Report the outcome of a background instance creation
+    ///
+    /// Returns the name and zone of the created instance, to refresh and select it.
+    fn apply_create_outcome(&mut self, outcome: CreateOutcome) -> Option<(String, String)> {
+        let _entered = outcome.span.enter();
+        let name = &outcome.spec.name;
+        self.creations_in_flight.remove(name);
+        self.show_actions_in_flight();
+
+        match outcome.result {
+            Ok(()) if self.cloud_client.is_dry_run() => {
+                self.ui_state.notify(
+                    NotificationLevel::Info,
+                    format!("(dry-run) would create instance {}", name),
+                );
+                None
+            }
+            Ok(()) => {
+                info!("Created instance {}", name);
+                self.ui_state.notify(
+                    NotificationLevel::Success,
+                    format!("{}: create completed", name),
+                );
+                Some((name.clone(), outcome.spec.zone.clone()))
+            }
+            Err(e) => {
+                error!("Failed to create instance {}: {:#}", name, e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("{}: create failed: {:#}", name, e),
+                );
+                self.fail_if_fatal(e.context(format!("Failed to create instance {}", name)));
+                None
+            }
+        }
+    }
+
+    /// Handle the outcome of background actions that have completed
+    async fn process_action_outcomes(&mut self) {
+        let mut refresh = false;
+        let mut last_span = None;
+
+        while let Ok(outcome) = self.action_rx.try_recv() {
+            self.dirty = true;
+            last_span = Some(outcome.span.clone());
+            refresh |= self.apply_action_outcome(outcome);
+        }
+        if last_span.is_none() {
+            return;
+        }
+
+        // Completed instances of a bulk action free slots for the queued ones
+        self.start_queued_bulk_actions().await;
+        refresh |= self.finish_bulk_action();
+
+        // Refresh data after actions, logged under the last completed action
+        if let Some(span) = last_span.filter(|_| refresh) {
+            self.refresh_or_notify().instrument(span).await;
+        }
+    }
+
+    /// Show the actions and instance creations still running in the status bar
+    fn show_actions_in_flight(&mut self) {
+        let running = self
+            .actions_in_flight
+            .keys()
+            .chain(&self.creations_in_flight)
+            .cloned()
+            .collect();
+        self.ui_state.set_actions_in_flight(running);
+    }
+
+    /// Report the outcome of a background action, returning whether to refresh
+    ///
+    /// A failure is reported, and only kept to exit with when `exit_on_error` is set.
+    fn apply_action_outcome(&mut self, outcome: ActionOutcome) -> bool {
+        let _entered = outcome.span.enter();
+        self.actions_in_flight.remove(&outcome.instance_id);
+        self.show_actions_in_flight();
+
+        let instance = self
+            .ui_state
+            .instance_name(&outcome.instance_id)
+            .unwrap_or(&outcome.instance_id)
+            .to_string();
+        let action = outcome.action.description();
+
+        // Instances of a bulk action only refresh once the last one is done
+        let mut refresh = true;
+        if let Some(bulk) = self.bulk_action.as_mut() {
+            if bulk.remaining.remove(&outcome.instance_id) {
+                let state = match &outcome.result {
+                    Ok(()) => BulkItemState::Done,
+                    Err(e) => {
+                        bulk.failed += 1;
+                        BulkItemState::Failed(format!("{:#}", e))
+                    }
+                };
+                self.ui_state.set_bulk_state(&outcome.instance_id, state);
+                refresh = false;
+            }
+        }
+
+        match outcome.result {
+            Ok(()) if self.cloud_client.is_dry_run() => {
+                info!("Action {} simulated on instance {}", action, instance);
+                self.ui_state.notify(
+                    NotificationLevel::Info,
+                    format!("(dry-run) would {} instance {}", action, instance),
+                );
+            }
+            Ok(()) => {
+                info!("Action {} completed on instance {}", action, instance);
+                self.ui_state.notify(
+                    NotificationLevel::Success,
+                    format!("{}: {} completed", instance, action),
+                );
+            }
+            Err(e) => {
+                error!("Failed to {} instance {}: {:#}", action, instance, e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("{}: {} failed: {:#}", instance, action, e),
+                );
+                self.fail_if_fatal(
+                    e.context(format!("Failed to {} instance {}", action, instance)),
+                );
+            }
+        }
+        refresh
+    }
+
+    /// Show the refresh progress and run the refresh on the next loop iteration
+    ///
+    /// The refresh blocks the loop, so the progress is drawn before it starts.
+    fn request_manual_refresh(&mut self) {
+        self.ui_state
+            .set_refresh_progress(Some("Refreshing: listing instances…".to_string()));
+        self.manual_refresh_pending = true;
+    }
+
+    /// Refresh data on request, reporting the outcome so it can be retried
+    async fn manual_refresh(&mut self) {
+        let result = self.refresh_data().await;
+        self.ui_state.set_refresh_progress(None);
+
+        match result {
+            Ok(count) => self.ui_state.notify(
+                NotificationLevel::Success,
+                format!("Refreshed: {} instance(s) found", count),
+            ),
+            Err(e) => {
+                error!("{:#}", e);
+                self.schedule_next_refresh();
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!("Refresh failed, press r to retry: {:#}", e),
+                );
+                self.offer_reauth_if_expired(&e);
+                self.fail_if_fatal(e);
+            }
+        }
+    }
+
+    /// Change the refresh interval, offering to save it when quitting
+    ///
+    /// The wait for the current refresh is recomputed, so a shorter interval
+    /// takes effect right away.
+    fn set_refresh_interval(&mut self, seconds: u64) {
+        let seconds = clamp_refresh_interval(seconds);
+        self.config.refresh_interval = seconds;
+        self.refresh_delay = jittered_interval(seconds, self.config.refresh_jitter);
+        self.ui_state.set_refresh_interval(seconds);
+        self.unsaved_settings
+            .insert("refresh_interval", toml::Value::Integer(seconds as i64));
+    }
+
+    /// Start waiting for the next refresh, spread by the configured jitter
+    fn schedule_next_refresh(&mut self) {
+        self.last_refresh = Instant::now();
+        self.refresh_delay =
+            jittered_interval(self.config.refresh_interval, self.config.refresh_jitter);
+    }
+
+    /// Refresh data, reporting failures as notifications instead of exiting
+    async fn refresh_or_notify(&mut self) {
+        match self.refresh_data().await {
+            Ok(_) => self.reauth_declined = false,
+            Err(e) => {
+                error!("{:#}", e);
+
+                // Wait for the next interval rather than retrying on every tick
+                self.schedule_next_refresh();
+                self.ui_state
+                    .notify(NotificationLevel::Error, format!("{:#}", e));
+                self.offer_reauth_if_expired(&e);
+                self.fail_if_fatal(e);
+            }
+        }
+    }
+
+    /// Offer to log in again if a refresh failed because the credentials expired
+    ///
+    /// Once declined, the offer waits for a successful refresh (e.g. after
+    /// logging in from another shell), so it doesn't reopen on every refresh.
+    fn offer_reauth_if_expired(&mut self, e: &anyhow::Error) {
+        if GciError::is_auth_expired(e) && !self.reauth_declined {
+            self.ui_state.request_reauth();
+        }
+    }
+
+    /// Report the outcome of a login flow, refreshing with the new credentials
+    async fn finish_login(&mut self, kind: LoginKind, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                info!("Logged in again with {}", kind.command_line());
+                self.ui_state.notify(
+                    NotificationLevel::Success,
+                    format!("Logged in again with {}", kind.command_line()),
+                );
+
+                // The login may have switched to another account
+                self.load_active_account();
+                self.update_ui_info();
+                self.refresh_or_notify().await;
+            }
+            Err(e) => {
+                error!("Login failed: {:#}", e);
+                self.ui_state.notify(
+                    NotificationLevel::Error,
+                    format!(
+                        "Login failed: {:#}. Run {} in a shell, then press r to refresh",
+                        e,
+                        kind.command_line()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Keep an action or refresh error to exit with, if `exit_on_error` is set
+    ///
+    /// Otherwise the error has only been reported in the UI, so the app stays
+    /// open to read it, check the logs and retry.
+    fn fail_if_fatal(&mut self, e: anyhow::Error) {
+        if self.config.exit_on_error && self.fatal_error.is_none() {
+            self.fatal_error = Some(e);
+        }
+    }
+}
+
+/// Get a refresh interval randomly spread by up to ±`jitter_percent`
+///
+/// Spreading refreshes keeps many instances of g1c started together from
+/// listing instances at the same time.
+fn jittered_interval(interval_secs: u64, jitter_percent: u8) -> Duration {
+    let interval = Duration::from_secs(interval_secs);
+    if jitter_percent == 0 {
+        return interval;
+    }
+
+    // Randomly seeded hasher, mapped to [-1, 1]
+    let random = RandomState::new().build_hasher().finish();
+    let unit = random as f64 / u64::MAX as f64 * 2.0 - 1.0;
+
+    let jitter = f64::from(jitter_percent.min(MAX_REFRESH_JITTER)) / 100.0;
+    interval.mul_f64(1.0 + jitter * unit)
+}
+
+/// Keep a refresh interval within the range reachable at runtime
+fn clamp_refresh_interval(seconds: u64) -> u64 {
+    seconds.clamp(
+        *REFRESH_INTERVAL_RANGE.start(),
+        *REFRESH_INTERVAL_RANGE.end(),
+    )
+}
+
+/// Get a warning message if the gcloud CLI version is older than supported
+fn cli_version_warning(cli_version: &str) -> Option<String> {
+    let Some(version) = GcloudVersion::parse(cli_version) else {
+        warn!("Could not parse gcloud CLI version: {}", cli_version);
+        return None;
+    };
+
+    if version.is_supported() {
+        return None;
+    }
+
+    warn!(
+        "gcloud CLI version {} is older than the minimum supported version {}, some features may fail",
+        version, MIN_GCLOUD_VERSION
+    );
+    Some(format!("below minimum {}", MIN_GCLOUD_VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Create an app listing the given instances, without running gcloud
+    fn test_app(config: Config, instances: Vec<Instance>) -> App {
+        let mut app = App::with_client(
+            config,
+            CloudClient::for_test("my-project"),
+            "Google Cloud SDK 500.0.0".to_string(),
+        )
+        .unwrap();
+        app.ui_state.update_instances(instances);
+        app
+    }
+
+    /// Get the outcome of an action finished in the background
+    fn outcome(instance_id: &str, action: Action, result: Result<()>) -> ActionOutcome {
+        ActionOutcome {
+            instance_id: instance_id.to_string(),
+            action,
+            result,
+            span: Span::none(),
+        }
+    }
+
+    #[test]
+    fn failing_action_keeps_the_app_running() {
+        let instance = Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium");
+        let mut app = test_app(Config::default(), vec![instance.clone()]);
+        app.actions_in_flight
+            .insert(instance.id.clone(), Action::Stop);
+
+        let refresh = app.apply_action_outcome(outcome(
+            &instance.id,
+            Action::Stop,
+            Err(anyhow::anyhow!("Quota exceeded")),
+        ));
+
+        assert!(refresh);
+        assert!(!app.should_quit);
+        assert!(app.fatal_error.is_none());
+        assert!(app.actions_in_flight.is_empty());
+        let toast = app.ui_state.notifications_mut().toasts().last().unwrap();
+        assert_eq!(toast.level, NotificationLevel::Error);
+        assert_eq!(toast.message, "web-1: stop failed: Quota exceeded");
+    }
+
+    #[test]
+    fn failing_action_is_fatal_with_exit_on_error() {
+        let instance = Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium");
+        let mut config = Config::default();
+        config.exit_on_error = true;
+        let mut app = test_app(config, vec![instance.clone()]);
+
+        app.apply_action_outcome(outcome(
+            &instance.id,
+            Action::Restart,
+            Err(anyhow::anyhow!("Permission denied")),
+        ));
+
+        // The run loop returns the error, rather than quitting normally
+        assert!(!app.should_quit);
+        assert_eq!(
+            format!("{:#}", app.fatal_error.unwrap()),
+            "Failed to restart instance web-1: Permission denied"
+        );
+    }
+
+    #[test]
+    fn clamp_refresh_interval_keeps_the_runtime_range() {
+        assert_eq!(clamp_refresh_interval(0), 1);
+        assert_eq!(clamp_refresh_interval(1), 1);
+        assert_eq!(clamp_refresh_interval(5), 5);
+        assert_eq!(clamp_refresh_interval(300), 300);
+        assert_eq!(clamp_refresh_interval(301), 300);
+        assert_eq!(clamp_refresh_interval(u64::MAX), 300);
+    }
+
+    #[test]
+    fn quitting_during_a_create_asks_for_confirmation() {
+        let mut app = test_app(Config::default(), Vec::new());
+        app.creations_in_flight.insert("web-3".to_string());
+        app.show_actions_in_flight();
+
+        app.request_quit();
+
+        assert!(!app.should_quit);
+        assert!(app.ui_state.is_quit_confirmation_open());
+        assert!(app.ui_state.is_animating());
+    }
+
+    /// Create an app with two marked instances
+    fn app_with_marked(config: Config) -> App {
+        let mut app = test_app(
+            config,
+            vec![
+                Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium"),
+                Instance::for_test("web-2", "RUNNING", "europe-west1-b", "e2-medium"),
+            ],
+        );
+        app.ui_state.toggle_marked();
+        app.ui_state.next_item();
+        app.ui_state.toggle_marked();
+        app
+    }
+
+    #[tokio::test]
+    async fn bulk_stop_asks_for_one_confirmation() {
+        let mut app = app_with_marked(Config::default());
+
+        app.perform_bulk_action(Action::Stop).await;
+
+        assert!(app.actions_in_flight.is_empty());
+        assert!(app.bulk_action.is_none());
+        let confirmation = app.ui_state.take_confirmation().unwrap();
+        assert!(confirmation.bulk);
+        assert_eq!(confirmation.instance_ids, vec!["web-1-id", "web-2-id"]);
+        assert_eq!(confirmation.instance_names, vec!["web-1", "web-2"]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_bulk_restart_starts_nothing() {
+        let mut app = app_with_marked(Config::default());
+        app.perform_bulk_action(Action::Restart).await;
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE))
+            .await;
+
+        assert!(app.ui_state.take_confirmation().is_none());
+        assert!(app.actions_in_flight.is_empty());
+        assert!(app.bulk_action.is_none());
+        // The instances stay marked, to try again
+        assert_eq!(app.ui_state.marked_instance_ids().len(), 2);
+    }
+
+    /// Create an app running a bulk stop on web-1, with web-2 and web-3 queued
+    fn app_with_bulk_stop() -> App {
+        let instances = ["web-1", "web-2", "web-3"]
+            .map(|name| Instance::for_test(name, "RUNNING", "europe-west1-b", "e2-medium"));
+        let ids: Vec<String> = instances.iter().map(|i| i.id.clone()).collect();
+        let mut app = test_app(Config::default(), instances.to_vec());
+
+        app.ui_state
+            .start_bulk_progress(Action::Stop.description(), &ids);
+        app.ui_state
+            .set_bulk_state("web-1-id", BulkItemState::InProgress);
+        app.actions_in_flight
+            .insert("web-1-id".to_string(), Action::Stop);
+        app.bulk_action = Some(BulkAction {
+            action: Action::Stop,
+            queued: ids[1..].iter().cloned().collect(),
+            remaining: HashSet::from(["web-1-id".to_string()]),
+            total: 3,
+            failed: 0,
+            cancelled: 0,
+            ran: true,
+        });
+        app
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_bulk_action_lets_the_running_instances_complete() {
+        let mut app = app_with_bulk_stop();
+
+        app.cancel_bulk_action().await;
+
+        assert!(app.bulk_action.is_some());
+        let view = app.ui_state.bulk_progress_mut().unwrap();
+        assert_eq!(view.state("web-1-id"), Some(&BulkItemState::InProgress));
+        assert_eq!(view.state("web-2-id"), Some(&BulkItemState::Cancelled));
+        assert_eq!(view.state("web-3-id"), Some(&BulkItemState::Cancelled));
+
+        // The list is only refreshed once, when the bulk action is summed up
+        assert!(!app.apply_action_outcome(outcome("web-1-id", Action::Stop, Ok(()))));
+        assert!(app.finish_bulk_action());
+
+        assert!(app.bulk_action.is_none());
+        let view = app.ui_state.bulk_progress_mut().unwrap();
+        assert_eq!(view.state("web-1-id"), Some(&BulkItemState::Done));
+        assert_eq!(view.finished_count(), 3);
+        let toast = app.ui_state.notifications_mut().toasts().last().unwrap();
+        assert_eq!(toast.level, NotificationLevel::Success);
+        assert_eq!(
+            toast.message,
+            "Bulk stop: 1 of 3 instance(s) succeeded, 2 cancelled"
+        );
+    }
+
+    #[test]
+    fn failed_bulk_instances_show_the_error() {
+        let mut app = app_with_bulk_stop();
+
+        app.apply_action_outcome(outcome(
+            "web-1-id",
+            Action::Stop,
+            Err(anyhow::anyhow!("Quota exceeded")),
+        ));
+
+        let view = app.ui_state.bulk_progress_mut().unwrap();
+        assert_eq!(
+            view.state("web-1-id"),
+            Some(&BulkItemState::Failed("Quota exceeded".to_string()))
+        );
+        assert_eq!(view.state("web-2-id"), Some(&BulkItemState::Queued));
+        assert_eq!(app.bulk_action.as_ref().unwrap().failed, 1);
+    }
+
+    #[tokio::test]
+    async fn busy_instances_are_not_started_by_a_bulk_action() {
+        let mut app = app_with_marked(Config::default());
+        for id in ["web-1-id", "web-2-id"] {
+            app.actions_in_flight.insert(id.to_string(), Action::Start);
+        }
+
+        let instance_ids = app.ui_state.marked_instance_ids();
+        app.start_bulk_action(Action::Stop, instance_ids).await;
+
+        assert!(app.bulk_action.is_none());
+        assert!(!app.ui_state.has_marked());
+        let view = app.ui_state.bulk_progress_mut().unwrap();
+        assert!(view.is_open());
+        assert_eq!(
+            view.state("web-2-id"),
+            Some(&BulkItemState::Failed(
+                "another action is running".to_string()
+            ))
+        );
+        let toast = app.ui_state.notifications_mut().toasts().last().unwrap();
+        assert_eq!(toast.message, "Bulk stop: 0 of 2 instance(s) succeeded");
+    }
+
+    #[test]
+    fn ssh_keeps_the_zone_of_the_listed_instance() {
+        let instance = Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium");
+        let mut app = test_app(Config::default(), vec![instance]);
+
+        app.request_ssh();
+
+        let pending = app.pending_ssh.as_ref().unwrap();
+        assert_eq!(pending.name, "web-1");
+        assert_eq!(pending.zone, "europe-west1-b");
     }
 }
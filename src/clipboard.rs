@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tracing::{debug, info};
+
+/// Clipboard tools tried in order, with their arguments
+const CLIPBOARD_TOOLS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Copy text to the system clipboard
+///
+/// Uses the first available clipboard tool, and falls back to the OSC 52
+/// terminal escape sequence, which also works over SSH in most terminals.
+pub fn copy(text: &str) -> Result<()> {
+    for (tool, args) in CLIPBOARD_TOOLS {
+        match copy_with_tool(tool, args, text) {
+            Ok(()) => {
+                info!("Copied {} bytes to the clipboard with {}", text.len(), tool);
+                return Ok(());
+            }
+            Err(e) => debug!("Clipboard tool {} unavailable: {:#}", tool, e),
+        }
+    }
+
+    copy_with_osc52(text)
+}
+
+/// Copy text by piping it to a clipboard tool
+fn copy_with_tool(tool: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to execute {}", tool))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("{} exited with {}", tool, status));
+    }
+
+    Ok(())
+}
+
+/// Copy text with the OSC 52 terminal escape sequence
+fn copy_with_osc52(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()?;
+
+    info!("Copied {} bytes to the clipboard with OSC 52", text.len());
+    Ok(())
+}
+
+/// Encode bytes as standard base64
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use regex::Regex;
+use serde::Deserialize;
 use std::process::Command;
-use tracing::debug; //, info, warn};
+use tracing::{debug, info};
+
+use crate::error::GciError;
 
 /// Get the default project ID from gcloud config
 pub fn get_default_project() -> Result<String> {
@@ -22,13 +25,134 @@ pub fn get_default_project() -> Result<String> {
 
     // Check if project ID is empty
     if project_id.is_empty() {
-        return Err(anyhow::anyhow!("No default project found. Please set a default project with 'gcloud config set project PROJECT_ID' or specify a project with --project"));
+        return Err(GciError::NoDefaultProject.into());
     }
 
     debug!("Default project: {}", project_id);
     Ok(project_id)
 }
 
+/// Get the default compute region from gcloud config, if set
+pub fn get_default_region() -> Result<Option<String>> {
+    let output = Command::new("gcloud")
+        .args(["config", "get-value", "compute/region"])
+        .output()
+        .context("Failed to execute gcloud config get-value compute/region command")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to get default region: {}", error));
+    }
+
+    let region = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    debug!("Default region: {:?}", region);
+    Ok(Some(region).filter(|region| !region.is_empty()))
+}
+
+/// Get the default compute zone from gcloud config, if set
+pub fn get_default_zone() -> Result<Option<String>> {
+    let output = Command::new("gcloud")
+        .args(["config", "get-value", "compute/zone"])
+        .output()
+        .context("Failed to execute gcloud config get-value compute/zone command")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to get default zone: {}", error));
+    }
+
+    let zone = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    debug!("Default zone: {:?}", zone);
+    Ok(Some(zone).filter(|zone| !zone.is_empty()))
+}
+
+/// gcloud login flow, to get new credentials once they expired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginKind {
+    /// Credentials of the gcloud CLI, used by every command
+    User,
+    /// Application Default Credentials, used by client libraries
+    ApplicationDefault,
+}
+
+impl LoginKind {
+    /// Get the gcloud arguments of the login flow
+    fn args(&self) -> &'static [&'static str] {
+        match self {
+            Self::User => &["auth", "login"],
+            Self::ApplicationDefault => &["auth", "application-default", "login"],
+        }
+    }
+
+    /// Get the command line of the login flow, to show to the user
+    pub fn command_line(&self) -> String {
+        format!("gcloud {}", self.args().join(" "))
+    }
+}
+
+/// Run a gcloud login flow, interacting with the user in the terminal
+///
+/// The flow opens a browser or prints a URL and waits for a code, so the
+/// terminal must be released by the UI beforehand.
+pub fn login(kind: LoginKind) -> Result<()> {
+    let status = Command::new("gcloud")
+        .args(kind.args())
+        .status()
+        .with_context(|| format!("Failed to execute {}", kind.command_line()))?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "{} failed ({})",
+            kind.command_line(),
+            status
+        ));
+    }
+    Ok(())
+}
+
+/// Minimum gcloud CLI version known to support the commands and JSON output used by g1c
+pub const MIN_GCLOUD_VERSION: GcloudVersion = GcloudVersion {
+    major: 400,
+    minor: 0,
+    patch: 0,
+};
+
+/// Version of the gcloud CLI (Google Cloud SDK)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GcloudVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GcloudVersion {
+    /// Parse a version string such as "450.0.1", missing components default to 0
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.trim().split('.').map(|part| part.parse::<u32>());
+
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    /// Check if this version is at least the minimum supported version
+    pub fn is_supported(&self) -> bool {
+        *self >= MIN_GCLOUD_VERSION
+    }
+}
+
+impl std::fmt::Display for GcloudVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 // Get the version of the gcloud CLI
 pub fn get_gcloud_version() -> Result<String> {
     // Run gcloud --version command
@@ -44,51 +168,142 @@ pub fn get_gcloud_version() -> Result<String> {
 
     // Parse output to extract Google Cloud SDK version
     let version_output = String::from_utf8_lossy(&output.stdout);
-    let re = Regex::new(r"Google Cloud SDK ([\d.]+)").unwrap();
-
-    if let Some(captures) = re.captures(&version_output) {
-        if let Some(version) = captures.get(1) {
-            return Ok(version.as_str().to_string());
-        }
+    if let Some(version) = parse_sdk_version(&version_output) {
+        return Ok(version);
     }
 
-    // Return a simplified version if regex fails
+    // Return a simplified version if parsing fails
     Ok("Unknown".to_string())
 }
 
-// Check if gcloud CLI is installed and authenticated
-// pub fn check_gcloud() -> Result<()> {
-//     // Check if gcloud is installed
-//     let output = Command::new("gcloud")
-//         .arg("--version")
-//         .output()
-//         .context("Failed to execute gcloud --version command. Is gcloud CLI installed?")?;
+/// Extract the SDK version from gcloud --version output
+///
+/// The output lists one component per line (SDK, alpha, beta, bq, ...), only
+/// the "Google Cloud SDK" line carries the CLI version.
+fn parse_sdk_version(version_output: &str) -> Option<String> {
+    let re = Regex::new(r"^Google Cloud SDK\s+([\d.]+)").unwrap();
 
-//     if !output.status.success() {
-//         let error = String::from_utf8_lossy(&output.stderr);
-//         return Err(anyhow::anyhow!("gcloud CLI check failed: {}", error));
-//     }
+    version_output
+        .lines()
+        .find_map(|line| re.captures(line.trim()))
+        .and_then(|captures| captures.get(1))
+        .map(|version| version.as_str().trim_end_matches('.').to_string())
+}
 
-//     // Check if user is authenticated
-//     let output = Command::new("gcloud")
-//         .args(["auth", "list"])
-//         .output()
-//         .context("Failed to execute gcloud auth list command")?;
+/// Get the active account from gcloud auth list
+pub fn get_active_account() -> Result<Option<String>> {
+    // Run gcloud auth list command
+    let output = Command::new("gcloud")
+        .args(["auth", "list"])
+        .output()
+        .context("Failed to execute gcloud auth list command")?;
 
-//     if !output.status.success() {
-//         let error = String::from_utf8_lossy(&output.stderr);
-//         return Err(anyhow::anyhow!("Authentication check failed: {}", error));
-//     }
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to list gcloud accounts: {}", error));
+    }
 
-//     let auth_output = String::from_utf8_lossy(&output.stdout);
-//     if !auth_output.contains("*") {
-//         warn!("No active gcloud account found. Please run 'gcloud auth login' to authenticate");
-//         return Err(anyhow::anyhow!("No active gcloud account found"));
-//     }
+    // The active account is the one marked with '*'
+    let auth_output = String::from_utf8_lossy(&output.stdout);
+    let account = parse_active_account(&auth_output);
 
-//     info!("gcloud CLI is installed and authenticated");
-//     Ok(())
-// }
+    debug!("Active account: {:?}", account);
+    Ok(account)
+}
+
+/// Account known to gcloud, from gcloud auth list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    /// Account email
+    pub email: String,
+    /// Whether gcloud commands run as this account
+    pub active: bool,
+}
+
+/// Account as listed by gcloud auth list --format json
+#[derive(Debug, Deserialize)]
+struct GcloudAccount {
+    account: String,
+    status: Option<String>,
+}
+
+/// List the accounts gcloud has credentials for, the active one flagged
+pub fn list_accounts() -> Result<Vec<Account>> {
+    let output = Command::new("gcloud")
+        .args(["auth", "list", "--format", "json"])
+        .output()
+        .context("Failed to execute gcloud auth list command")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to list gcloud accounts: {}", error));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let accounts: Vec<GcloudAccount> =
+        serde_json::from_str(&stdout).context("Failed to parse gcloud account list JSON")?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|account| Account {
+            active: account.status.as_deref() == Some("ACTIVE"),
+            email: account.account,
+        })
+        .collect())
+}
+
+/// Make an account the active one of the gcloud configuration
+///
+/// This changes the account of every gcloud command, including outside g1c.
+pub fn set_account(email: &str) -> Result<()> {
+    info!("Switching the active gcloud account to {}", email);
+
+    let output = Command::new("gcloud")
+        .args(["config", "set", "account", email])
+        .output()
+        .context("Failed to execute gcloud config set account command")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!(
+            "Failed to switch to account {}: {}",
+            email,
+            error.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Extract the '*'-marked account from gcloud auth list output
+fn parse_active_account(auth_output: &str) -> Option<String> {
+    auth_output
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix('*'))
+        .map(|account| account.trim().to_string())
+        .filter(|account| !account.is_empty())
+}
+
+/// Check that the gcloud CLI is installed, and has an active account if `require_account`
+///
+/// Run before starting the TUI, so a missing or unauthenticated gcloud is
+/// reported on the terminal rather than failing behind the alternate screen.
+/// No account is needed when a credentials file is configured.
+pub fn check_gcloud(require_account: bool) -> Result<()> {
+    get_gcloud_version()?;
+
+    if require_account && get_active_account()?.is_none() {
+        return Err(anyhow::anyhow!(
+            "No active gcloud account found. Run 'gcloud auth login' to authenticate"
+        ));
+    }
+
+    info!("gcloud CLI is installed and authenticated");
+    Ok(())
+}
 
 // Check if user has compute API access
 // pub fn check_compute_api(project_id: &str) -> Result<()> {
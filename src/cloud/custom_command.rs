@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use std::process::Stdio;
+use tracing::info;
+
+use super::gcloud;
+
+/// Output of a gcloud command typed by the user
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    /// Full command line, as run
+    pub command_line: String,
+    /// Whether the command exited successfully
+    pub success: bool,
+    /// Standard output
+    pub stdout: String,
+    /// Standard error
+    pub stderr: String,
+}
+
+/// gcloud verbs that only read resources, allowed in dry-run mode
+const READ_ONLY_VERBS: [&str; 8] = [
+    "list",
+    "describe",
+    "get-iam-policy",
+    "get-serial-port-output",
+    "get-guest-attributes",
+    "get-value",
+    "info",
+    "version",
+];
+
+/// Split a command typed by the user into arguments, honoring single and double quotes
+pub fn split_args(input: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote = None;
+
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(anyhow::anyhow!("Unterminated quote in command: {}", input));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Check if a gcloud command only reads resources, from its verbs
+fn is_read_only(args: &[String]) -> bool {
+    args.iter()
+        .take_while(|arg| !arg.starts_with('-'))
+        .any(|arg| READ_ONLY_VERBS.contains(&arg.as_str()))
+}
+
+/// Run a gcloud command typed by the user, e.g. "compute disks list"
+///
+/// A leading "gcloud" is optional, and the project is added unless given.
+/// In dry-run mode, only commands reading resources are run.
+pub async fn run_command(project_id: &str, input: &str) -> Result<CommandOutput> {
+    let mut args = split_args(input)?;
+    if args.first().is_some_and(|arg| arg == "gcloud") {
+        args.remove(0);
+    }
+    if args.is_empty() {
+        return Err(anyhow::anyhow!("No gcloud command to run"));
+    }
+
+    // Add the project unless given
+    let has_project = args
+        .iter()
+        .any(|arg| arg == "--project" || arg.starts_with("--project="));
+    if !has_project {
+        args.push("--project".to_string());
+        args.push(project_id.to_string());
+    }
+
+    let command_line = super::instances::format_command_line(&args);
+
+    if gcloud::is_dry_run() && !is_read_only(&args) {
+        info!("(dry-run) would run: {}", command_line);
+        return Ok(CommandOutput {
+            stdout: format!("(dry-run) would run: {}", command_line),
+            command_line,
+            success: true,
+            stderr: String::new(),
+        });
+    }
+
+    info!("Running gcloud command: {}", command_line);
+
+    // Never wait for an answer on stdin, the terminal belongs to the UI
+    let mut cmd = gcloud::command();
+    cmd.args(&args).stdin(Stdio::null());
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud command")?;
+
+    Ok(CommandOutput {
+        command_line,
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
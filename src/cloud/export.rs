@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::io::Write;
+
+use super::Instance;
+
+/// Header of the CSV export
+const CSV_HEADER: [&str; 7] = [
+    "name",
+    "status",
+    "machine_type",
+    "zone",
+    "internal_ip",
+    "external_ip",
+    "creation_timestamp",
+];
+
+/// Write instances as pretty-printed JSON
+pub fn to_json(instances: &[Instance], mut writer: impl Write) -> Result<()> {
+    serde_json::to_writer_pretty(&mut writer, instances)
+        .context("Failed to serialize instances as JSON")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Write instances as CSV, with a header row and one row per instance
+pub fn to_csv(instances: &[Instance], mut writer: impl Write) -> Result<()> {
+    writeln!(writer, "{}", CSV_HEADER.join(","))?;
+
+    for instance in instances {
+        let fields = [
+            instance.name.as_str(),
+            instance.status.as_str(),
+            instance.machine_type.as_str(),
+            instance.zone.as_str(),
+            instance.internal_ip.as_deref().unwrap_or(""),
+            instance.external_ip.as_deref().unwrap_or(""),
+            instance.creation_timestamp.as_deref().unwrap_or(""),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Write instances as a YAML sequence
+pub fn to_yaml(instances: &[Instance], mut writer: impl Write) -> Result<()> {
+    let value = serde_json::to_value(instances).context("Failed to serialize instances")?;
+
+    let mut yaml = String::new();
+    yaml_block(&value, 0, &mut yaml);
+    writer.write_all(yaml.as_bytes())?;
+    Ok(())
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Write a value as a YAML block at the given indentation
+fn yaml_block(value: &Value, indent: usize, out: &mut String) {
+    let padding = " ".repeat(indent);
+
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                if is_yaml_collection(item) {
+                    // Start the nested block on the same line as the dash
+                    let mut nested = String::new();
+                    yaml_block(item, indent + 2, &mut nested);
+                    out.push_str(&padding);
+                    out.push_str("- ");
+                    out.push_str(&nested[indent + 2..]);
+                } else {
+                    out.push_str(&padding);
+                    out.push('-');
+                    yaml_nested(item, indent + 2, out);
+                }
+            }
+        }
+        Value::Object(map) if !map.is_empty() => {
+            for (key, item) in map {
+                out.push_str(&padding);
+                out.push_str(&yaml_string(key));
+                out.push(':');
+                yaml_nested(item, indent + 2, out);
+            }
+        }
+        scalar => {
+            out.push_str(&padding);
+            out.push_str(&yaml_scalar(scalar));
+            out.push('\n');
+        }
+    }
+}
+
+/// Write a value following a "key:" or "-" marker
+fn yaml_nested(value: &Value, indent: usize, out: &mut String) {
+    if is_yaml_collection(value) {
+        out.push('\n');
+        yaml_block(value, indent, out);
+    } else {
+        out.push(' ');
+        out.push_str(&yaml_scalar(value));
+        out.push('\n');
+    }
+}
+
+/// Check if a value is written as a nested YAML block
+fn is_yaml_collection(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Format a scalar (or empty collection) as YAML
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => yaml_string(s),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+/// Format a string as YAML, quoting it unless it is unambiguous as a plain scalar
+fn yaml_string(s: &str) -> String {
+    let is_plain = s
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '@'))
+        && !matches!(
+            s.to_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off"
+        );
+
+    if is_plain {
+        s.to_string()
+    } else {
+        // Double-quoted YAML strings use the same escapes as JSON
+        Value::String(s.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write_csv(instances: &[Instance]) -> String {
+        let mut out = Vec::new();
+        to_csv(instances, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_has_a_header_and_a_row_per_instance() {
+        let mut web = Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium");
+        web.internal_ip = Some("10.0.0.2".to_string());
+        web.external_ip = Some("34.1.2.3".to_string());
+        web.creation_timestamp = Some("2024-01-02T03:04:05.000-07:00".to_string());
+        let batch = Instance::for_test("batch", "TERMINATED", "europe-west1-c", "n2-standard-8");
+
+        assert_eq!(
+            write_csv(&[web, batch]),
+            "name,status,machine_type,zone,internal_ip,external_ip,creation_timestamp\n\
+             web-1,RUNNING,e2-medium,europe-west1-b,10.0.0.2,34.1.2.3,2024-01-02T03:04:05.000-07:00\n\
+             batch,TERMINATED,n2-standard-8,europe-west1-c,,,\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators_quotes_or_line_breaks() {
+        let instance = Instance::for_test("say \"hi\", then\nbye", "RUNNING", "zone", "e2-micro");
+
+        assert_eq!(
+            write_csv(&[instance]).lines().nth(1).unwrap(),
+            "\"say \"\"hi\"\", then"
+        );
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(csv_field("a\rb"), "\"a\rb\"");
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use super::gcloud;
+
+/// Firewall rule applying to an instance network interface
+#[derive(Debug, Clone)]
+pub struct FirewallRule {
+    /// Rule name, or the policy name for firewall policy rules
+    pub name: String,
+    /// Traffic direction (INGRESS or EGRESS)
+    pub direction: String,
+    /// Rule priority, lower values take precedence
+    pub priority: Option<i64>,
+    /// Whether the rule allows (true) or denies (false) traffic
+    pub allow: bool,
+    /// Protocols and ports (e.g., tcp:22,80)
+    pub protocols: Vec<String>,
+    /// Source ranges for ingress rules, destination ranges for egress rules
+    pub ranges: Vec<String>,
+    /// Target network tags, empty when the rule applies to all instances
+    pub target_tags: Vec<String>,
+    /// Whether the rule is disabled
+    pub disabled: bool,
+}
+
+/// Output of gcloud compute instances network-interfaces get-effective-firewalls
+#[derive(Debug, Clone, Deserialize)]
+struct EffectiveFirewalls {
+    firewalls: Option<Vec<GcloudFirewall>>,
+    #[serde(rename = "firewallPolicys")]
+    firewall_policies: Option<Vec<GcloudFirewallPolicy>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudFirewall {
+    name: Option<String>,
+    direction: Option<String>,
+    priority: Option<i64>,
+    allowed: Option<Vec<GcloudPermission>>,
+    denied: Option<Vec<GcloudPermission>>,
+    #[serde(rename = "sourceRanges")]
+    source_ranges: Option<Vec<String>>,
+    #[serde(rename = "destinationRanges")]
+    destination_ranges: Option<Vec<String>>,
+    #[serde(rename = "targetTags")]
+    target_tags: Option<Vec<String>>,
+    disabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudPermission {
+    #[serde(rename = "IPProtocol", alias = "ipProtocol")]
+    ip_protocol: Option<String>,
+    ports: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudFirewallPolicy {
+    name: Option<String>,
+    rules: Option<Vec<GcloudPolicyRule>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudPolicyRule {
+    action: Option<String>,
+    direction: Option<String>,
+    priority: Option<i64>,
+    #[serde(rename = "match")]
+    rule_match: Option<GcloudPolicyMatch>,
+    #[serde(rename = "targetSecureTags")]
+    target_secure_tags: Option<Vec<GcloudSecureTag>>,
+    disabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudPolicyMatch {
+    #[serde(rename = "srcIpRanges")]
+    src_ip_ranges: Option<Vec<String>>,
+    #[serde(rename = "destIpRanges")]
+    dest_ip_ranges: Option<Vec<String>>,
+    #[serde(rename = "layer4Configs")]
+    layer4_configs: Option<Vec<GcloudPermission>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudSecureTag {
+    name: Option<String>,
+}
+
+impl From<GcloudFirewall> for FirewallRule {
+    fn from(firewall: GcloudFirewall) -> Self {
+        let direction = firewall.direction.unwrap_or_else(|| "INGRESS".to_string());
+        let (allow, permissions) = match (firewall.allowed, firewall.denied) {
+            (Some(allowed), _) => (true, allowed),
+            (None, Some(denied)) => (false, denied),
+            (None, None) => (true, Vec::new()),
+        };
+        let ranges = if direction == "EGRESS" {
+            firewall.destination_ranges
+        } else {
+            firewall.source_ranges
+        };
+
+        Self {
+            name: firewall.name.unwrap_or_else(|| "-".to_string()),
+            direction,
+            priority: firewall.priority,
+            allow,
+            protocols: permissions.iter().map(format_permission).collect(),
+            ranges: ranges.unwrap_or_default(),
+            target_tags: firewall.target_tags.unwrap_or_default(),
+            disabled: firewall.disabled.unwrap_or(false),
+        }
+    }
+}
+
+impl GcloudPolicyRule {
+    /// Convert a firewall policy rule, skipping "goto_next" pass-through rules
+    fn into_rule(self, policy_name: &str) -> Option<FirewallRule> {
+        let action = self.action.unwrap_or_default();
+        let allow = match action.as_str() {
+            "allow" => true,
+            "deny" => false,
+            _ => return None,
+        };
+
+        let direction = self.direction.unwrap_or_else(|| "INGRESS".to_string());
+        let rule_match = self.rule_match;
+        let ranges = rule_match.as_ref().and_then(|m| {
+            if direction == "EGRESS" {
+                m.dest_ip_ranges.clone()
+            } else {
+                m.src_ip_ranges.clone()
+            }
+        });
+        let protocols = rule_match
+            .and_then(|m| m.layer4_configs)
+            .unwrap_or_default()
+            .iter()
+            .map(format_permission)
+            .collect();
+
+        Some(FirewallRule {
+            name: format!("{} (policy)", policy_name),
+            direction,
+            priority: self.priority,
+            allow,
+            protocols,
+            ranges: ranges.unwrap_or_default(),
+            target_tags: self
+                .target_secure_tags
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|tag| tag.name)
+                .collect(),
+            disabled: self.disabled.unwrap_or(false),
+        })
+    }
+}
+
+/// Format a protocol and its ports (e.g., tcp:22,80)
+fn format_permission(permission: &GcloudPermission) -> String {
+    let protocol = permission.ip_protocol.as_deref().unwrap_or("all");
+    match &permission.ports {
+        Some(ports) if !ports.is_empty() => format!("{}:{}", protocol, ports.join(",")),
+        _ => protocol.to_string(),
+    }
+}
+
+/// Get the firewall rules effectively applied to an instance network interface
+///
+/// Rules are sorted by direction, then by priority.
+pub async fn get_effective_firewalls(
+    project_id: &str,
+    instance_name: &str,
+    zone: &str,
+) -> Result<Vec<FirewallRule>> {
+    info!(
+        "Getting effective firewalls for instance {} in project {}",
+        instance_name, project_id
+    );
+
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args([
+        "compute",
+        "instances",
+        "network-interfaces",
+        "get-effective-firewalls",
+        instance_name,
+        "--zone",
+        zone,
+        "--project",
+        project_id,
+        "--format",
+        "json",
+    ]);
+
+    // Execute command
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud get-effective-firewalls command")?;
+
+    // Check if command was successful
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        if let Some(e) = gcloud::impersonation_error(&error) {
+            return Err(e);
+        }
+        if error.contains("SERVICE_DISABLED") {
+            return Err(anyhow::anyhow!(
+                "Compute Engine API is not enabled for project {}",
+                project_id
+            ));
+        }
+        if is_permission_denied(&error) {
+            return Err(anyhow::anyhow!(
+                "Permission denied reading firewall rules for project {}. The compute.instances.getEffectiveFirewalls and compute.firewalls.list permissions are required",
+                project_id
+            ));
+        }
+        return Err(gcloud::command_error(
+            "Failed to get effective firewalls",
+            &error,
+        ));
+    }
+
+    // No rules may produce no output at all
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Parse JSON output
+    let effective: EffectiveFirewalls =
+        serde_json::from_str(&stdout).context("Failed to parse effective firewalls JSON")?;
+
+    let mut rules: Vec<FirewallRule> = effective
+        .firewalls
+        .unwrap_or_default()
+        .into_iter()
+        .map(FirewallRule::from)
+        .collect();
+
+    for policy in effective.firewall_policies.unwrap_or_default() {
+        let policy_name = policy.name.unwrap_or_else(|| "-".to_string());
+        rules.extend(
+            policy
+                .rules
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|rule| rule.into_rule(&policy_name)),
+        );
+    }
+
+    rules.sort_by(|a, b| {
+        a.direction
+            .cmp(&b.direction)
+            .reverse()
+            .then(a.priority.cmp(&b.priority))
+    });
+
+    debug!("Found {} effective firewall rules", rules.len());
+
+    Ok(rules)
+}
+
+/// Check whether a gcloud error means the caller lacks permissions
+fn is_permission_denied(error: &str) -> bool {
+    error.contains("PERMISSION_DENIED") || error.contains("Required '") || error.contains("403")
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tracing::info;
+
+use crate::error::GciError;
+
+/// Service account impersonated by every gcloud API command, set once at startup
+static IMPERSONATE_SERVICE_ACCOUNT: OnceLock<String> = OnceLock::new();
+
+/// Credentials file used by every gcloud API command, set once at startup
+static CREDENTIALS_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// stderr patterns of gcloud commands failing because the credentials expired, lowercase
+const AUTH_EXPIRED_PATTERNS: [&str; 4] = [
+    "reauthentication required",
+    "problem refreshing your current auth tokens",
+    "invalid_grant",
+    "invalid_rapt",
+];
+
+/// Whether commands changing cloud resources are only logged
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Only log the commands changing cloud resources instead of running them
+pub fn set_dry_run(dry_run: bool) {
+    if dry_run {
+        info!("Dry-run mode: commands changing cloud resources are only logged");
+    }
+    DRY_RUN.store(dry_run, Ordering::Relaxed);
+}
+
+/// Check if commands changing cloud resources are only logged
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// Impersonate a service account in every gcloud command built afterwards
+///
+/// Only the first service account is kept, as the client can be created again
+/// from the same configuration (e.g. when retrying without a project).
+pub fn set_impersonate_service_account(email: &str) -> Result<()> {
+    validate_service_account(email)?;
+
+    if IMPERSONATE_SERVICE_ACCOUNT.set(email.to_string()).is_ok() {
+        info!("Impersonating service account {}", email);
+    }
+    Ok(())
+}
+
+/// Get the impersonated service account, if any
+pub fn impersonated_service_account() -> Option<&'static str> {
+    IMPERSONATE_SERVICE_ACCOUNT.get().map(String::as_str)
+}
+
+/// Check that a service account email looks like `name@project.iam.gserviceaccount.com`
+fn validate_service_account(email: &str) -> Result<()> {
+    let re = Regex::new(r"^[a-z0-9][a-z0-9-]*@[a-z0-9][a-z0-9.-]*\.gserviceaccount\.com$").unwrap();
+
+    if re.is_match(email) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid service account to impersonate: {}. Expected an email such as name@project-id.iam.gserviceaccount.com",
+            email
+        ))
+    }
+}
+
+/// Authenticate every gcloud command built afterwards with a credentials file
+///
+/// The file is usually a service account key. Like the impersonated service
+/// account, only the first file is kept.
+pub fn set_credentials_file(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(anyhow::anyhow!(
+            "Credentials file {} does not exist. Fix credentials_path in the configuration",
+            path.display()
+        ));
+    }
+
+    if CREDENTIALS_FILE.set(path.to_path_buf()).is_ok() {
+        info!("Using credentials file {}", path.display());
+    }
+    Ok(())
+}
+
+/// Get the global flags added to every gcloud API command
+pub fn global_args() -> Vec<String> {
+    impersonated_service_account()
+        .map(|email| format!("--impersonate-service-account={}", email))
+        .into_iter()
+        .collect()
+}
+
+/// Create a gcloud command calling Google Cloud APIs, with the global flags
+///
+/// Commands only reading the local gcloud configuration (config get-value,
+/// auth list, --version) don't need credentials and use `Command` directly.
+pub fn command() -> Command {
+    let mut cmd = Command::new("gcloud");
+    cmd.args(global_args());
+
+    // gcloud reads its own override, client libraries the application default
+    if let Some(path) = CREDENTIALS_FILE.get() {
+        cmd.env("CLOUDSDK_AUTH_CREDENTIAL_FILE_OVERRIDE", path)
+            .env("GOOGLE_APPLICATION_CREDENTIALS", path);
+    }
+    cmd
+}
+
+/// Run a gcloud command on the blocking thread pool, capturing its output
+///
+/// `Command::output` blocks its thread until gcloud exits, which would hold a
+/// runtime worker and stall the other tasks, the UI loop included, while
+/// listings, actions or operation polls run. Every gcloud command run from an
+/// async function goes through here.
+pub async fn output(mut cmd: Command) -> Result<Output> {
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let output = tokio::task::spawn_blocking(move || cmd.output())
+        .await
+        .context("gcloud command task failed")??;
+    Ok(output)
+}
+
+/// Run a gcloud command changing cloud resources, e.g. `description` "start instance"
+///
+/// In dry-run mode, the command line is logged and the command succeeds without
+/// being run, so every mutating action goes through this single gate.
+pub async fn run_mutating(cmd: Command, description: &str) -> Result<()> {
+    run_mutating_output(cmd, description).await.map(|_| ())
+}
+
+/// Run a gcloud command changing cloud resources, and get its standard output
+///
+/// Returns `None` in dry-run mode, where the command is only logged.
+pub async fn run_mutating_output(cmd: Command, description: &str) -> Result<Option<String>> {
+    if is_dry_run() {
+        let args: Vec<_> = cmd.get_args().map(|arg| arg.to_string_lossy()).collect();
+        info!("(dry-run) would run: gcloud {}", args.join(" "));
+        return Ok(None);
+    }
+
+    // Execute command
+    let output = output(cmd).await.context(format!(
+        "Failed to execute gcloud command to {}",
+        description
+    ))?;
+
+    // Check if command was successful
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(command_error(&format!("Failed to {}", description), &error));
+    }
+
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// Build the error of a failed gcloud command from its stderr
+pub fn command_error(context: &str, stderr: &str) -> anyhow::Error {
+    if let Some(error) = impersonation_error(stderr) {
+        return error;
+    }
+
+    let message = format!("{}: {}", context, stderr);
+    if is_auth_expired(stderr) {
+        GciError::AuthExpired(message).into()
+    } else {
+        anyhow::anyhow!(message)
+    }
+}
+
+/// Check if a command failed because the credentials expired and a new login is needed
+fn is_auth_expired(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    AUTH_EXPIRED_PATTERNS
+        .iter()
+        .any(|pattern| stderr.contains(pattern))
+}
+
+/// Get a clear error if the command failed because the impersonation was denied
+///
+/// gcloud reports these as a generic permission error, easily mistaken for a
+/// missing compute permission of the impersonated service account itself.
+pub fn impersonation_error(stderr: &str) -> Option<anyhow::Error> {
+    let email = impersonated_service_account()?;
+
+    let denied = stderr.contains("Failed to impersonate")
+        || stderr.contains("iam.serviceAccounts.getAccessToken");
+    denied.then(|| {
+        anyhow::anyhow!(
+            "Failed to impersonate service account {}. The active account needs the Service Account Token Creator role (roles/iam.serviceAccountTokenCreator) on it",
+            email
+        )
+    })
+}
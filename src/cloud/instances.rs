@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::process::{Command, Stdio};
-use tracing::{debug, info};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::gcloud;
+use super::operations;
+use super::pricing;
 
 /// Instance model representing a Google Cloud VM instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +15,9 @@ pub struct Instance {
     pub id: String,
     /// Instance name
     pub name: String,
+    /// Project the instance belongs to
+    #[serde(default)]
+    pub project: String,
     /// Instance status
     pub status: String,
     /// Machine type (e.g., e2-micro, n1-standard-1)
@@ -23,14 +30,180 @@ pub struct Instance {
     pub internal_ip: Option<String>,
     /// Network name
     pub network: Option<String>,
+    /// Network interfaces, in the instance's order (nic0, nic1...)
+    pub network_interfaces: Vec<NetworkInterfaceInfo>,
     /// Creation timestamp
     pub creation_timestamp: Option<String>,
     /// Description
     pub description: Option<String>,
     /// Metadata as key-value pairs
     pub metadata: Option<HashMap<String, String>>,
+    /// Labels as key-value pairs, e.g. for cost or ownership
+    #[serde(default)]
+    pub labels: Option<HashMap<String, String>>,
     /// Tags
     pub tags: Vec<String>,
+    /// Attached disks
+    pub disks: Vec<DiskInfo>,
+    /// Attached GPUs and other accelerators
+    #[serde(default)]
+    pub accelerators: Vec<AcceleratorInfo>,
+    /// Whether the instance can be preempted, as a preemptible or spot VM
+    #[serde(default)]
+    pub preemptible: bool,
+    /// Provisioning model (e.g., STANDARD, SPOT), if reported
+    #[serde(default)]
+    pub provisioning_model: Option<String>,
+    /// Creator of the instance, from labels or metadata
+    pub owner: Option<String>,
+    /// Shielded VM settings, if configured
+    pub shielded: Option<ShieldedConfig>,
+}
+
+/// Disk attached to an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    /// Device name inside the instance
+    pub device_name: String,
+    /// Disk size in GB, if known
+    pub size_gb: Option<u64>,
+    /// Disk type (e.g., PERSISTENT, SCRATCH)
+    pub disk_type: String,
+    /// Whether this is the boot disk
+    pub boot: bool,
+}
+
+/// Accelerator attached to an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceleratorInfo {
+    /// Accelerator type (e.g., nvidia-tesla-t4)
+    pub accelerator_type: String,
+    /// Number of accelerators of this type
+    pub count: u32,
+}
+
+/// Network interface of an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkInterfaceInfo {
+    /// Interface name (e.g., nic0)
+    pub name: String,
+    /// Network name
+    pub network: Option<String>,
+    /// Subnetwork name
+    pub subnetwork: Option<String>,
+    /// Internal IP address
+    pub internal_ip: Option<String>,
+    /// External IP address, if any
+    pub external_ip: Option<String>,
+    /// Stack type (e.g., IPV4_ONLY, IPV4_IPV6)
+    pub stack_type: Option<String>,
+}
+
+/// Shielded VM settings of an instance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ShieldedConfig {
+    /// Whether Secure Boot is enabled
+    pub secure_boot: bool,
+    /// Whether the virtual Trusted Platform Module is enabled
+    pub vtpm: bool,
+    /// Whether integrity monitoring is enabled
+    pub integrity_monitoring: bool,
+}
+
+/// Label and metadata keys commonly used to record who created an instance
+const OWNER_KEYS: [&str; 4] = ["created-by", "creator", "owner", "created_by"];
+
+impl Instance {
+    /// Create an instance with the given name, status, zone and machine type, for tests
+    #[cfg(test)]
+    pub fn for_test(name: &str, status: &str, zone: &str, machine_type: &str) -> Self {
+        Self {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            project: "my-project".to_string(),
+            status: status.to_string(),
+            machine_type: machine_type.to_string(),
+            zone: zone.to_string(),
+            external_ip: None,
+            internal_ip: None,
+            network: None,
+            network_interfaces: Vec::new(),
+            creation_timestamp: None,
+            description: None,
+            metadata: None,
+            labels: None,
+            tags: Vec::new(),
+            disks: Vec::new(),
+            accelerators: Vec::new(),
+            preemptible: false,
+            provisioning_model: None,
+            owner: None,
+            shielded: None,
+        }
+    }
+
+    /// Check if the instance was created by the given account
+    ///
+    /// Label values cannot contain '@' or '.', so owners recorded as labels are
+    /// compared against a normalized form of the account email and its user name.
+    pub fn is_owned_by(&self, account: &str) -> bool {
+        let Some(owner) = &self.owner else {
+            return false;
+        };
+
+        let owner = normalize_owner(owner);
+        let user = account.split('@').next().unwrap_or(account);
+        owner == normalize_owner(account) || owner == normalize_owner(user)
+    }
+
+    /// Check if any network interface has an external IP, i.e. the instance is reachable from the internet
+    pub fn has_external_ip(&self) -> bool {
+        self.external_ip.is_some()
+            || self
+                .network_interfaces
+                .iter()
+                .any(|iface| iface.external_ip.is_some())
+    }
+
+    /// Get how long a RUNNING instance has been up, from its creation timestamp
+    ///
+    /// Returns `None` for instances that are not running, or whose timestamp is
+    /// missing, malformed or in the future.
+    pub fn uptime(&self) -> Option<Duration> {
+        if self.status != "RUNNING" {
+            return None;
+        }
+
+        let created =
+            chrono::DateTime::parse_from_rfc3339(self.creation_timestamp.as_deref()?).ok()?;
+        chrono::Local::now()
+            .signed_duration_since(created)
+            .to_std()
+            .ok()
+    }
+
+    /// Estimate the on-demand hourly cost of a RUNNING instance, in USD
+    ///
+    /// Returns `None` for instances that are not running, whose machine type or
+    /// region is not priced, or that are preemptible, as spot prices vary.
+    pub fn estimated_hourly_usd(&self) -> Option<f64> {
+        if self.status != "RUNNING" || self.preemptible {
+            return None;
+        }
+
+        let (region, _) = self.zone.rsplit_once('-')?;
+        pricing::hourly_usd(&self.machine_type, region)
+    }
+
+    /// Get the total size of all attached disks in GB
+    ///
+    /// Returns `None` when no disk reports a size.
+    pub fn total_disk_size_gb(&self) -> Option<u64> {
+        self.disks
+            .iter()
+            .filter_map(|disk| disk.size_gb)
+            .reduce(|total, size| total + size)
+    }
 }
 
 /// Simplified model for instance data coming from gcloud CLI
@@ -49,16 +222,27 @@ struct GcloudInstance {
     description: Option<String>,
     metadata: Option<InstanceMetadata>,
     tags: Option<Tags>,
+    disks: Option<Vec<AttachedDisk>>,
+    #[serde(rename = "guestAccelerators")]
+    guest_accelerators: Option<Vec<GuestAccelerator>>,
+    labels: Option<HashMap<String, String>>,
+    #[serde(rename = "shieldedInstanceConfig")]
+    shielded_instance_config: Option<ShieldedInstanceConfig>,
+    scheduling: Option<Scheduling>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct NetworkInterface {
+    name: Option<String>,
     #[serde(rename = "networkIP")]
     network_ip: Option<String>,
     #[serde(rename = "accessConfigs")]
     access_configs: Option<Vec<AccessConfig>>,
     #[serde(rename = "network")]
     network: Option<String>,
+    subnetwork: Option<String>,
+    #[serde(rename = "stackType")]
+    stack_type: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -83,39 +267,127 @@ struct Tags {
     items: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct AttachedDisk {
+    #[serde(rename = "deviceName")]
+    device_name: Option<String>,
+    // gcloud encodes int64 values as strings
+    #[serde(rename = "diskSizeGb")]
+    disk_size_gb: Option<serde_json::Value>,
+    #[serde(rename = "type")]
+    disk_type: Option<String>,
+    boot: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GuestAccelerator {
+    #[serde(rename = "acceleratorType")]
+    accelerator_type: String,
+    #[serde(rename = "acceleratorCount")]
+    accelerator_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Scheduling {
+    preemptible: Option<bool>,
+    #[serde(rename = "provisioningModel")]
+    provisioning_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ShieldedInstanceConfig {
+    #[serde(rename = "enableSecureBoot")]
+    enable_secure_boot: Option<bool>,
+    #[serde(rename = "enableVtpm")]
+    enable_vtpm: Option<bool>,
+    #[serde(rename = "enableIntegrityMonitoring")]
+    enable_integrity_monitoring: Option<bool>,
+}
+
+impl From<GuestAccelerator> for AcceleratorInfo {
+    fn from(accelerator: GuestAccelerator) -> Self {
+        Self {
+            accelerator_type: resource_name(&accelerator.accelerator_type, "acceleratorTypes"),
+            count: accelerator.accelerator_count.unwrap_or(1),
+        }
+    }
+}
+
+impl From<ShieldedInstanceConfig> for ShieldedConfig {
+    fn from(config: ShieldedInstanceConfig) -> Self {
+        Self {
+            secure_boot: config.enable_secure_boot.unwrap_or(false),
+            vtpm: config.enable_vtpm.unwrap_or(false),
+            integrity_monitoring: config.enable_integrity_monitoring.unwrap_or(false),
+        }
+    }
+}
+
+impl From<AttachedDisk> for DiskInfo {
+    fn from(disk: AttachedDisk) -> Self {
+        let size_gb = disk.disk_size_gb.and_then(|size| match size {
+            serde_json::Value::String(size) => size.parse().ok(),
+            serde_json::Value::Number(size) => size.as_u64(),
+            _ => None,
+        });
+
+        Self {
+            device_name: disk.device_name.unwrap_or_else(|| "-".to_string()),
+            size_gb,
+            disk_type: disk.disk_type.unwrap_or_else(|| "-".to_string()),
+            boot: disk.boot.unwrap_or(false),
+        }
+    }
+}
+
+impl From<NetworkInterface> for NetworkInterfaceInfo {
+    fn from(iface: NetworkInterface) -> Self {
+        // The first access config with a NAT IP is the external IP
+        let external_ip = iface
+            .access_configs
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|config| config.nat_ip);
+
+        // Extract the network and subnetwork names from their URLs
+        let network = iface
+            .network
+            .map(|network| resource_name(&network, "networks"));
+        let subnetwork = iface
+            .subnetwork
+            .map(|subnetwork| resource_name(&subnetwork, "subnetworks"));
+
+        Self {
+            name: iface.name.unwrap_or_default(),
+            network,
+            subnetwork,
+            internal_ip: iface.network_ip,
+            external_ip,
+            stack_type: iface.stack_type,
+        }
+    }
+}
+
 impl From<GcloudInstance> for Instance {
     fn from(gcloud_instance: GcloudInstance) -> Self {
-        let mut external_ip = None;
-        let mut internal_ip = None;
-        let mut network = None;
-
-        // Extract IP addresses from network interfaces
-        if let Some(network_interfaces) = gcloud_instance.network_interfaces {
-            for iface in network_interfaces {
-                // Internal IP
-                if let Some(ip) = iface.network_ip {
-                    internal_ip = Some(ip);
-                }
-
-                // External IP
-                if let Some(access_configs) = iface.access_configs {
-                    for config in access_configs {
-                        if let Some(nat_ip) = config.nat_ip {
-                            external_ip = Some(nat_ip);
-                            break;
-                        }
-                    }
-                }
-
-                // Network
-                if let Some(net) = &iface.network {
-                    // Extract the network name from the URL path
-                    if let Some(name) = net.split('/').last() {
-                        network = Some(name.to_string());
-                    }
-                }
-            }
-        }
+        let network_interfaces: Vec<NetworkInterfaceInfo> = gcloud_instance
+            .network_interfaces
+            .map(|ifaces| ifaces.into_iter().map(NetworkInterfaceInfo::from).collect())
+            .unwrap_or_default();
+
+        // The list shows the last interface with an address, as before multi-NIC support
+        let internal_ip = network_interfaces
+            .iter()
+            .rev()
+            .find_map(|iface| iface.internal_ip.clone());
+        let external_ip = network_interfaces
+            .iter()
+            .rev()
+            .find_map(|iface| iface.external_ip.clone());
+        let network = network_interfaces
+            .iter()
+            .rev()
+            .find_map(|iface| iface.network.clone());
 
         // Extract metadata
         let metadata = gcloud_instance.metadata.and_then(|meta| {
@@ -136,46 +408,163 @@ impl From<GcloudInstance> for Instance {
             .and_then(|tags| tags.items)
             .unwrap_or_default();
 
-        // Extract zone from zone URL
-        let zone = gcloud_instance
-            .zone
-            .split('/')
-            .last()
-            .unwrap_or("unknown")
-            .to_string();
-
-        // Extract machine type from machine type URL
-        let machine_type = gcloud_instance
-            .machine_type
-            .split('/')
-            .last()
-            .unwrap_or("unknown")
-            .to_string();
+        // Extract disks
+        let disks = gcloud_instance
+            .disks
+            .map(|disks| disks.into_iter().map(DiskInfo::from).collect())
+            .unwrap_or_default();
+
+        // Extract accelerators
+        let accelerators = gcloud_instance
+            .guest_accelerators
+            .map(|accelerators| {
+                accelerators
+                    .into_iter()
+                    .map(AcceleratorInfo::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Extract the scheduling, spot VMs being preemptible too
+        let (preemptible, provisioning_model) = match gcloud_instance.scheduling {
+            Some(scheduling) => (
+                scheduling.preemptible.unwrap_or(false)
+                    || scheduling.provisioning_model.as_deref() == Some("SPOT"),
+                scheduling.provisioning_model,
+            ),
+            None => (false, None),
+        };
+
+        // Extract the owner, labels take precedence over metadata
+        let owner = OWNER_KEYS.iter().find_map(|key| {
+            gcloud_instance
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(*key))
+                .or_else(|| metadata.as_ref().and_then(|metadata| metadata.get(*key)))
+                .filter(|owner| !owner.is_empty())
+                .cloned()
+        });
+
+        // Extract zone and machine type from their URLs
+        let zone = resource_name(&gcloud_instance.zone, "zones");
+        let machine_type = resource_name(&gcloud_instance.machine_type, "machineTypes");
 
         Self {
             id: gcloud_instance.id,
             name: gcloud_instance.name,
+            project: String::new(),
             status: gcloud_instance.status,
             machine_type,
             zone,
             external_ip,
             internal_ip,
             network,
+            network_interfaces,
             creation_timestamp: gcloud_instance.creation_timestamp,
             description: gcloud_instance.description,
             metadata,
+            labels: gcloud_instance.labels,
             tags,
+            disks,
+            accelerators,
+            preemptible,
+            provisioning_model,
+            owner,
+            shielded: gcloud_instance
+                .shielded_instance_config
+                .map(ShieldedConfig::from),
         }
     }
 }
 
-/// List all instances in a project
-pub async fn list_instances(project_id: &str, json_output: bool) -> Result<Vec<Instance>> {
+/// Extract a resource name from a full resource URL or a bare name
+///
+/// URLs are expected to end with `<collection>/<name>`. Empty values and other
+/// shapes are logged so parsing issues can be diagnosed.
+fn resource_name(value: &str, collection: &str) -> String {
+    let value = value.trim();
+    if value.is_empty() {
+        warn!("Missing {} in instance description", collection);
+        return "-".to_string();
+    }
+
+    // Already a bare name
+    if !value.contains('/') {
+        return value.to_string();
+    }
+
+    let mut segments = value.rsplit('/');
+    match (segments.next(), segments.next()) {
+        (Some(name), Some(parent)) if !name.is_empty() && parent == collection => name.to_string(),
+        _ => {
+            warn!(
+                "Unexpected {} URL in instance description: {}",
+                collection, value
+            );
+            value
+                .rsplit('/')
+                .find(|segment| !segment.is_empty())
+                .unwrap_or(value)
+                .to_string()
+        }
+    }
+}
+
+/// Normalize an owner or account for comparison, as label values are restricted
+/// to lowercase letters, digits, '-' and '_'
+fn normalize_owner(owner: &str) -> String {
+    owner
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// List all instances in a project, optionally scoped by a gcloud filter expression
+pub async fn list_instances(
+    project_id: &str,
+    filter: Option<&str>,
+    json_output: bool,
+) -> Result<Vec<Instance>> {
     info!("Listing instances for project: {}", project_id);
+    list_instances_in(project_id, None, filter, json_output).await
+}
 
+/// List the instances of a single zone in a project
+pub async fn list_zone_instances(
+    project_id: &str,
+    zone: &str,
+    filter: Option<&str>,
+    json_output: bool,
+) -> Result<Vec<Instance>> {
+    info!(
+        "Listing instances for project {} in zone {}",
+        project_id, zone
+    );
+    list_instances_in(project_id, Some(zone), filter, json_output).await
+}
+
+/// List the instances of a project, in a single zone or in all of them
+async fn list_instances_in(
+    project_id: &str,
+    zone: Option<&str>,
+    filter: Option<&str>,
+    json_output: bool,
+) -> Result<Vec<Instance>> {
     // Build command
-    let mut cmd = Command::new("gcloud");
+    let mut cmd = gcloud::command();
     cmd.args(["compute", "instances", "list", "--project", project_id]);
+    if let Some(zone) = zone {
+        cmd.args(["--zones", zone]);
+    }
+
+    // Add server-side filter
+    if let Some(filter) = filter {
+        debug!("Using list filter: {}", filter);
+        cmd.args(["--filter", filter]);
+    }
 
     // Add format flags
     if json_output {
@@ -183,25 +572,35 @@ pub async fn list_instances(project_id: &str, json_output: bool) -> Result<Vec<I
     }
 
     // Execute command
-    let output = cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    let output = gcloud::output(cmd)
+        .await
         .context("Failed to execute gcloud compute instances list command")?;
 
     // Check if command was successful
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Failed to list instances: {}", error));
+        return Err(gcloud::command_error("Failed to list instances", &error));
     }
 
-    // Parse JSON output
+    // A filter matching nothing may produce no output at all rather than an empty array
     let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        debug!("Found no instances");
+        return Ok(Vec::new());
+    }
+
+    // Parse JSON output
     let gcloud_instances: Vec<GcloudInstance> =
         serde_json::from_str(&stdout).context("Failed to parse instance list JSON")?;
 
     // Convert to our model
-    let instances: Vec<Instance> = gcloud_instances.into_iter().map(Instance::from).collect();
+    let instances: Vec<Instance> = gcloud_instances
+        .into_iter()
+        .map(|gcloud_instance| Instance {
+            project: project_id.to_string(),
+            ..Instance::from(gcloud_instance)
+        })
+        .collect();
 
     debug!("Found {} instances", instances.len());
 
@@ -217,7 +616,7 @@ pub async fn get_instance(
     info!("Getting instance {} in project {}", instance_id, project_id);
 
     // First we need to find which zone the instance is in
-    let instances = list_instances(project_id, json_output).await?;
+    let instances = list_instances(project_id, None, json_output).await?;
 
     // Find the instance by ID or name
     let instance = instances
@@ -226,14 +625,29 @@ pub async fn get_instance(
         .ok_or_else(|| anyhow::anyhow!("Instance not found: {}", instance_id))?;
 
     // Now get detailed information
-    let mut cmd = Command::new("gcloud");
+    describe_instance(project_id, &instance.name, &instance.zone, json_output).await
+}
+
+/// Describe an instance whose zone is already known
+pub async fn describe_instance(
+    project_id: &str,
+    instance_name: &str,
+    zone: &str,
+    json_output: bool,
+) -> Result<Instance> {
+    debug!(
+        "Describing instance {} in zone {} of project {}",
+        instance_name, zone, project_id
+    );
+
+    let mut cmd = gcloud::command();
     cmd.args([
         "compute",
         "instances",
         "describe",
-        &instance.name,
+        instance_name,
         "--zone",
-        &instance.zone,
+        zone,
         "--project",
         project_id,
     ]);
@@ -244,16 +658,17 @@ pub async fn get_instance(
     }
 
     // Execute command
-    let output = cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    let output = gcloud::output(cmd)
+        .await
         .context("Failed to execute gcloud compute instances describe command")?;
 
     // Check if command was successful
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Failed to get instance details: {}", error));
+        return Err(gcloud::command_error(
+            "Failed to get instance details",
+            &error,
+        ));
     }
 
     // Parse JSON output
@@ -261,128 +676,683 @@ pub async fn get_instance(
     let gcloud_instance: GcloudInstance =
         serde_json::from_str(&stdout).context("Failed to parse instance details JSON")?;
 
-    Ok(Instance::from(gcloud_instance))
+    Ok(Instance {
+        project: project_id.to_string(),
+        ..Instance::from(gcloud_instance)
+    })
 }
 
-/// Start an instance
-pub async fn start_instance(project_id: &str, instance_id: &str) -> Result<()> {
-    info!(
-        "Starting instance {} in project {}",
-        instance_id, project_id
+/// Get the complete gcloud describe output of an instance, as pretty-printed JSON
+pub async fn describe_instance_raw(
+    project_id: &str,
+    instance_name: &str,
+    zone: &str,
+) -> Result<String> {
+    debug!(
+        "Describing raw instance {} in zone {} of project {}",
+        instance_name, zone, project_id
     );
 
-    // First we need to find which zone the instance is in
-    let instance = get_instance(project_id, instance_id, true).await?;
-
-    // Build command
-    let mut cmd = Command::new("gcloud");
+    let mut cmd = gcloud::command();
     cmd.args([
         "compute",
         "instances",
-        "start",
-        &instance.name,
+        "describe",
+        instance_name,
         "--zone",
-        &instance.zone,
+        zone,
         "--project",
         project_id,
-        "--quiet", // Disable interactive prompts
+        "--format",
+        "json",
     ]);
 
     // Execute command
-    let output = cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .context("Failed to execute gcloud compute instances start command")?;
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud compute instances describe command")?;
 
     // Check if command was successful
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Failed to start instance: {}", error));
+        return Err(gcloud::command_error("Failed to describe instance", &error));
     }
 
-    info!("Successfully started instance {}", instance.name);
-    Ok(())
+    // Re-format the output, so the indentation doesn't depend on the gcloud version
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value: serde_json::Value =
+        serde_json::from_str(&stdout).context("Failed to parse instance details JSON")?;
+
+    serde_json::to_string_pretty(&value).context("Failed to format instance details JSON")
 }
 
-/// Stop an instance
-pub async fn stop_instance(project_id: &str, instance_id: &str) -> Result<()> {
-    info!(
-        "Stopping instance {} in project {}",
-        instance_id, project_id
+/// Get the serial port output of an instance, e.g. its boot messages
+pub async fn get_serial_output(
+    project_id: &str,
+    instance_name: &str,
+    zone: &str,
+) -> Result<String> {
+    debug!(
+        "Getting serial port output of instance {} in zone {} of project {}",
+        instance_name, zone, project_id
     );
 
-    // First we need to find which zone the instance is in
-    let instance = get_instance(project_id, instance_id, true).await?;
-
-    // Build command
-    let mut cmd = Command::new("gcloud");
+    let mut cmd = gcloud::command();
     cmd.args([
         "compute",
         "instances",
-        "stop",
-        &instance.name,
+        "get-serial-port-output",
+        instance_name,
         "--zone",
-        &instance.zone,
+        zone,
         "--project",
         project_id,
-        "--quiet", // Disable interactive prompts
     ]);
 
     // Execute command
-    let output = cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .context("Failed to execute gcloud compute instances stop command")?;
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud compute instances get-serial-port-output command")?;
 
     // Check if command was successful
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Failed to stop instance: {}", error));
+        return Err(gcloud::command_error(
+            "Failed to get serial port output",
+            &error,
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// gcloud command acting on a single instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceCommand {
+    Start,
+    Stop,
+    /// Reset is like a power cycle/restart
+    Reset,
+    Ssh,
+    /// Delete the instance, and its disks set to auto-delete
+    Delete,
+}
+
+impl InstanceCommand {
+    /// Get the gcloud compute subcommand
+    fn subcommand(&self) -> &'static [&'static str] {
+        match self {
+            InstanceCommand::Start => &["instances", "start"],
+            InstanceCommand::Stop => &["instances", "stop"],
+            InstanceCommand::Reset => &["instances", "reset"],
+            InstanceCommand::Ssh => &["ssh"],
+            InstanceCommand::Delete => &["instances", "delete"],
+        }
+    }
+}
+
+/// Build the gcloud arguments of a command acting on an instance
+pub fn instance_command_args(
+    command: InstanceCommand,
+    project_id: &str,
+    instance_name: &str,
+    zone: &str,
+) -> Vec<String> {
+    let mut args = vec!["compute".to_string()];
+    args.extend(command.subcommand().iter().map(|arg| arg.to_string()));
+    args.extend([
+        instance_name.to_string(),
+        "--zone".to_string(),
+        zone.to_string(),
+        "--project".to_string(),
+        project_id.to_string(),
+    ]);
+
+    // Disable interactive prompts, except for ssh which is interactive
+    if command != InstanceCommand::Ssh {
+        args.push("--quiet".to_string());
     }
 
-    info!("Successfully stopped instance {}", instance.name);
+    args
+}
+
+/// Build the gcloud arguments changing the machine type of an instance
+pub fn set_machine_type_args(
+    project_id: &str,
+    name: &str,
+    zone: &str,
+    machine_type: &str,
+) -> Vec<String> {
+    [
+        "compute",
+        "instances",
+        "set-machine-type",
+        name,
+        "--zone",
+        zone,
+        "--project",
+        project_id,
+        "--machine-type",
+        machine_type,
+        "--quiet", // Disable interactive prompts
+    ]
+    .map(str::to_string)
+    .to_vec()
+}
+
+/// Build the gcloud arguments adding or removing network tags, with "add-tags" or "remove-tags"
+pub fn tags_command_args(
+    subcommand: &str,
+    project_id: &str,
+    name: &str,
+    zone: &str,
+    tags: &[String],
+) -> Vec<String> {
+    [
+        "compute",
+        "instances",
+        subcommand,
+        name,
+        "--zone",
+        zone,
+        "--project",
+        project_id,
+        "--tags",
+        &tags.join(","),
+        "--quiet", // Disable interactive prompts
+    ]
+    .map(str::to_string)
+    .to_vec()
+}
+
+/// Build the gcloud arguments creating an instance
+pub fn create_command_args(project_id: &str, spec: &CreateSpec) -> Vec<String> {
+    [
+        "compute",
+        "instances",
+        "create",
+        &spec.name,
+        "--zone",
+        &spec.zone,
+        "--project",
+        project_id,
+        "--machine-type",
+        &spec.machine_type,
+        "--image-family",
+        &spec.image_family,
+        "--image-project",
+        &spec.image_project,
+        "--network",
+        &spec.network,
+        "--quiet", // Disable interactive prompts
+    ]
+    .map(str::to_string)
+    .to_vec()
+}
+
+/// Build the gcloud arguments of an interactive ssh session to an instance
+///
+/// Instances without an external IP are reached through Identity-Aware Proxy.
+pub fn ssh_command_args(
+    project_id: &str,
+    instance: &Instance,
+    agent_forwarding: bool,
+) -> Vec<String> {
+    let mut args = instance_command_args(
+        InstanceCommand::Ssh,
+        project_id,
+        &instance.name,
+        &instance.zone,
+    );
+    if !instance.has_external_ip() {
+        args.push("--tunnel-through-iap".to_string());
+    }
+    if agent_forwarding {
+        args.push("--ssh-flag=-A".to_string());
+    }
+    args
+}
+
+/// Format a gcloud command line with the global flags, quoting arguments for a POSIX shell
+pub fn format_command_line(args: &[String]) -> String {
+    let global_args = gcloud::global_args();
+    std::iter::once("gcloud")
+        .chain(global_args.iter().map(String::as_str))
+        .chain(args.iter().map(String::as_str))
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quote an argument for a POSIX shell if needed
+fn shell_quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '=' | '@')
+        });
+
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Get the zone and name of an instance, from its ID or name
+///
+/// This lists every instance of the project, so callers knowing the zone
+/// use the `_in_zone` functions instead.
+async fn resolve_zone(project_id: &str, instance_id: &str) -> Result<(String, String)> {
+    // First we need to find which zone the instance is in
+    let instance = get_instance(project_id, instance_id, true).await?;
+    Ok((instance.name, instance.zone))
+}
+
+/// Run a gcloud command changing an instance in a known zone
+async fn run_instance_command(
+    command: InstanceCommand,
+    project_id: &str,
+    name: &str,
+    zone: &str,
+    description: &str,
+) -> Result<()> {
+    let mut cmd = gcloud::command();
+    cmd.args(instance_command_args(command, project_id, name, zone));
+    // Return the operation right away, and follow it until it is done
+    cmd.args(["--async", "--format", "json"]);
+
+    let Some(stdout) = gcloud::run_mutating_output(cmd, description).await? else {
+        return Ok(());
+    };
+    for operation in operations::operation_names(&stdout)? {
+        operations::wait_for_operation(project_id, zone, &operation).await?;
+    }
+    Ok(())
+}
+
+/// Start an instance
+pub async fn start_instance(project_id: &str, instance_id: &str) -> Result<()> {
+    let (name, zone) = resolve_zone(project_id, instance_id).await?;
+    start_instance_in_zone(project_id, &name, &zone).await
+}
+
+/// Start an instance in a known zone
+pub async fn start_instance_in_zone(project_id: &str, name: &str, zone: &str) -> Result<()> {
+    info!(
+        "Starting instance {} in zone {} of project {}",
+        name, zone, project_id
+    );
+
+    run_instance_command(
+        InstanceCommand::Start,
+        project_id,
+        name,
+        zone,
+        "start instance",
+    )
+    .await?;
+
+    info!("Successfully started instance {}", name);
+    Ok(())
+}
+
+/// Stop an instance
+pub async fn stop_instance(project_id: &str, instance_id: &str) -> Result<()> {
+    let (name, zone) = resolve_zone(project_id, instance_id).await?;
+    stop_instance_in_zone(project_id, &name, &zone).await
+}
+
+/// Stop an instance in a known zone
+pub async fn stop_instance_in_zone(project_id: &str, name: &str, zone: &str) -> Result<()> {
+    info!(
+        "Stopping instance {} in zone {} of project {}",
+        name, zone, project_id
+    );
+
+    run_instance_command(
+        InstanceCommand::Stop,
+        project_id,
+        name,
+        zone,
+        "stop instance",
+    )
+    .await?;
+
+    info!("Successfully stopped instance {}", name);
     Ok(())
 }
 
 /// Restart an instance (stop then start)
 pub async fn restart_instance(project_id: &str, instance_id: &str) -> Result<()> {
+    let (name, zone) = resolve_zone(project_id, instance_id).await?;
+    restart_instance_in_zone(project_id, &name, &zone).await
+}
+
+/// Restart an instance in a known zone
+pub async fn restart_instance_in_zone(project_id: &str, name: &str, zone: &str) -> Result<()> {
     info!(
-        "Restarting instance {} in project {}",
-        instance_id, project_id
+        "Restarting instance {} in zone {} of project {}",
+        name, zone, project_id
     );
 
-    // First we need to find which zone the instance is in
-    let instance = get_instance(project_id, instance_id, true).await?;
+    run_instance_command(
+        InstanceCommand::Reset,
+        project_id,
+        name,
+        zone,
+        "restart instance",
+    )
+    .await?;
+
+    info!("Successfully restarted instance {}", name);
+    Ok(())
+}
+
+/// Delete an instance
+pub async fn delete_instance(project_id: &str, instance_id: &str) -> Result<()> {
+    let (name, zone) = resolve_zone(project_id, instance_id).await?;
+    delete_instance_in_zone(project_id, &name, &zone).await
+}
+
+/// Delete an instance in a known zone
+pub async fn delete_instance_in_zone(project_id: &str, name: &str, zone: &str) -> Result<()> {
+    info!(
+        "Deleting instance {} in zone {} of project {}",
+        name, zone, project_id
+    );
+
+    run_instance_command(
+        InstanceCommand::Delete,
+        project_id,
+        name,
+        zone,
+        "delete instance",
+    )
+    .await?;
+
+    info!("Successfully deleted instance {}", name);
+    Ok(())
+}
+
+/// Open an interactive ssh session to an instance, inheriting the terminal
+///
+/// The session ends when the user logs out, a failure of ssh itself or of
+/// the last remote command being reported as an error.
+pub async fn ssh_instance(
+    project_id: &str,
+    instance: &Instance,
+    agent_forwarding: bool,
+) -> Result<()> {
+    info!(
+        "Opening ssh session to instance {} in zone {} of project {}",
+        instance.name, instance.zone, project_id
+    );
+
+    let status = gcloud::command()
+        .args(ssh_command_args(project_id, instance, agent_forwarding))
+        .status()
+        .context("Failed to execute gcloud compute ssh command")?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "ssh session to {} ended with {}",
+            instance.name,
+            status
+        ));
+    }
+
+    info!("ssh session to instance {} ended", instance.name);
+    Ok(())
+}
+
+/// Change the machine type of an instance
+///
+/// The instance must be TERMINATED. With `restart`, a running instance is
+/// stopped first and started again once its machine type has changed.
+pub async fn resize_instance(
+    project_id: &str,
+    instance_id: &str,
+    machine_type: &str,
+    restart: bool,
+) -> Result<()> {
+    let (name, zone) = resolve_zone(project_id, instance_id).await?;
+    resize_instance_in_zone(project_id, &name, &zone, machine_type, restart).await
+}
+
+/// Change the machine type of an instance in a known zone
+pub async fn resize_instance_in_zone(
+    project_id: &str,
+    name: &str,
+    zone: &str,
+    machine_type: &str,
+    restart: bool,
+) -> Result<()> {
+    info!(
+        "Changing machine type of instance {} in zone {} of project {} to {}",
+        name, zone, project_id, machine_type
+    );
+
+    if restart {
+        stop_instance_in_zone(project_id, name, zone).await?;
+    }
 
     // Build command
-    let mut cmd = Command::new("gcloud");
-    cmd.args([
-        "compute",
-        "instances",
-        "reset", // reset is like a power cycle/restart
-        &instance.name,
-        "--zone",
-        &instance.zone,
-        "--project",
+    let mut cmd = gcloud::command();
+    cmd.args(set_machine_type_args(project_id, name, zone, machine_type));
+
+    gcloud::run_mutating(cmd, "change machine type of instance").await?;
+
+    info!(
+        "Successfully changed machine type of instance {} to {}",
+        name, machine_type
+    );
+
+    if restart {
+        start_instance_in_zone(project_id, name, zone).await?;
+    }
+
+    Ok(())
+}
+
+/// Add network tags to an instance in a known zone
+pub async fn add_tags(project_id: &str, name: &str, zone: &str, tags: &[String]) -> Result<()> {
+    run_tags_command("add-tags", project_id, name, zone, tags).await
+}
+
+/// Remove network tags from an instance in a known zone
+pub async fn remove_tags(project_id: &str, name: &str, zone: &str, tags: &[String]) -> Result<()> {
+    run_tags_command("remove-tags", project_id, name, zone, tags).await
+}
+
+/// Run gcloud compute instances add-tags or remove-tags
+async fn run_tags_command(
+    subcommand: &str,
+    project_id: &str,
+    name: &str,
+    zone: &str,
+    tags: &[String],
+) -> Result<()> {
+    info!(
+        "Running {} {} on instance {} in zone {} of project {}",
+        subcommand,
+        tags.join(","),
+        name,
+        zone,
+        project_id
+    );
+
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args(tags_command_args(subcommand, project_id, name, zone, tags));
+
+    gcloud::run_mutating(cmd, &format!("{} of instance", subcommand)).await?;
+
+    info!("Successfully ran {} on instance {}", subcommand, name);
+    Ok(())
+}
+
+/// Settings of an instance to create
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateSpec {
+    /// Instance name
+    pub name: String,
+    /// Zone to create the instance in
+    pub zone: String,
+    /// Machine type (e.g., e2-medium)
+    pub machine_type: String,
+    /// Image family of the boot disk (e.g., debian-12)
+    pub image_family: String,
+    /// Project of the image family (e.g., debian-cloud)
+    pub image_project: String,
+    /// VPC network of the network interface
+    pub network: String,
+}
+
+impl CreateSpec {
+    /// Create the settings of a small Debian instance in a zone, to be named
+    pub fn new(zone: String) -> Self {
+        Self {
+            name: String::new(),
+            zone,
+            machine_type: "e2-medium".to_string(),
+            image_family: "debian-12".to_string(),
+            image_project: "debian-cloud".to_string(),
+            network: "default".to_string(),
+        }
+    }
+
+    /// Check the settings before running gcloud, for a clearer error than its own
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            anyhow::bail!("Enter an instance name");
+        }
+        if !is_valid_name(&self.name) {
+            anyhow::bail!(
+                "Invalid instance name {}: use up to 63 lowercase letters, digits and dashes, starting with a letter",
+                self.name
+            );
+        }
+        if self.zone.is_empty() {
+            anyhow::bail!("Enter a zone");
+        }
+        if !is_valid_machine_type(&self.machine_type) {
+            anyhow::bail!(
+                "Invalid machine type {}: expected a name like e2-medium or n2-standard-4",
+                self.machine_type
+            );
+        }
+        if self.image_family.is_empty() || self.image_project.is_empty() {
+            anyhow::bail!("Enter an image family and its project");
+        }
+        if self.network.is_empty() {
+            anyhow::bail!("Enter a network");
+        }
+        Ok(())
+    }
+}
+
+/// Check if a name follows the Compute Engine naming rule, [a-z]([-a-z0-9]*[a-z0-9])?
+///
+/// Instance names and network tags follow this rule.
+pub fn is_valid_name(name: &str) -> bool {
+    name.len() <= 63
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && !name.ends_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Check if a machine type looks like family-name, e.g. e2-medium or a2-highgpu-1g
+fn is_valid_machine_type(machine_type: &str) -> bool {
+    let parts: Vec<&str> = machine_type.split('-').collect();
+    parts.len() >= 2
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// Create an instance, waiting until it is created
+pub async fn create_instance(project_id: &str, spec: &CreateSpec) -> Result<()> {
+    spec.validate()?;
+
+    info!(
+        "Creating instance {} in zone {} of project {} ({}, {}/{}, network {})",
+        spec.name,
+        spec.zone,
         project_id,
-        "--quiet", // Disable interactive prompts
-    ]);
+        spec.machine_type,
+        spec.image_project,
+        spec.image_family,
+        spec.network
+    );
 
-    // Execute command
-    let output = cmd
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .context("Failed to execute gcloud compute instances reset command")?;
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args(create_command_args(project_id, spec));
+    // Return the operation right away, and follow it until it is done
+    cmd.args(["--async", "--format", "json"]);
 
-    // Check if command was successful
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow::anyhow!("Failed to restart instance: {}", error));
+    let Some(stdout) = gcloud::run_mutating_output(cmd, "create instance").await? else {
+        return Ok(());
+    };
+    for operation in operations::operation_names(&stdout)? {
+        operations::wait_for_operation(project_id, &spec.zone, &operation).await?;
     }
 
-    info!("Successfully restarted instance {}", instance.name);
+    info!("Successfully created instance {}", spec.name);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn resource_name_of_full_url() {
+        assert_eq!(
+            resource_name(
+                "https://www.googleapis.com/compute/v1/projects/my-project/zones/europe-west1-b",
+                "zones"
+            ),
+            "europe-west1-b"
+        );
+        assert_eq!(
+            resource_name(
+                "https://www.googleapis.com/compute/v1/projects/my-project/zones/europe-west1-b/machineTypes/e2-medium",
+                "machineTypes"
+            ),
+            "e2-medium"
+        );
+    }
+
+    #[test]
+    fn resource_name_of_bare_name() {
+        assert_eq!(resource_name("europe-west1-b", "zones"), "europe-west1-b");
+        assert_eq!(resource_name(" e2-medium ", "machineTypes"), "e2-medium");
+    }
+
+    #[test]
+    fn resource_name_of_empty_value() {
+        assert_eq!(resource_name("", "zones"), "-");
+        assert_eq!(resource_name("   ", "machineTypes"), "-");
+    }
+
+    #[test]
+    fn resource_name_of_unexpected_shape() {
+        // Another collection, or a trailing slash, keep the last non-empty segment
+        assert_eq!(
+            resource_name("projects/my-project/regions/europe-west1", "zones"),
+            "europe-west1"
+        );
+        assert_eq!(
+            resource_name("zones/europe-west1-b/", "zones"),
+            "europe-west1-b"
+        );
+        assert_eq!(resource_name("/", "zones"), "/");
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use super::gcloud;
+
+/// Cloud Logging entry for an instance
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogEntry {
+    /// Entry timestamp (RFC3339)
+    pub timestamp: Option<String>,
+    /// Entry severity (INFO, WARNING, ERROR, ...)
+    pub severity: Option<String>,
+    /// Plain text payload
+    #[serde(rename = "textPayload")]
+    text_payload: Option<String>,
+    /// Structured payload
+    #[serde(rename = "jsonPayload")]
+    json_payload: Option<serde_json::Value>,
+    /// Audit log payload
+    #[serde(rename = "protoPayload")]
+    proto_payload: Option<serde_json::Value>,
+}
+
+impl LogEntry {
+    /// Get a single-line message for the entry, whatever its payload type
+    pub fn message(&self) -> String {
+        if let Some(text) = &self.text_payload {
+            return text.trim_end().to_string();
+        }
+
+        if let Some(json) = &self.json_payload {
+            return match json.get("message").and_then(|m| m.as_str()) {
+                Some(message) => message.trim_end().to_string(),
+                None => json.to_string(),
+            };
+        }
+
+        if let Some(proto) = &self.proto_payload {
+            if let Some(method) = proto.get("methodName").and_then(|m| m.as_str()) {
+                return format!("audit: {}", method);
+            }
+            return proto.to_string();
+        }
+
+        String::new()
+    }
+}
+
+/// Read the most recent Cloud Logging entries for an instance
+pub async fn read_instance_logs(
+    project_id: &str,
+    instance_id: &str,
+    limit: usize,
+) -> Result<Vec<LogEntry>> {
+    info!(
+        "Reading {} log entries for instance {} in project {}",
+        limit, instance_id, project_id
+    );
+
+    let filter = format!(
+        "resource.type=gce_instance AND resource.labels.instance_id={}",
+        instance_id
+    );
+    let limit = limit.to_string();
+
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args([
+        "logging",
+        "read",
+        &filter,
+        "--limit",
+        &limit,
+        "--project",
+        project_id,
+        "--format",
+        "json",
+    ]);
+
+    // Execute command
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud logging read command")?;
+
+    // Check if command was successful
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        if let Some(e) = gcloud::impersonation_error(&error) {
+            return Err(e);
+        }
+        if is_api_disabled(&error) {
+            return Err(anyhow::anyhow!(
+                "Cloud Logging API is not enabled for project {}. Enable it at https://console.cloud.google.com/apis/library/logging.googleapis.com",
+                project_id
+            ));
+        }
+        return Err(gcloud::command_error(
+            "Failed to read instance logs",
+            &error,
+        ));
+    }
+
+    // No entries may produce no output at all
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Parse JSON output
+    let entries: Vec<LogEntry> =
+        serde_json::from_str(&stdout).context("Failed to parse log entries JSON")?;
+
+    debug!("Found {} log entries", entries.len());
+
+    Ok(entries)
+}
+
+/// Check whether a gcloud error means the Logging API is disabled
+fn is_api_disabled(error: &str) -> bool {
+    error.contains("SERVICE_DISABLED")
+        || error.contains("has not been used in project")
+        || error.contains("it is disabled")
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+/// vCPU and memory of a machine type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MachineSpec {
+    /// Number of vCPUs
+    pub vcpus: u32,
+    /// Memory in MB
+    pub memory_mb: u32,
+    /// Whether the vCPUs are a fraction of a shared core
+    pub shared_core: bool,
+}
+
+impl MachineSpec {
+    /// Get the spec of a predefined or custom machine type
+    ///
+    /// Returns `None` for machine types whose spec cannot be derived from the name.
+    pub fn parse(machine_type: &str) -> Option<Self> {
+        let shared = |vcpus, memory_mb| MachineSpec {
+            vcpus,
+            memory_mb,
+            shared_core: true,
+        };
+
+        match machine_type {
+            "f1-micro" => return Some(shared(1, 614)),
+            "g1-small" => return Some(shared(1, 1740)),
+            "e2-micro" => return Some(shared(2, 1024)),
+            "e2-small" => return Some(shared(2, 2048)),
+            "e2-medium" => return Some(shared(2, 4096)),
+            _ => {}
+        }
+
+        let parts: Vec<&str> = machine_type.split('-').collect();
+        match parts.as_slice() {
+            // custom-CPUS-MEMORY, family-custom-CPUS-MEMORY, with an optional -ext suffix
+            ["custom", vcpus, memory, ..] | [_, "custom", vcpus, memory, ..] => Some(MachineSpec {
+                vcpus: vcpus.parse().ok()?,
+                memory_mb: memory.parse().ok()?,
+                shared_core: false,
+            }),
+            [family, class, vcpus] => {
+                let vcpus: u32 = vcpus.parse().ok()?;
+                let memory_per_vcpu = memory_per_vcpu_mb(family, class)?;
+                Some(MachineSpec {
+                    vcpus,
+                    memory_mb: (f64::from(vcpus) * memory_per_vcpu).round() as u32,
+                    shared_core: false,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MachineSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} vCPU", self.vcpus)?;
+        if self.shared_core {
+            write!(f, " (shared)")?;
+        }
+        write!(f, ", {:.1} GB", f64::from(self.memory_mb) / 1024.0)
+    }
+}
+
+/// Check if a machine type belongs to a family such as "e2" or "n1"
+///
+/// The "custom" family matches every custom machine type. Legacy custom
+/// machine types (custom-CPUS-MEMORY) are N1 machines, so they also match "n1".
+pub fn is_machine_family(machine_type: &str, family: &str) -> bool {
+    let machine_type = machine_type.to_lowercase();
+    let family = family.to_lowercase();
+    let mut parts = machine_type.split('-');
+
+    match parts.next() {
+        Some("custom") => family == "custom" || family == "n1",
+        Some(prefix) if prefix == family => true,
+        Some(_) => family == "custom" && parts.any(|part| part == "custom"),
+        None => false,
+    }
+}
+
+/// Memory per vCPU in MB of a predefined machine class
+fn memory_per_vcpu_mb(family: &str, class: &str) -> Option<f64> {
+    // N1 predates the 4 GB per vCPU standard ratio of later families
+    let n1 = family == "n1";
+    match class {
+        "standard" => Some(if n1 { 3840.0 } else { 4096.0 }),
+        "highmem" => Some(if n1 { 6656.0 } else { 8192.0 }),
+        "highcpu" => Some(if n1 { 921.6 } else { 1024.0 }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Dedicated-core spec
+    fn spec(vcpus: u32, memory_mb: u32) -> Option<MachineSpec> {
+        Some(MachineSpec {
+            vcpus,
+            memory_mb,
+            shared_core: false,
+        })
+    }
+
+    #[test]
+    fn parse_machine_types() {
+        let shared = |vcpus, memory_mb| {
+            Some(MachineSpec {
+                vcpus,
+                memory_mb,
+                shared_core: true,
+            })
+        };
+        let cases = [
+            // Standard
+            ("e2-standard-4", spec(4, 16384)),
+            ("n2-standard-2", spec(2, 8192)),
+            ("n1-standard-1", spec(1, 3840)),
+            ("n1-standard-8", spec(8, 30720)),
+            // High memory
+            ("n2-highmem-4", spec(4, 32768)),
+            ("n1-highmem-2", spec(2, 13312)),
+            // High CPU
+            ("e2-highcpu-8", spec(8, 8192)),
+            ("n1-highcpu-4", spec(4, 3686)),
+            ("n1-highcpu-16", spec(16, 14746)),
+            // Shared core
+            ("f1-micro", shared(1, 614)),
+            ("g1-small", shared(1, 1740)),
+            ("e2-micro", shared(2, 1024)),
+            ("e2-small", shared(2, 2048)),
+            ("e2-medium", shared(2, 4096)),
+            // Custom
+            ("custom-2-4096", spec(2, 4096)),
+            ("n2-custom-4-8192", spec(4, 8192)),
+            ("n2-custom-4-8192-ext", spec(4, 8192)),
+            ("e2-custom-2-5120", spec(2, 5120)),
+            // Not derivable from the name
+            ("n2-ultramem-4", None),
+            ("n2-standard-x", None),
+            ("n2-custom-four-8192", None),
+            ("a2-highgpu", None),
+            ("", None),
+        ];
+
+        for (machine_type, expected) in cases {
+            assert_eq!(
+                MachineSpec::parse(machine_type),
+                expected,
+                "{}",
+                machine_type
+            );
+        }
+    }
+
+    #[test]
+    fn display_spec() {
+        assert_eq!(
+            MachineSpec::parse("e2-medium").unwrap().to_string(),
+            "2 vCPU (shared), 4.0 GB"
+        );
+        assert_eq!(
+            MachineSpec::parse("n1-standard-1").unwrap().to_string(),
+            "1 vCPU, 3.8 GB"
+        );
+    }
+}
@@ -1,28 +1,88 @@
 mod auth;
+mod custom_command;
+pub mod export;
+mod firewalls;
+mod gcloud;
 mod instances;
+mod logs;
+mod machine_types;
+mod operations;
+pub mod pricing;
+mod regions;
 
 use anyhow::{Context, Result};
-use tracing::{debug, info};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
 
 use crate::config::Config;
 
-pub use self::instances::Instance;
+pub use self::auth::{check_gcloud, Account, GcloudVersion, LoginKind, MIN_GCLOUD_VERSION};
+pub use self::custom_command::CommandOutput;
+pub use self::firewalls::FirewallRule;
+pub use self::instances::{
+    is_valid_name, CreateSpec, DiskInfo, Instance, InstanceCommand, NetworkInterfaceInfo,
+};
+pub use self::logs::LogEntry;
+pub use self::machine_types::{is_machine_family, MachineSpec};
+
+/// Region used when neither the configuration nor gcloud set one
+const DEFAULT_REGION: &str = "us-central1";
+
+/// Instances listed from every project, with the projects or zones that failed to list
+#[derive(Debug, Default)]
+pub struct InstanceListing {
+    /// Instances of the projects and zones that were listed
+    pub instances: Vec<Instance>,
+    /// What failed to list ("project p", "zone z of project p"), with the error
+    pub failures: Vec<(String, anyhow::Error)>,
+}
 
 /// Google Cloud API client
+#[derive(Debug, Clone)]
 pub struct CloudClient {
     /// Project ID
     project_id: String,
+    /// Projects whose instances are listed, the project ID alone unless several are configured
+    projects: Vec<String>,
     /// Default region
     region: String,
+    /// Whether instances are only listed in the region, rather than in all regions
+    region_scoped: bool,
+    /// Zone of the region instances are only listed in, if scoped to a zone
+    zone: Option<String>,
+    /// Default zone from gcloud config, if set
+    default_zone: Option<String>,
+    /// gcloud filter expression applied when listing instances
+    list_filter: Option<String>,
     /// Whether to format output as JSON
     json_output: bool,
+    /// Whether ssh commands forward the local ssh-agent
+    ssh_agent_forwarding: bool,
+    /// Whether instances of a region are listed with one concurrent query per zone
+    parallel_zone_queries: bool,
+    /// Maximum number of zones listed at a time, at least 1
+    max_zone_queries: usize,
 }
 
 impl CloudClient {
     /// Create a new Cloud API client
     pub async fn new(config: &Config) -> Result<Self> {
-        // Get project ID from config or gcloud
-        let project_id = match &config.project {
+        gcloud::set_dry_run(config.dry_run);
+
+        // Impersonate the service account in every gcloud command
+        if let Some(email) = &config.impersonate_service_account {
+            gcloud::set_impersonate_service_account(email)?;
+        }
+
+        // Authenticate every gcloud command with the configured credentials file
+        if let Some(path) = &config.credentials_path {
+            gcloud::set_credentials_file(path)?;
+        }
+
+        // Get project ID from config, the listed projects or gcloud
+        let project_id = match config.project.as_ref().or(config.projects.first()) {
             Some(project) => project.clone(),
             None => {
                 info!("No project ID specified, trying to detect from gcloud config");
@@ -30,44 +90,561 @@ impl CloudClient {
             }
         };
 
-        // Get region from config or use default
-        let region = config.region.clone().unwrap_or_else(|| {
-            info!("No region specified, using us-central1");
-            "us-central1".to_string()
-        });
+        // Get region from config, gcloud config or use default
+        let region = match &config.region {
+            Some(region) => region.clone(),
+            None => match auth::get_default_region() {
+                Ok(Some(region)) => region,
+                Ok(None) => {
+                    info!("No region specified, using {}", DEFAULT_REGION);
+                    DEFAULT_REGION.to_string()
+                }
+                Err(e) => {
+                    warn!("{:#}, using {}", e, DEFAULT_REGION);
+                    DEFAULT_REGION.to_string()
+                }
+            },
+        };
+
+        // Get the default zone from gcloud config, falling back to a zone of the region
+        let default_zone = match auth::get_default_zone() {
+            Ok(zone) => zone,
+            Err(e) => {
+                warn!("{:#}, using a zone of region {}", e, region);
+                None
+            }
+        };
+
+        // Ignore blank filters so they are not passed to gcloud
+        let list_filter = config
+            .list_filter
+            .as_ref()
+            .map(|filter| filter.trim().to_string())
+            .filter(|filter| !filter.is_empty());
+
+        // List every configured project, without duplicates
+        let mut projects: Vec<String> = Vec::new();
+        for project in &config.projects {
+            if !projects.contains(project) {
+                projects.push(project.clone());
+            }
+        }
+        if projects.is_empty() {
+            projects.push(project_id.clone());
+        }
 
         debug!(
-            "Initialized CloudClient with project={}, region={}",
-            project_id, region
+            "Initialized CloudClient with project={}, projects={:?}, region={}, default_zone={:?}, filter={:?}",
+            project_id, projects, region, default_zone, list_filter
         );
 
         Ok(Self {
             project_id,
+            projects,
             region,
+            region_scoped: false,
+            zone: None,
+            default_zone,
+            list_filter,
             json_output: true,
+            ssh_agent_forwarding: config.ssh_agent_forwarding,
+            parallel_zone_queries: config.parallel_zone_queries,
+            max_zone_queries: config.max_zone_queries.max(1),
         })
     }
 
-    /// List instances in the project
-    pub async fn list_instances(&self) -> Result<Vec<Instance>> {
-        instances::list_instances(&self.project_id, self.json_output).await
+    /// Create a client of a project without reading the gcloud configuration, for tests
+    #[cfg(test)]
+    pub fn for_test(project_id: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            projects: vec![project_id.to_string()],
+            region: DEFAULT_REGION.to_string(),
+            region_scoped: false,
+            zone: None,
+            default_zone: None,
+            list_filter: None,
+            json_output: true,
+            ssh_agent_forwarding: false,
+            parallel_zone_queries: false,
+            max_zone_queries: 1,
+        }
+    }
+
+    /// List instances in every project, only in the region when scoped to it
+    ///
+    /// Projects are listed concurrently. A project failing to list is reported
+    /// in the listing, and only fails the whole listing when every project failed.
+    pub async fn list_instances(&self) -> Result<InstanceListing> {
+        // One query per zone, rather than one aggregated list
+        if self.parallel_zone_queries {
+            let zones = match &self.zone {
+                Some(zone) => vec![zone.clone()],
+                None => {
+                    let region = self.region_scoped.then_some(self.region.as_str());
+                    regions::list_zones(&self.project_id, region).await?
+                }
+            };
+            return self.list_instances_by_zones(&zones).await;
+        }
+
+        let mut tasks = JoinSet::new();
+        for (index, project) in self.projects.iter().enumerate() {
+            let project = project.clone();
+            let filter = self.list_filter.clone();
+            let json_output = self.json_output;
+            tasks.spawn(async move {
+                let result =
+                    instances::list_instances(&project, filter.as_deref(), json_output).await;
+                (index, project, result)
+            });
+        }
+
+        // Merge the results in the configured project order
+        let mut results = Vec::with_capacity(self.projects.len());
+        while let Some(joined) = tasks.join_next().await {
+            results.push(joined.context("Instance listing task failed")?);
+        }
+        results.sort_by_key(|(index, _, _)| *index);
+
+        let mut listing = InstanceListing::default();
+        for (_, project, result) in results {
+            match result {
+                Ok(instances) => listing.instances.extend(instances),
+                Err(e) => listing.failures.push((format!("project {}", project), e)),
+            }
+        }
+
+        // Nothing to show if every project failed
+        if listing.failures.len() == self.projects.len() {
+            let (scope, e) = listing.failures.remove(0);
+            return Err(if self.is_multi_project() {
+                e.context(format!("Failed to list instances in {}", scope))
+            } else {
+                e
+            });
+        }
+
+        if let Some(zone) = &self.zone {
+            listing.instances.retain(|instance| &instance.zone == zone);
+        } else if self.region_scoped {
+            let zone_prefix = format!("{}-", self.region);
+            listing
+                .instances
+                .retain(|instance| instance.zone.starts_with(&zone_prefix));
+        }
+
+        Ok(listing)
+    }
+
+    /// List instances in the given zones of every project, one gcloud command per zone
+    ///
+    /// Zones are listed concurrently, which is faster than an aggregated list for
+    /// large projects. Each query is a gcloud process of its own, so at most
+    /// `max_zone_queries` run at a time: an unscoped listing has one query per
+    /// zone of every region and project, well over a hundred processes, which
+    /// would exhaust the machine and the API rate limits. A zone failing to
+    /// list is reported in the listing, and only fails the whole listing when
+    /// every zone failed.
+    pub async fn list_instances_by_zones(&self, zones: &[String]) -> Result<InstanceListing> {
+        let permits = Arc::new(Semaphore::new(self.max_zone_queries));
+        let mut tasks = JoinSet::new();
+        let queries = self
+            .projects
+            .iter()
+            .flat_map(|project| zones.iter().map(move |zone| (project, zone)));
+        for (index, (project, zone)) in queries.enumerate() {
+            let project = project.clone();
+            let zone = zone.clone();
+            let filter = self.list_filter.clone();
+            let json_output = self.json_output;
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                // The semaphore is never closed
+                let _permit = permits.acquire_owned().await;
+                let result =
+                    instances::list_zone_instances(&project, &zone, filter.as_deref(), json_output)
+                        .await;
+                (
+                    index,
+                    format!("zone {} of project {}", zone, project),
+                    result,
+                )
+            });
+        }
+
+        // Merge the results in the configured project and zone order
+        let mut results = Vec::with_capacity(tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            results.push(joined.context("Instance listing task failed")?);
+        }
+        results.sort_by_key(|(index, _, _)| *index);
+
+        let query_count = results.len();
+        let mut listing = InstanceListing::default();
+        for (_, scope, result) in results {
+            match result {
+                Ok(instances) => listing.instances.extend(instances),
+                Err(e) => listing.failures.push((scope, e)),
+            }
+        }
+
+        // Nothing to show if every zone failed
+        if query_count > 0 && listing.failures.len() == query_count {
+            let (scope, e) = listing.failures.remove(0);
+            return Err(e.context(format!("Failed to list instances in {}", scope)));
+        }
+
+        Ok(listing)
+    }
+
+    /// Check if instances are listed from several projects
+    pub fn is_multi_project(&self) -> bool {
+        self.projects.len() > 1
+    }
+
+    /// Get the projects whose instances are listed
+    pub fn get_projects(&self) -> &[String] {
+        &self.projects
+    }
+
+    /// Get a client acting on the project of an instance
+    ///
+    /// Actions only take the instance ID, which must be looked up in its own
+    /// project when several projects are listed.
+    pub fn for_project(&self, project: &str) -> Self {
+        if project.is_empty() || project == self.project_id {
+            return self.clone();
+        }
+
+        Self {
+            project_id: project.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// List the regions available to the project
+    pub async fn list_regions(&self) -> Result<Vec<String>> {
+        regions::list_regions(&self.project_id).await
+    }
+
+    /// List the zones available to the project, in all regions
+    pub async fn list_zones(&self) -> Result<Vec<String>> {
+        regions::list_zones(&self.project_id, None).await
+    }
+
+    /// Scope instance listing to a region, or list all regions with `None`
+    pub fn set_region(&mut self, region: Option<String>) {
+        self.zone = None;
+        match region {
+            Some(region) => {
+                info!("Scoping instance listing to region {}", region);
+                self.region = region;
+                self.region_scoped = true;
+            }
+            None => {
+                info!("Listing instances in all regions");
+                self.region_scoped = false;
+            }
+        }
+    }
+
+    /// Scope instance listing to a zone, and to its region
+    pub fn set_zone(&mut self, zone: String) {
+        info!("Scoping instance listing to zone {}", zone);
+        if let Some((region, _)) = zone.rsplit_once('-') {
+            self.region = region.to_string();
+        }
+        self.region_scoped = true;
+        self.zone = Some(zone);
+    }
+
+    /// Check if instance listing is scoped to the region
+    pub fn is_region_scoped(&self) -> bool {
+        self.region_scoped
+    }
+
+    /// Get the zone instance listing is scoped to, if any
+    pub fn get_zone(&self) -> Option<&str> {
+        self.zone.as_deref()
+    }
+
+    /// Get the zone used by default, e.g. to create instances
+    ///
+    /// This is the zone instances are listed in, else the gcloud default zone
+    /// if it is in the region, else the "-a" zone of the region.
+    pub fn get_default_zone(&self) -> String {
+        if let Some(zone) = &self.zone {
+            return zone.clone();
+        }
+        match &self.default_zone {
+            Some(zone) if zone.rsplit_once('-').map(|(region, _)| region) == Some(&self.region) => {
+                zone.clone()
+            }
+            _ => format!("{}-a", self.region),
+        }
+    }
+
+    /// Describe an instance again to get its latest state
+    pub async fn describe_instance(&self, instance: &Instance) -> Result<Instance> {
+        instances::describe_instance(
+            self.project_of(instance),
+            &instance.name,
+            &instance.zone,
+            self.json_output,
+        )
+        .await
+    }
+
+    /// Get the complete gcloud describe output of an instance, as pretty-printed JSON
+    pub async fn describe_instance_raw(&self, instance: &Instance) -> Result<String> {
+        instances::describe_instance_raw(self.project_of(instance), &instance.name, &instance.zone)
+            .await
     }
 
-    /// Start an instance
+    /// Get the serial port output of an instance, e.g. its boot messages
+    pub async fn get_serial_output(&self, instance_name: &str, zone: &str) -> Result<String> {
+        instances::get_serial_output(&self.project_id, instance_name, zone).await
+    }
+
+    /// Start an instance, listing instances to find its zone
     pub async fn start_instance(&self, instance_id: &str) -> Result<()> {
         instances::start_instance(&self.project_id, instance_id).await
     }
 
-    /// Stop an instance
+    /// Start an instance in a known zone
+    pub async fn start_instance_in_zone(&self, name: &str, zone: &str) -> Result<()> {
+        instances::start_instance_in_zone(&self.project_id, name, zone).await
+    }
+
+    /// Stop an instance, listing instances to find its zone
     pub async fn stop_instance(&self, instance_id: &str) -> Result<()> {
         instances::stop_instance(&self.project_id, instance_id).await
     }
 
-    /// Restart an instance
+    /// Stop an instance in a known zone
+    pub async fn stop_instance_in_zone(&self, name: &str, zone: &str) -> Result<()> {
+        instances::stop_instance_in_zone(&self.project_id, name, zone).await
+    }
+
+    /// Restart an instance, listing instances to find its zone
     pub async fn restart_instance(&self, instance_id: &str) -> Result<()> {
         instances::restart_instance(&self.project_id, instance_id).await
     }
 
+    /// Restart an instance in a known zone
+    pub async fn restart_instance_in_zone(&self, name: &str, zone: &str) -> Result<()> {
+        instances::restart_instance_in_zone(&self.project_id, name, zone).await
+    }
+
+    /// Delete an instance, listing instances to find its zone
+    pub async fn delete_instance(&self, instance_id: &str) -> Result<()> {
+        instances::delete_instance(&self.project_id, instance_id).await
+    }
+
+    /// Delete an instance in a known zone
+    pub async fn delete_instance_in_zone(&self, name: &str, zone: &str) -> Result<()> {
+        instances::delete_instance_in_zone(&self.project_id, name, zone).await
+    }
+
+    /// Change the machine type of an instance, stopping and starting it with `restart`
+    ///
+    /// Instances are listed to find its zone.
+    pub async fn resize_instance(
+        &self,
+        instance_id: &str,
+        machine_type: &str,
+        restart: bool,
+    ) -> Result<()> {
+        instances::resize_instance(&self.project_id, instance_id, machine_type, restart).await
+    }
+
+    /// Change the machine type of an instance in a known zone
+    pub async fn resize_instance_in_zone(
+        &self,
+        name: &str,
+        zone: &str,
+        machine_type: &str,
+        restart: bool,
+    ) -> Result<()> {
+        instances::resize_instance_in_zone(&self.project_id, name, zone, machine_type, restart)
+            .await
+    }
+
+    /// Add network tags to an instance in a known zone
+    pub async fn add_tags(&self, name: &str, zone: &str, tags: &[String]) -> Result<()> {
+        instances::add_tags(&self.project_id, name, zone, tags).await
+    }
+
+    /// Remove network tags from an instance in a known zone
+    pub async fn remove_tags(&self, name: &str, zone: &str, tags: &[String]) -> Result<()> {
+        instances::remove_tags(&self.project_id, name, zone, tags).await
+    }
+
+    /// Add and remove network tags of an instance
+    pub async fn update_tags(
+        &self,
+        instance_id: &str,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<()> {
+        let instance =
+            instances::get_instance(&self.project_id, instance_id, self.json_output).await?;
+        self.update_tags_in_zone(&instance.name, &instance.zone, add, remove)
+            .await
+    }
+
+    /// Add and remove network tags of an instance in a known zone, skipping empty lists
+    pub async fn update_tags_in_zone(
+        &self,
+        name: &str,
+        zone: &str,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<()> {
+        if !add.is_empty() {
+            self.add_tags(name, zone, add).await?;
+        }
+        if !remove.is_empty() {
+            self.remove_tags(name, zone, remove).await?;
+        }
+        Ok(())
+    }
+
+    /// Create an instance, waiting until it is created
+    pub async fn create_instance(&self, spec: CreateSpec) -> Result<()> {
+        instances::create_instance(&self.project_id, &spec).await
+    }
+
+    /// Read the most recent Cloud Logging entries for an instance
+    pub async fn read_instance_logs(
+        &self,
+        instance_id: &str,
+        limit: usize,
+    ) -> Result<Vec<LogEntry>> {
+        logs::read_instance_logs(&self.project_id, instance_id, limit).await
+    }
+
+    /// Get the firewall rules effectively applied to an instance
+    pub async fn get_effective_firewalls(
+        &self,
+        instance_name: &str,
+        zone: &str,
+    ) -> Result<Vec<FirewallRule>> {
+        firewalls::get_effective_firewalls(&self.project_id, instance_name, zone).await
+    }
+
+    /// Run a gcloud command typed by the user in the project, e.g. "compute disks list"
+    pub async fn run_command(&self, input: &str) -> Result<CommandOutput> {
+        custom_command::run_command(&self.project_id, input).await
+    }
+
+    /// Get the gcloud command line acting on an instance, without running it
+    pub fn instance_command_line(&self, command: InstanceCommand, instance: &Instance) -> String {
+        // The command runs in the user's shell, so ssh already uses its SSH_AUTH_SOCK
+        let args = if command == InstanceCommand::Ssh {
+            instances::ssh_command_args(
+                self.project_of(instance),
+                instance,
+                self.ssh_agent_forwarding,
+            )
+        } else {
+            instances::instance_command_args(
+                command,
+                self.project_of(instance),
+                &instance.name,
+                &instance.zone,
+            )
+        };
+        instances::format_command_line(&args)
+    }
+
+    /// Get the gcloud command lines changing the machine type of an instance, joined with &&
+    ///
+    /// With `restart`, the instance is stopped first and started again afterwards.
+    pub fn resize_command_line(
+        &self,
+        instance: &Instance,
+        machine_type: &str,
+        restart: bool,
+    ) -> String {
+        let project = self.project_of(instance);
+        let resize = instances::format_command_line(&instances::set_machine_type_args(
+            project,
+            &instance.name,
+            &instance.zone,
+            machine_type,
+        ));
+        if !restart {
+            return resize;
+        }
+
+        [
+            self.instance_command_line(InstanceCommand::Stop, instance),
+            resize,
+            self.instance_command_line(InstanceCommand::Start, instance),
+        ]
+        .join(" && ")
+    }
+
+    /// Get the gcloud command lines adding and removing network tags, joined with &&
+    pub fn tags_command_line(
+        &self,
+        instance: &Instance,
+        add: &[String],
+        remove: &[String],
+    ) -> String {
+        let project = self.project_of(instance);
+        [("add-tags", add), ("remove-tags", remove)]
+            .into_iter()
+            .filter(|(_, tags)| !tags.is_empty())
+            .map(|(subcommand, tags)| {
+                instances::format_command_line(&instances::tags_command_args(
+                    subcommand,
+                    project,
+                    &instance.name,
+                    &instance.zone,
+                    tags,
+                ))
+            })
+            .collect::<Vec<_>>()
+            .join(" && ")
+    }
+
+    /// Get the gcloud command line creating an instance in the project
+    pub fn create_command_line(&self, spec: &CreateSpec) -> String {
+        instances::format_command_line(&instances::create_command_args(&self.project_id, spec))
+    }
+
+    /// Open an interactive ssh session to a listed instance, the terminal must be released
+    pub async fn ssh_instance(&self, instance: &Instance) -> Result<()> {
+        instances::ssh_instance(
+            self.project_of(instance),
+            instance,
+            self.ssh_agent_forwarding,
+        )
+        .await
+    }
+
+    /// Get the project of an instance, the client's project if unknown
+    fn project_of<'a>(&'a self, instance: &'a Instance) -> &'a str {
+        if instance.project.is_empty() {
+            &self.project_id
+        } else {
+            &instance.project
+        }
+    }
+
+    /// Check if commands changing cloud resources are only logged
+    pub fn is_dry_run(&self) -> bool {
+        gcloud::is_dry_run()
+    }
+
+    /// Get the service account impersonated by gcloud commands, if any
+    pub fn get_impersonated_service_account(&self) -> Option<&'static str> {
+        gcloud::impersonated_service_account()
+    }
+
     /// Get the region for this client
     pub fn get_region(&self) -> &str {
         &self.region
@@ -82,4 +659,24 @@ impl CloudClient {
     pub fn get_cli_version(&self) -> Result<String> {
         auth::get_gcloud_version()
     }
+
+    /// Get the active gcloud account
+    pub fn get_active_account(&self) -> Result<Option<String>> {
+        auth::get_active_account()
+    }
+
+    /// List the accounts gcloud has credentials for
+    pub fn list_accounts(&self) -> Result<Vec<Account>> {
+        auth::list_accounts()
+    }
+
+    /// Make an account the active one of the gcloud configuration
+    pub fn set_account(&self, email: &str) -> Result<()> {
+        auth::set_account(email)
+    }
+
+    /// Log in again with gcloud, interacting with the user in the terminal
+    pub fn login(&self, kind: LoginKind) -> Result<()> {
+        auth::login(kind)
+    }
 }
@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use super::gcloud;
+
+/// Delay between two polls of a running operation
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest wait for an operation, after which it is left running
+const MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+
+/// Zonal Compute Engine operation, as returned by `--async` commands
+#[derive(Debug, Clone, Deserialize)]
+struct Operation {
+    name: String,
+    status: Option<String>,
+    error: Option<OperationErrors>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OperationErrors {
+    errors: Option<Vec<OperationError>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OperationError {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl Operation {
+    /// Check if the operation is over, successfully or not
+    fn is_done(&self) -> bool {
+        self.status.as_deref() == Some("DONE")
+    }
+
+    /// Get the error messages of a failed operation
+    fn error_message(&self) -> Option<String> {
+        let errors = self.error.as_ref()?.errors.as_ref()?;
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| match (&error.code, &error.message) {
+                (Some(code), Some(message)) => format!("{}: {}", code, message),
+                (None, Some(message)) => message.clone(),
+                (Some(code), None) => code.clone(),
+                (None, None) => "unknown error".to_string(),
+            })
+            .collect();
+        Some(messages.join("; "))
+    }
+}
+
+/// Get the names of the operations printed by an `--async --format json` command
+///
+/// gcloud prints a list of operations, or a single one depending on the command.
+pub fn operation_names(stdout: &str) -> Result<Vec<String>> {
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let value: serde_json::Value =
+        serde_json::from_str(stdout).context("Failed to parse operation JSON")?;
+    let operations: Vec<Operation> = match value {
+        serde_json::Value::Array(_) => serde_json::from_value(value),
+        _ => serde_json::from_value(value).map(|operation| vec![operation]),
+    }
+    .context("Failed to parse operation JSON")?;
+
+    Ok(operations
+        .into_iter()
+        .map(|operation| operation.name)
+        .collect())
+}
+
+/// Wait for a zonal operation to be done, failing with its error if it failed
+pub async fn wait_for_operation(project_id: &str, zone: &str, name: &str) -> Result<()> {
+    debug!("Waiting for operation {} in zone {}", name, zone);
+    let started = Instant::now();
+
+    loop {
+        let operation = describe_operation(project_id, zone, name).await?;
+        if operation.is_done() {
+            if let Some(error) = operation.error_message() {
+                return Err(anyhow::anyhow!("Operation {} failed: {}", name, error));
+            }
+            info!(
+                "Operation {} done after {}s",
+                name,
+                started.elapsed().as_secs()
+            );
+            return Ok(());
+        }
+
+        if started.elapsed() >= MAX_WAIT {
+            return Err(anyhow::anyhow!(
+                "Operation {} still {} after {} minutes, check it with gcloud compute operations describe",
+                name,
+                operation.status.as_deref().unwrap_or("running"),
+                MAX_WAIT.as_secs() / 60
+            ));
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Get the current state of a zonal operation
+async fn describe_operation(project_id: &str, zone: &str, name: &str) -> Result<Operation> {
+    let mut cmd = gcloud::command();
+    cmd.args([
+        "compute",
+        "operations",
+        "describe",
+        name,
+        "--zone",
+        zone,
+        "--project",
+        project_id,
+        "--format",
+        "json",
+    ]);
+
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud compute operations describe command")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(gcloud::command_error(
+            "Failed to describe operation",
+            &error,
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).context("Failed to parse operation JSON")
+}
@@ -0,0 +1,67 @@
+/// Hourly price of shared-core machine types in us-central1
+const SHARED_CORE_PRICES: [(&str, f64); 5] = [
+    ("f1-micro", 0.0076),
+    ("g1-small", 0.0257),
+    ("e2-micro", 0.008376),
+    ("e2-small", 0.016751),
+    ("e2-medium", 0.033503),
+];
+
+/// Hourly price per vCPU of predefined machine classes in us-central1
+const VCPU_PRICES: [(&str, &str, f64); 12] = [
+    ("e2", "standard", 0.033503),
+    ("e2", "highmem", 0.045192),
+    ("e2", "highcpu", 0.024734),
+    ("n1", "standard", 0.0475),
+    ("n1", "highmem", 0.0592),
+    ("n1", "highcpu", 0.03545),
+    ("n2", "standard", 0.048559),
+    ("n2", "highmem", 0.065497),
+    ("n2", "highcpu", 0.035845),
+    ("n2d", "standard", 0.042246),
+    ("c2", "standard", 0.0522),
+    ("t2d", "standard", 0.042246),
+];
+
+/// Price multiplier of regions relative to us-central1
+const REGION_MULTIPLIERS: [(&str, f64); 10] = [
+    ("us-central1", 1.0),
+    ("us-east1", 1.0),
+    ("us-west1", 1.0),
+    ("us-east4", 1.126),
+    ("europe-west1", 1.1),
+    ("europe-west4", 1.1),
+    ("europe-west2", 1.29),
+    ("europe-west3", 1.29),
+    ("asia-east1", 1.16),
+    ("asia-northeast1", 1.28),
+];
+
+/// Estimate the on-demand hourly price of a machine type in a region, in USD
+///
+/// Prices are approximate list prices, without sustained or committed use
+/// discounts, disks, licenses or network, so they only give an order of
+/// magnitude. Returns `None` for machine types or regions missing from the
+/// table, rather than guessing.
+pub fn hourly_usd(machine_type: &str, region: &str) -> Option<f64> {
+    let multiplier = REGION_MULTIPLIERS
+        .iter()
+        .find(|(name, _)| *name == region)
+        .map(|(_, multiplier)| *multiplier)?;
+
+    if let Some((_, price)) = SHARED_CORE_PRICES
+        .iter()
+        .find(|(name, _)| *name == machine_type)
+    {
+        return Some(price * multiplier);
+    }
+
+    let [family, class, vcpus] = machine_type.split('-').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let vcpus: u32 = vcpus.parse().ok()?;
+    VCPU_PRICES
+        .iter()
+        .find(|(f, c, _)| *f == family && *c == class)
+        .map(|(_, _, price)| price * f64::from(vcpus) * multiplier)
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use super::gcloud;
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudRegion {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GcloudZone {
+    name: String,
+}
+
+/// List the names of the regions available to a project, sorted
+pub async fn list_regions(project_id: &str) -> Result<Vec<String>> {
+    info!("Listing regions in project {}", project_id);
+
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args([
+        "compute",
+        "regions",
+        "list",
+        "--project",
+        project_id,
+        "--format",
+        "json",
+    ]);
+
+    // Execute command
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud compute regions list command")?;
+
+    // Check if command was successful
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(gcloud::command_error("Failed to list regions", &error));
+    }
+
+    // Parse JSON output
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let regions: Vec<GcloudRegion> =
+        serde_json::from_str(&stdout).context("Failed to parse region list JSON")?;
+
+    let mut names: Vec<String> = regions.into_iter().map(|region| region.name).collect();
+    names.sort();
+
+    debug!("Found {} regions", names.len());
+
+    Ok(names)
+}
+
+/// List the names of the zones available to a project, only in a region if given, sorted
+pub async fn list_zones(project_id: &str, region: Option<&str>) -> Result<Vec<String>> {
+    info!(
+        "Listing zones of region {} in project {}",
+        region.unwrap_or("(all)"),
+        project_id
+    );
+
+    // Build command
+    let mut cmd = gcloud::command();
+    cmd.args([
+        "compute",
+        "zones",
+        "list",
+        "--project",
+        project_id,
+        "--format",
+        "json",
+    ]);
+
+    // Execute command
+    let output = gcloud::output(cmd)
+        .await
+        .context("Failed to execute gcloud compute zones list command")?;
+
+    // Check if command was successful
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(gcloud::command_error("Failed to list zones", &error));
+    }
+
+    // Parse JSON output
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let zones: Vec<GcloudZone> =
+        serde_json::from_str(&stdout).context("Failed to parse zone list JSON")?;
+
+    // Zone names are the region name with a suffix, e.g. us-central1-a
+    let zone_prefix = region.map(|region| format!("{}-", region));
+    let mut names: Vec<String> = zones
+        .into_iter()
+        .map(|zone| zone.name)
+        .filter(|name| {
+            zone_prefix
+                .as_ref()
+                .is_none_or(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    names.sort();
+
+    debug!("Found {} zones", names.len());
+
+    Ok(names)
+}
@@ -1,12 +1,17 @@
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::keymap::{ConfiguredKeys, Keymap, KeymapPreset};
+use crate::ui::{Column, CountFocus, SortKey, StatusStyleConfig, ALL_COLUMNS};
+
 /// Application configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
     /// Google Cloud project ID
     pub project: Option<String>,
@@ -17,14 +22,103 @@ pub struct Config {
     /// Refresh interval in seconds
     pub refresh_interval: u64,
 
-    /// UI theme
+    /// Random spread of the refresh interval, in percent (0 to disable, at most 50)
+    pub refresh_jitter: u8,
+
+    /// UI theme: dark (or default) or light, for the dashboard and details views
     pub theme: String,
 
     /// Whether to use SSH for connecting to instances
     pub use_ssh: bool,
 
+    /// Forward the local ssh-agent in ssh commands (`--ssh-flag=-A`)
+    pub ssh_agent_forwarding: bool,
+
+    /// List instances with one concurrent gcloud command per zone, for large projects
+    pub parallel_zone_queries: bool,
+
+    /// Maximum number of zones listed at a time with parallel_zone_queries
+    pub max_zone_queries: usize,
+
     /// Path to Google Cloud credentials file
     pub credentials_path: Option<PathBuf>,
+
+    /// Service account impersonated by gcloud commands
+    pub impersonate_service_account: Option<String>,
+
+    /// Whether to show the instance count sparkline in the overview
+    pub show_sparkline: bool,
+
+    /// Number of refreshes kept in the instance count history
+    pub history_length: usize,
+
+    /// gcloud filter expression applied when listing instances
+    pub list_filter: Option<String>,
+
+    /// Whether to show the DISK column in the instance list
+    pub show_disk_column: bool,
+
+    /// Columns of the instance list, toggled with C (PROJECT only shows with several projects)
+    pub columns: Vec<Column>,
+
+    /// Show only the zone suffix ("b" for "europe-west1-b") when a single region is listed
+    pub short_zone_names: bool,
+
+    /// Show instances as compact lines (name, status, machine type, zone, IP) without the overview
+    pub dense: bool,
+
+    /// Projects whose instances are listed together, instead of only the default project
+    pub projects: Vec<String>,
+
+    /// Order of the instance list (status_priority, name or zone)
+    pub sort_key: SortKey,
+
+    /// Sort the instance list in ascending order, descending otherwise
+    pub sort_ascending: bool,
+
+    /// Instance count emphasized in the overview (total or shown)
+    pub count_focus: CountFocus,
+
+    /// Key binding preset (default or vim)
+    pub keymap_preset: KeymapPreset,
+
+    /// Keys of actions, by action name, replacing the keys of the preset
+    pub keybindings: HashMap<String, ConfiguredKeys>,
+
+    /// Metadata keys containing any of these patterns have their values hidden
+    pub sensitive_metadata_patterns: Vec<String>,
+
+    /// Only log the commands changing cloud resources, without running them
+    pub dry_run: bool,
+
+    /// Whether stop, restart and delete ask for confirmation before running
+    pub confirm_destructive: bool,
+
+    /// Maximum number of instances a bulk action runs on at a time, the others are queued
+    pub max_bulk_actions: usize,
+
+    /// Whether a failed action or refresh exits, instead of being reported in the UI
+    pub exit_on_error: bool,
+
+    /// Color, emoji, ASCII marker and list label of instance statuses, by status
+    pub status_styles: HashMap<String, StatusStyleConfig>,
+
+    /// Whether to use emoji, disable for terminals without emoji glyphs
+    pub emoji: bool,
+
+    /// Whether to use colors, disable for monochrome terminals or captured output
+    pub color: bool,
+
+    /// Whether to capture the mouse, disable to use the terminal's own text selection
+    pub mouse: bool,
+
+    /// Configuration file loaded, or to create, where changed settings are saved
+    #[serde(skip)]
+    path: Option<PathBuf>,
+
+    /// Project configuration file merged over the base one, if any
+    #[serde(skip)]
+    project_path: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -33,16 +127,70 @@ impl Default for Config {
             project: None,
             region: None,
             refresh_interval: 5,
+            refresh_jitter: 0,
             theme: "default".to_string(),
             use_ssh: true,
+            ssh_agent_forwarding: false,
+            parallel_zone_queries: false,
+            max_zone_queries: 8,
             credentials_path: None,
+            impersonate_service_account: None,
+            show_sparkline: true,
+            history_length: 60,
+            list_filter: None,
+            show_disk_column: true,
+            columns: ALL_COLUMNS.to_vec(),
+            short_zone_names: false,
+            projects: Vec::new(),
+            dense: false,
+            sort_key: SortKey::StatusPriority,
+            sort_ascending: true,
+            count_focus: CountFocus::Total,
+            keymap_preset: KeymapPreset::Default,
+            keybindings: HashMap::new(),
+            sensitive_metadata_patterns: ["key", "token", "password", "secret"]
+                .map(String::from)
+                .to_vec(),
+            dry_run: false,
+            confirm_destructive: true,
+            max_bulk_actions: 8,
+            exit_on_error: false,
+            status_styles: HashMap::new(),
+            emoji: true,
+            color: true,
+            mouse: true,
+            path: None,
+            project_path: None,
         }
     }
 }
 
 impl Config {
     /// Load configuration from default locations
-    pub fn load(config_path: Option<&str>) -> Result<Self> {
+    ///
+    /// When a project is known (from the command line or the base config), the
+    /// project-scoped file `<config_dir>/projects/<project_id>.toml` is merged
+    /// over the base configuration.
+    pub fn load(config_path: Option<&str>, project: Option<&str>) -> Result<Self> {
+        let config = Self::load_base(config_path)?;
+
+        // Apply project-scoped overrides
+        let project = project
+            .map(str::to_string)
+            .or_else(|| config.project.clone());
+        match project {
+            Some(project) => config.with_project_overrides(&project),
+            None => Ok(config),
+        }
+    }
+
+    /// Build the key bindings of the preset with the configured keys
+    pub fn keymap(&self) -> Result<Keymap> {
+        Keymap::new(self.keymap_preset, &self.keybindings)
+    }
+
+    /// Load the base configuration, without project overrides
+    fn load_base(config_path: Option<&str>) -> Result<Self> {
         // If config path is provided, try to load from it
         if let Some(path) = config_path {
             return Self::load_from_file(path).context(format!(
@@ -52,19 +200,81 @@ impl Config {
         }
 
         // Otherwise try default locations
-        if let Some(config_dir) = Self::config_dir() {
-            let config_file = config_dir.join("config.toml");
-            if config_file.exists() {
-                return Self::load_from_file(&config_file).context(format!(
-                    "Failed to load config from default path: {:?}",
-                    config_file
-                ));
-            }
+        let config_file = Self::config_dir().map(|config_dir| config_dir.join("config.toml"));
+        if let Some(config_file) = config_file.as_ref().filter(|file| file.exists()) {
+            return Self::load_from_file(config_file).context(format!(
+                "Failed to load config from default path: {:?}",
+                config_file
+            ));
         }
 
-        // If no config file exists, return default config
+        // If no config file exists, return default config, saved to the default path
         info!("No configuration file found, using defaults");
-        Ok(Config::default())
+        Ok(Config {
+            path: config_file,
+            ..Config::default()
+        })
+    }
+
+    /// Merge the project-scoped config file over this configuration, if one exists
+    fn with_project_overrides(self, project: &str) -> Result<Self> {
+        let Some(project_file) = Self::project_config_file(project) else {
+            return Ok(self);
+        };
+        if !project_file.exists() {
+            debug!("No project configuration found at {:?}", project_file);
+            return Ok(self);
+        }
+
+        let overrides_str = fs::read_to_string(&project_file).context(format!(
+            "Failed to read project config file: {:?}",
+            project_file
+        ))?;
+        let overrides: toml::Table = toml::from_str(&overrides_str)
+            .context("Failed to parse project config file as TOML")?;
+
+        let mut config = self.merge(overrides).context(format!(
+            "Failed to apply project config from {:?}",
+            project_file
+        ))?;
+        config.project_path = Some(project_file.clone());
+
+        info!("Applied project configuration from {:?}", project_file);
+        Ok(config)
+    }
+
+    /// Merge a TOML table over this configuration
+    fn merge(self, overrides: toml::Table) -> Result<Self> {
+        let mut base =
+            toml::Table::try_from(&self).context("Failed to serialize base configuration")?;
+        merge_tables(&mut base, overrides);
+
+        let merged: Self = base
+            .try_into()
+            .context("Failed to parse merged configuration")?;
+        Ok(Self {
+            path: self.path,
+            project_path: self.project_path,
+            ..merged
+        })
+    }
+
+    /// Get the path of the config file for a project
+    fn project_config_file(project: &str) -> Option<PathBuf> {
+        // Project IDs never contain path separators, ignore anything that does
+        if project.is_empty() || project.contains(['/', '\\']) || project.contains("..") {
+            warn!(
+                "Ignoring project config for invalid project ID: {}",
+                project
+            );
+            return None;
+        }
+
+        Self::config_dir().map(|config_dir| {
+            config_dir
+                .join("projects")
+                .join(format!("{}.toml", project))
+        })
     }
 
     /// Load configuration from a specific file
@@ -72,13 +282,45 @@ impl Config {
         let config_str = fs::read_to_string(&path)
             .context(format!("Failed to read config file: {:?}", path.as_ref()))?;
 
-        let config: Config =
+        let mut config: Config =
             toml::from_str(&config_str).context("Failed to parse config file as TOML")?;
+        config.path = Some(path.as_ref().to_path_buf());
 
         debug!("Loaded configuration from {:?}", path.as_ref());
         Ok(config)
     }
 
+    /// Save a setting in the configuration file it was loaded from, keeping the other settings
+    ///
+    /// Settings of the project configuration file are saved there, as they
+    /// would override the base file. The file is rewritten from its parsed
+    /// content, so comments are not kept.
+    pub fn save_setting(&self, key: &str, value: toml::Value) -> Result<()> {
+        let config_file = match &self.project_path {
+            Some(project_file) if read_table(project_file)?.contains_key(key) => project_file,
+            _ => self
+                .path
+                .as_ref()
+                .context("Failed to determine the configuration file")?,
+        };
+
+        let mut table = read_table(config_file)?;
+        table.insert(key.to_string(), value);
+
+        let config_str = toml::to_string(&table).context("Failed to serialize configuration")?;
+        if let Some(config_dir) = config_file.parent() {
+            fs::create_dir_all(config_dir).context(format!(
+                "Failed to create config directory: {:?}",
+                config_dir
+            ))?;
+        }
+        fs::write(config_file, config_str)
+            .context(format!("Failed to write config file: {:?}", config_file))?;
+
+        debug!("Saved {} to {:?}", key, config_file);
+        Ok(())
+    }
+
     /// Get the configuration directory
     pub fn config_dir() -> Option<PathBuf> {
         ProjectDirs::from("com", "g1c", "g1c").map(|proj_dirs| proj_dirs.config_dir().to_path_buf())
@@ -100,16 +342,237 @@ impl Config {
         self
     }
 
-    /// Update config with a new refresh interval
-    pub fn with_refresh_interval(mut self, interval: u64) -> Self {
-        if interval > 0 {
-            self.refresh_interval = interval;
-        } else {
-            warn!(
-                "Invalid refresh interval provided: {}, using default",
-                interval
-            );
+    /// Update config with a service account to impersonate, if provided
+    pub fn with_impersonate_service_account(mut self, email: Option<String>) -> Self {
+        if let Some(email) = email {
+            self.impersonate_service_account = Some(email);
+        }
+        self
+    }
+
+    /// Update config with a new list filter, if provided
+    pub fn with_list_filter(mut self, list_filter: Option<String>) -> Self {
+        if let Some(list_filter) = list_filter {
+            self.list_filter = Some(list_filter);
+        }
+        self
+    }
+
+    /// Enable dry-run mode if requested
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        if dry_run {
+            self.dry_run = true;
+        }
+        self
+    }
+
+    /// Replace emoji with ASCII markers if requested
+    pub fn with_no_emoji(mut self, no_emoji: bool) -> Self {
+        if no_emoji {
+            self.emoji = false;
+        }
+        self
+    }
+
+    /// Drop colors and emoji if requested, for plain text output
+    pub fn with_no_color(mut self, no_color: bool) -> Self {
+        if no_color {
+            self.color = false;
+            self.emoji = false;
+        }
+        self
+    }
+
+    /// Disable mouse capture if requested
+    pub fn with_no_mouse(mut self, no_mouse: bool) -> Self {
+        if no_mouse {
+            self.mouse = false;
         }
         self
     }
+
+    /// Update config with a new refresh interval, if provided
+    pub fn with_refresh_interval(mut self, interval: Option<u64>) -> Self {
+        match interval {
+            Some(0) => warn!("Invalid refresh interval provided: 0, keeping the configured one"),
+            Some(interval) => self.refresh_interval = interval,
+            None => {}
+        }
+        self
+    }
+}
+
+/// Read a configuration file as a TOML table, empty if the file doesn't exist
+fn read_table(path: &Path) -> Result<toml::Table> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    let config_str =
+        fs::read_to_string(path).context(format!("Failed to read config file: {:?}", path))?;
+    toml::from_str(&config_str).context("Failed to parse config file as TOML")
+}
+
+/// Recursively merge `overrides` into `base`, nested tables are merged key by key
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(override_table)) => {
+                merge_tables(base_table, override_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn table(toml_str: &str) -> toml::Table {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn merge_tables_overrides_scalars() {
+        let mut base = table("refresh_interval = 5\ntheme = \"dark\"");
+        merge_tables(&mut base, table("refresh_interval = 10"));
+
+        assert_eq!(base, table("refresh_interval = 10\ntheme = \"dark\""));
+    }
+
+    #[test]
+    fn merge_tables_merges_nested_tables() {
+        let mut base = table(
+            "[status_styles.RUNNING]\ncolor = \"green\"\nlabel = \"UP\"\n\
+             [status_styles.STOPPING]\ncolor = \"yellow\"",
+        );
+        merge_tables(
+            &mut base,
+            table(
+                "[status_styles.RUNNING]\ncolor = \"blue\"\n[status_styles.UNKNOWN]\nascii = \"?\"",
+            ),
+        );
+
+        assert_eq!(
+            base,
+            table(
+                "[status_styles.RUNNING]\ncolor = \"blue\"\nlabel = \"UP\"\n\
+                 [status_styles.STOPPING]\ncolor = \"yellow\"\n\
+                 [status_styles.UNKNOWN]\nascii = \"?\""
+            )
+        );
+    }
+
+    #[test]
+    fn merge_tables_replaces_arrays() {
+        let mut base = table("columns = [\"name\", \"status\", \"zone\"]");
+        merge_tables(&mut base, table("columns = [\"name\"]"));
+
+        assert_eq!(base, table("columns = [\"name\"]"));
+    }
+
+    #[test]
+    fn merge_keeps_base_settings() {
+        let base = Config {
+            refresh_interval: 3,
+            dry_run: true,
+            ..Config::default()
+        };
+        let merged = base.merge(table("refresh_interval = 30")).unwrap();
+
+        assert_eq!(merged.refresh_interval, 30);
+        assert!(merged.dry_run);
+    }
+
+    /// Create an empty directory for a test's config files
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("g1c-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn save_setting_writes_the_loaded_file() {
+        let dir = test_dir("save-loaded");
+        let config_file = dir.join("custom.toml");
+        fs::write(&config_file, "refresh_interval = 3\n").unwrap();
+
+        let config = Config::load(config_file.to_str(), None).unwrap();
+        config
+            .save_setting("dense", toml::Value::Boolean(true))
+            .unwrap();
+
+        assert_eq!(
+            read_table(&config_file).unwrap(),
+            table("refresh_interval = 3\ndense = true")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn save_setting_keeps_project_settings_in_the_project_file() {
+        let dir = test_dir("save-project");
+        let config_file = dir.join("config.toml");
+        let project_file = dir.join("project.toml");
+        fs::write(&config_file, "refresh_interval = 3\n").unwrap();
+        fs::write(&project_file, "dense = false\n").unwrap();
+
+        let config = Config {
+            project_path: Some(project_file.clone()),
+            ..Config::load(config_file.to_str(), None).unwrap()
+        };
+        config
+            .save_setting("dense", toml::Value::Boolean(true))
+            .unwrap();
+        config
+            .save_setting("theme", toml::Value::String("dark".to_string()))
+            .unwrap();
+
+        assert_eq!(read_table(&project_file).unwrap(), table("dense = true"));
+        assert_eq!(
+            read_table(&config_file).unwrap(),
+            table("refresh_interval = 3\ntheme = \"dark\"")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn refresh_interval_is_only_overridden_when_given() {
+        let dir = test_dir("refresh-interval");
+        let config_file = dir.join("config.toml");
+        fs::write(&config_file, "refresh_interval = 30\n").unwrap();
+        let config = Config::load(config_file.to_str(), None).unwrap();
+
+        assert_eq!(
+            config.clone().with_refresh_interval(None).refresh_interval,
+            30
+        );
+        assert_eq!(
+            config
+                .clone()
+                .with_refresh_interval(Some(0))
+                .refresh_interval,
+            30
+        );
+        assert_eq!(config.with_refresh_interval(Some(10)).refresh_interval, 10);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn project_config_file_rejects_paths() {
+        for project in ["", "..", "../other", "a/b", "a\\b", "my..project"] {
+            assert_eq!(Config::project_config_file(project), None, "{:?}", project);
+        }
+    }
+
+    #[test]
+    fn project_config_file_uses_projects_dir() {
+        if let Some(path) = Config::project_config_file("my-project") {
+            assert!(path.ends_with("projects/my-project.toml"));
+        }
+    }
 }
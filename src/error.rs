@@ -1,18 +1,21 @@
-use std::io;
 use thiserror::Error;
 
 /// Custom error types for GCI application
 #[derive(Error, Debug)]
 pub enum GciError {
-    #[error("IO error: {0}")]
-    Io(#[from] io::Error),
+    #[error("No default project found. Please set a default project with 'gcloud config set project PROJECT_ID' or specify a project with --project")]
+    NoDefaultProject,
 
-    #[error("Configuration error: {0}")]
-    Config(String),
+    /// gcloud command failing because the credentials expired, with its context and stderr
+    #[error("{0}")]
+    AuthExpired(String),
 }
 
-impl From<serde_json::Error> for GciError {
-    fn from(err: serde_json::Error) -> Self {
-        GciError::Config(format!("JSON error: {}", err))
+impl GciError {
+    /// Check if an error, or one of its causes, is expired credentials
+    pub fn is_auth_expired(error: &anyhow::Error) -> bool {
+        error
+            .chain()
+            .any(|cause| matches!(cause.downcast_ref(), Some(GciError::AuthExpired(_))))
     }
 }
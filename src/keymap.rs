@@ -0,0 +1,874 @@
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+/// Action triggered by a key in the main view
+///
+/// Actions are named in snake case in the `[keybindings]` configuration table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Quit,
+    Suspend,
+    Help,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    Details,
+    RevealSecrets,
+    RawJson,
+    ClosePopup,
+    Refresh,
+    Start,
+    Stop,
+    Restart,
+    Delete,
+    Create,
+    Mark,
+    BulkProgress,
+    Resize,
+    Tags,
+    Ssh,
+    Logs,
+    Firewalls,
+    SerialConsole,
+    CopyCommand,
+    Export,
+    Filter,
+    OnlyMine,
+    OnlyPublic,
+    StatusFilter,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    Palette,
+    Regions,
+    Accounts,
+    CountFocus,
+    Dense,
+    Theme,
+    FasterRefresh,
+    SlowerRefresh,
+    CycleSort,
+    SortDirection,
+    Columns,
+    Command,
+    Notifications,
+}
+
+impl KeyAction {
+    /// Get the name of the action in the `[keybindings]` table, e.g. "page_up"
+    pub fn config_name(&self) -> String {
+        let mut name = String::new();
+        for (index, c) in format!("{:?}", self).chars().enumerate() {
+            if c.is_uppercase() && index > 0 {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+
+    /// Get the help section listing the action
+    pub fn help_section(&self) -> HelpSection {
+        match self {
+            Self::Up | Self::Down | Self::Top | Self::Bottom => HelpSection::Navigation,
+            Self::PageUp | Self::PageDown => HelpSection::Navigation,
+            Self::Palette | Self::Details | Self::ClosePopup => HelpSection::Navigation,
+            Self::Filter | Self::Search | Self::OnlyMine | Self::OnlyPublic => {
+                HelpSection::Filtering
+            }
+            Self::StatusFilter => HelpSection::Filtering,
+            Self::NextMatch | Self::PreviousMatch => HelpSection::Filtering,
+            Self::Start | Self::Stop | Self::Restart | Self::Delete => HelpSection::Actions,
+            Self::Create | Self::Mark | Self::BulkProgress => HelpSection::Actions,
+            Self::Resize | Self::Tags | Self::Ssh => HelpSection::Actions,
+            Self::RevealSecrets | Self::RawJson | Self::Logs | Self::Firewalls => {
+                HelpSection::Actions
+            }
+            Self::SerialConsole | Self::CopyCommand | Self::Export => HelpSection::Actions,
+            Self::Accounts => HelpSection::Miscellaneous,
+            Self::Refresh | Self::Regions | Self::CountFocus | Self::Dense | Self::Theme => {
+                HelpSection::Miscellaneous
+            }
+            Self::CycleSort | Self::SortDirection | Self::Columns => HelpSection::Miscellaneous,
+            Self::FasterRefresh | Self::SlowerRefresh => HelpSection::Miscellaneous,
+            Self::Command | Self::Notifications | Self::Help => HelpSection::Miscellaneous,
+            Self::Suspend | Self::Quit => HelpSection::Miscellaneous,
+        }
+    }
+
+    /// Get the description of the action in the help popup
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Quit => "Quit application",
+            Self::Suspend => "Suspend to the shell (resume with fg)",
+            Self::Help => "Show this help screen (type to search)",
+            Self::Up => "Move selection up (scroll network interfaces in details)",
+            Self::Down => "Move selection down (scroll network interfaces in details)",
+            Self::Top => "Move selection to the first instance",
+            Self::Bottom => "Move selection to the last instance",
+            Self::PageUp => "Move selection one page up",
+            Self::PageDown => "Move selection one page down",
+            Self::Details => "Show instance details",
+            Self::RevealSecrets => "Reveal/hide sensitive metadata in details",
+            Self::RawJson => "Show the raw describe JSON in details",
+            Self::ClosePopup => "Close popup or cancel action",
+            Self::Refresh => "Refresh instance data (only the shown instance in details)",
+            Self::Start => "Start selected instance, or all marked instances",
+            Self::Stop => "Stop selected instance, or all marked instances",
+            Self::Restart => "Restart selected instance, or all marked instances",
+            Self::Delete => "Delete selected instance, or all marked instances (with confirmation)",
+            Self::Create => "Create an instance from a form (name, zone, machine type, image, network)",
+            Self::Mark => {
+                "Mark/unmark the selected instance for bulk start/stop/restart/delete (Esc unmarks all)"
+            }
+            Self::BulkProgress => {
+                "Show the progress of the last bulk action (c cancels the queued instances)"
+            }
+            Self::Resize => "Change machine type (stops and starts a running instance)",
+            Self::Tags => "Edit the network tags of the selected instance",
+            Self::Ssh => {
+                "Open an ssh session to the running instance (through IAP without external IP)"
+            }
+            Self::Logs => "View recent Cloud Logging entries (+/- entry count, r refresh)",
+            Self::Firewalls => "View effective firewall rules (r refresh)",
+            Self::SerialConsole => "View the serial port output, e.g. boot messages (r refresh)",
+            Self::CopyCommand => "Copy a gcloud command, picked with the next key (see Copy Mode)",
+            Self::Export => "Export the shown instances to g1c-export-<timestamp>.csv",
+            Self::Filter => {
+                "Toggle filter mode (all words must match, family:e2 for a machine family, env:prod for a label)"
+            }
+            Self::OnlyMine => "Only show instances created by the active account",
+            Self::OnlyPublic => "Only show instances with an external IP",
+            Self::StatusFilter => "Only show RUNNING, TERMINATED, then SUSPENDED instances, then all",
+            Self::Search => "Toggle search mode, highlighting matches (Esc clears them)",
+            Self::NextMatch => "Select the next instance matching the search",
+            Self::PreviousMatch => "Select the previous instance matching the search",
+            Self::Palette => "Jump to an instance by fuzzy name",
+            Self::Regions => "Switch region or zone (a lists the zones of every region)",
+            Self::Accounts => "Switch the active gcloud account (gcloud config set account)",
+            Self::CountFocus => "Emphasize the total or shown instance count (saved on quit)",
+            Self::Dense => "Toggle the dense layout, one line per instance (saved on quit)",
+            Self::Theme => "Switch between the dark and light themes (saved on quit)",
+            Self::FasterRefresh => "Halve the refresh interval, down to 1s (saved on quit)",
+            Self::SlowerRefresh => "Double the refresh interval, up to 300s (saved on quit)",
+            Self::CycleSort => {
+                "Sort by the next column: status, name, zone, creation time... (saved on quit)"
+            }
+            Self::SortDirection => "Reverse the sort order (saved on quit)",
+            Self::Columns => "Choose the columns of the instance list (saved on quit)",
+            Self::Command => "Run a gcloud command in the project and show its output",
+            Self::Notifications => "Show the notification history (c to clear)",
+        }
+    }
+}
+
+/// Section of the help popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HelpSection {
+    Navigation,
+    Filtering,
+    Input,
+    Actions,
+    CopyMode,
+    Miscellaneous,
+}
+
+impl HelpSection {
+    /// Get the title of the section
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Navigation => "Navigation",
+            Self::Filtering => "Filtering and Searching",
+            Self::Input => "Filter and Search Input",
+            Self::Actions => "Instance Actions",
+            Self::CopyMode => "Copy Mode (after y)",
+            Self::Miscellaneous => "Miscellaneous",
+        }
+    }
+}
+
+/// Keys bound to an action, as listed in the help popup
+#[derive(Debug, Clone)]
+pub struct HelpEntry {
+    /// Section listing the action
+    pub section: HelpSection,
+    /// Keys bound to the action, e.g. "↑/k"
+    pub keys: String,
+    /// Description of the action
+    pub description: &'static str,
+}
+
+/// Keys handled outside of the binding table: section, keys and description
+///
+/// Every character key goes to the filter or search text while typing it,
+/// bound ones included. Only quit keys that don't type a character, such as
+/// Ctrl+c, still quit.
+const CONTEXT_HELP: [(HelpSection, &str, &str); 11] = [
+    (
+        HelpSection::Input,
+        "Other keys",
+        "Type the filter or search text",
+    ),
+    (
+        HelpSection::Input,
+        "Backspace/Delete",
+        "Remove the character before/after the cursor",
+    ),
+    (HelpSection::Input, "←/→, Home/End", "Move the cursor"),
+    (HelpSection::Input, "Ctrl+u", "Clear the text"),
+    (
+        HelpSection::Input,
+        "Esc, Enter",
+        "Stop typing, keeping the filter or search text",
+    ),
+    (
+        HelpSection::CopyMode,
+        "s/S/R/d",
+        "Copy the start/stop/reset/delete command of the selected instance",
+    ),
+    (
+        HelpSection::CopyMode,
+        "x",
+        "Copy the ssh command of the selected instance",
+    ),
+    (
+        HelpSection::CopyMode,
+        "i",
+        "Copy the external IP of the selected instance, or its internal IP",
+    ),
+    (
+        HelpSection::CopyMode,
+        "m",
+        "Copy the shown instances as a Markdown table",
+    ),
+    (HelpSection::CopyMode, "Other keys", "Cancel"),
+    (
+        HelpSection::Miscellaneous,
+        "Esc (help)",
+        "Clear the help search, then close the help",
+    ),
+];
+
+/// Curated set of key bindings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    /// Arrow keys and mnemonic letters
+    #[default]
+    Default,
+    /// Default bindings plus h/l to close/open popups and g/G to jump to the top/bottom
+    Vim,
+}
+
+/// Keys bound to an action in the configuration, one key or a list of keys
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConfiguredKeys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ConfiguredKeys {
+    /// Get the configured key names
+    fn names(&self) -> &[String] {
+        match self {
+            Self::One(name) => std::slice::from_ref(name),
+            Self::Many(names) => names,
+        }
+    }
+}
+
+/// Key with its modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a binding for a key without modifiers
+    const fn key(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Create a binding for a character without modifiers
+    const fn char(c: char) -> Self {
+        Self::key(KeyCode::Char(c))
+    }
+
+    /// Create a binding for Ctrl and a character
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Parse a key name from the configuration, e.g. "x", "D", "ctrl+d", "space" or "pagedown"
+    ///
+    /// Single characters are case-sensitive, named keys and the ctrl+ prefix are not.
+    fn parse(name: &str) -> Result<Self> {
+        let (modifiers, key) = match name.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("ctrl+") && name.len() > 5 => {
+                (KeyModifiers::CONTROL, &name[5..])
+            }
+            _ => (KeyModifiers::NONE, name),
+        };
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                function => match function
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse().ok())
+                {
+                    Some(number @ 1..=12) => KeyCode::F(number),
+                    _ => anyhow::bail!("Unknown key {:?}", name),
+                },
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+
+    /// Get the name of the key, as shown in the help popup
+    fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            code => format!("{:?}", code),
+        };
+
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            format!("Ctrl+{}", key)
+        } else {
+            key
+        }
+    }
+
+    /// Create a binding matching a key event
+    ///
+    /// Shift is part of the character itself ('S' vs 's'), and is only reported
+    /// by some terminals, so it is ignored.
+    fn from_event(key: &KeyEvent) -> Self {
+        Self {
+            code: key.code,
+            modifiers: key.modifiers - KeyModifiers::SHIFT,
+        }
+    }
+}
+
+/// Bindings shared by every preset, in help order
+const DEFAULT_BINDINGS: [(KeyBinding, KeyAction); 54] = [
+    (KeyBinding::char('q'), KeyAction::Quit),
+    (KeyBinding::ctrl('c'), KeyAction::Quit),
+    (KeyBinding::ctrl('z'), KeyAction::Suspend),
+    (KeyBinding::char('?'), KeyAction::Help),
+    (KeyBinding::key(KeyCode::Up), KeyAction::Up),
+    (KeyBinding::char('k'), KeyAction::Up),
+    (KeyBinding::key(KeyCode::Down), KeyAction::Down),
+    (KeyBinding::char('j'), KeyAction::Down),
+    (KeyBinding::key(KeyCode::Home), KeyAction::Top),
+    (KeyBinding::key(KeyCode::End), KeyAction::Bottom),
+    (KeyBinding::char('g'), KeyAction::Top),
+    (KeyBinding::char('G'), KeyAction::Bottom),
+    (KeyBinding::key(KeyCode::PageUp), KeyAction::PageUp),
+    (KeyBinding::key(KeyCode::PageDown), KeyAction::PageDown),
+    (KeyBinding::key(KeyCode::Enter), KeyAction::Details),
+    (KeyBinding::char('v'), KeyAction::RevealSecrets),
+    (KeyBinding::char('J'), KeyAction::RawJson),
+    (KeyBinding::key(KeyCode::Esc), KeyAction::ClosePopup),
+    (KeyBinding::char('r'), KeyAction::Refresh),
+    (KeyBinding::char('-'), KeyAction::FasterRefresh),
+    (KeyBinding::char('+'), KeyAction::SlowerRefresh),
+    (KeyBinding::char('s'), KeyAction::Start),
+    (KeyBinding::char('S'), KeyAction::Stop),
+    (KeyBinding::char('R'), KeyAction::Restart),
+    (KeyBinding::char('d'), KeyAction::Delete),
+    (KeyBinding::char('A'), KeyAction::Create),
+    (KeyBinding::char(' '), KeyAction::Mark),
+    (KeyBinding::char('b'), KeyAction::BulkProgress),
+    (KeyBinding::char('T'), KeyAction::Resize),
+    (KeyBinding::char('E'), KeyAction::Tags),
+    (KeyBinding::char('x'), KeyAction::Ssh),
+    (KeyBinding::char('w'), KeyAction::Firewalls),
+    (KeyBinding::char('V'), KeyAction::SerialConsole),
+    (KeyBinding::char('y'), KeyAction::CopyCommand),
+    (KeyBinding::char('e'), KeyAction::Export),
+    (KeyBinding::char('f'), KeyAction::Filter),
+    (KeyBinding::char('m'), KeyAction::OnlyMine),
+    (KeyBinding::char('P'), KeyAction::OnlyPublic),
+    (KeyBinding::key(KeyCode::Tab), KeyAction::StatusFilter),
+    (KeyBinding::char('/'), KeyAction::Search),
+    (KeyBinding::char('n'), KeyAction::NextMatch),
+    (KeyBinding::char('p'), KeyAction::PreviousMatch),
+    (KeyBinding::char(':'), KeyAction::Palette),
+    (KeyBinding::ctrl('p'), KeyAction::Palette),
+    (KeyBinding::char('z'), KeyAction::Regions),
+    (KeyBinding::char('a'), KeyAction::Accounts),
+    (KeyBinding::char('c'), KeyAction::CountFocus),
+    (KeyBinding::char('D'), KeyAction::Dense),
+    (KeyBinding::char('t'), KeyAction::Theme),
+    (KeyBinding::char('o'), KeyAction::CycleSort),
+    (KeyBinding::char('O'), KeyAction::SortDirection),
+    (KeyBinding::char('C'), KeyAction::Columns),
+    (KeyBinding::char('!'), KeyAction::Command),
+    (KeyBinding::char('N'), KeyAction::Notifications),
+];
+
+/// Table of key bindings consulted by the main view
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Bindings, in help order
+    bindings: Vec<(KeyBinding, KeyAction)>,
+}
+
+impl Keymap {
+    /// Create the binding table of a preset, with the keys configured in `[keybindings]`
+    ///
+    /// The configured keys of an action replace its preset keys, and take over
+    /// the keys they share with other actions, which keep their other keys.
+    /// Unknown actions or keys, a key configured for two actions, and an action
+    /// left without any key by the keys taken over, are errors.
+    pub fn new(preset: KeymapPreset, configured: &HashMap<String, ConfiguredKeys>) -> Result<Self> {
+        let mut configured_bindings: BTreeMap<String, (KeyBinding, KeyAction)> = BTreeMap::new();
+        let mut configured_actions = Vec::new();
+
+        // Sort by action name, so errors don't depend on the table order
+        let mut entries: Vec<_> = configured.iter().collect();
+        entries.sort_by_key(|(name, _)| name.as_str());
+        for (name, keys) in entries {
+            let action = KeyAction::deserialize(name.as_str().into_deserializer())
+                .map_err(|e: serde::de::value::Error| anyhow::anyhow!("{}", e))
+                .context(format!("Invalid action {:?} in [keybindings]", name))?;
+            configured_actions.push(action);
+
+            for key in keys.names() {
+                let binding = KeyBinding::parse(key)
+                    .context(format!("Invalid key for {} in [keybindings]", name))?;
+                if let Some((_, other)) = configured_bindings
+                    .values()
+                    .find(|(bound, other)| *bound == binding && *other != action)
+                {
+                    anyhow::bail!(
+                        "Key {:?} is bound to both {:?} and {:?} in [keybindings]",
+                        key,
+                        other,
+                        action
+                    );
+                }
+                configured_bindings.insert(key.clone(), (binding, action));
+            }
+        }
+
+        // Preset bindings of reconfigured actions or taken keys give way to the configured ones
+        let preset_bindings = Self::from_preset(preset).bindings;
+        let mut bindings = preset_bindings.clone();
+        bindings.retain(|(binding, action)| {
+            let taken = configured_bindings
+                .values()
+                .any(|(configured, _)| configured == binding);
+            if taken && !configured_actions.contains(action) {
+                info!(
+                    "Key {} unbound from {:?} by [keybindings]",
+                    binding.label(),
+                    action
+                );
+            }
+            !taken && !configured_actions.contains(action)
+        });
+        bindings.extend(configured_bindings.into_values());
+
+        // Every key of an action that isn't configured can't be taken, or it would be unusable
+        for (binding, action) in &preset_bindings {
+            if configured_actions.contains(action)
+                || bindings.iter().any(|(_, bound)| bound == action)
+            {
+                continue;
+            }
+            let taker = bindings
+                .iter()
+                .find(|(bound, _)| bound == binding)
+                .map(|(_, taker)| taker);
+            if let Some(taker) = taker {
+                anyhow::bail!(
+                    "{:?} has no key left, as [keybindings] gives {} to {:?}. Bind {} to another key",
+                    action,
+                    binding.label(),
+                    taker,
+                    action.config_name()
+                );
+            }
+        }
+
+        Ok(Self { bindings })
+    }
+
+    /// Create the binding table of a preset
+    fn from_preset(preset: KeymapPreset) -> Self {
+        let mut bindings = DEFAULT_BINDINGS.to_vec();
+
+        match preset {
+            KeymapPreset::Default => {
+                bindings.push((KeyBinding::char('l'), KeyAction::Logs));
+            }
+            KeymapPreset::Vim => {
+                // l moves "into" the selection like Enter, so logs move to L
+                bindings.extend([
+                    (KeyBinding::char('h'), KeyAction::ClosePopup),
+                    (KeyBinding::char('l'), KeyAction::Details),
+                    (KeyBinding::char('L'), KeyAction::Logs),
+                ]);
+            }
+        }
+
+        Self { bindings }
+    }
+
+    /// Get the action bound to a key event
+    pub fn action(&self, key: &KeyEvent) -> Option<KeyAction> {
+        let binding = KeyBinding::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == binding)
+            .map(|(_, action)| *action)
+    }
+
+    /// Get the help entries of the bound actions, by section then binding order
+    pub fn help_entries(&self) -> Vec<HelpEntry> {
+        let mut entries: Vec<(KeyAction, Vec<String>)> = Vec::new();
+        for (binding, action) in &self.bindings {
+            match entries.iter_mut().find(|(bound, _)| bound == action) {
+                Some((_, keys)) => keys.push(binding.label()),
+                None => entries.push((*action, vec![binding.label()])),
+            }
+        }
+
+        let mut entries: Vec<HelpEntry> = entries
+            .into_iter()
+            .map(|(action, keys)| HelpEntry {
+                section: action.help_section(),
+                keys: keys.join("/"),
+                description: action.description(),
+            })
+            .chain(
+                CONTEXT_HELP
+                    .iter()
+                    .map(|(section, keys, description)| HelpEntry {
+                        section: *section,
+                        keys: keys.to_string(),
+                        description,
+                    }),
+            )
+            .collect();
+        // Stable sort, keeping the binding order within a section
+        entries.sort_by_key(|entry| entry.section);
+        entries
+    }
+
+    /// Check if a key event quits the application
+    pub fn is_quit(&self, key: &KeyEvent) -> bool {
+        self.action(key) == Some(KeyAction::Quit)
+    }
+
+    /// Check if a key event quits the application while typing a filter or search
+    ///
+    /// Quit keys typing a character, such as 'q', are typed instead.
+    pub fn is_quit_while_typing(&self, key: &KeyEvent) -> bool {
+        let typed = matches!(key.code, KeyCode::Char(_))
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        self.is_quit(key) && !typed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Every action, the match failing to compile when one is added
+    fn all_actions() -> Vec<KeyAction> {
+        use KeyAction::*;
+        let actions = vec![
+            Quit,
+            Suspend,
+            Help,
+            Up,
+            Down,
+            Top,
+            Bottom,
+            PageUp,
+            PageDown,
+            Details,
+            RevealSecrets,
+            RawJson,
+            ClosePopup,
+            Refresh,
+            Start,
+            Stop,
+            Restart,
+            Delete,
+            Create,
+            Mark,
+            BulkProgress,
+            Resize,
+            Tags,
+            Ssh,
+            Logs,
+            Firewalls,
+            SerialConsole,
+            CopyCommand,
+            Export,
+            Filter,
+            OnlyMine,
+            OnlyPublic,
+            StatusFilter,
+            Search,
+            NextMatch,
+            PreviousMatch,
+            Palette,
+            Regions,
+            Accounts,
+            CountFocus,
+            Dense,
+            Theme,
+            FasterRefresh,
+            SlowerRefresh,
+            CycleSort,
+            SortDirection,
+            Columns,
+            Command,
+            Notifications,
+        ];
+        for action in &actions {
+            match action {
+                Quit | Suspend | Help | Up | Down | Top | Bottom | PageUp | PageDown | Details
+                | RevealSecrets | RawJson | ClosePopup | Refresh | Start | Stop | Restart
+                | Delete | Create | Mark | BulkProgress | Resize | Tags | Ssh | Logs
+                | Firewalls | SerialConsole | CopyCommand | Export | Filter | OnlyMine
+                | OnlyPublic | StatusFilter | Search | NextMatch | PreviousMatch | Palette
+                | Regions | Accounts | CountFocus | Dense | Theme | FasterRefresh
+                | SlowerRefresh | CycleSort | SortDirection | Columns | Command | Notifications => {
+                }
+            }
+        }
+        actions
+    }
+
+    const PRESETS: [KeymapPreset; 2] = [KeymapPreset::Default, KeymapPreset::Vim];
+
+    #[test]
+    fn every_action_has_a_help_entry() {
+        for preset in PRESETS {
+            let entries = Keymap::from_preset(preset).help_entries();
+            for action in all_actions() {
+                let matching: Vec<&HelpEntry> = entries
+                    .iter()
+                    .filter(|entry| entry.description == action.description())
+                    .collect();
+                assert_eq!(matching.len(), 1, "{:?} in {:?}", action, preset);
+                assert_eq!(matching[0].section, action.help_section());
+                assert!(!matching[0].keys.is_empty(), "{:?} in {:?}", action, preset);
+            }
+        }
+    }
+
+    #[test]
+    fn help_lists_every_bound_key() {
+        for preset in PRESETS {
+            let keymap = Keymap::from_preset(preset);
+            let entries = keymap.help_entries();
+            for (_, action) in &keymap.bindings {
+                let keys: Vec<String> = keymap
+                    .bindings
+                    .iter()
+                    .filter(|(_, bound)| bound == action)
+                    .map(|(binding, _)| binding.label())
+                    .collect();
+                let entry = entries
+                    .iter()
+                    .find(|entry| entry.description == action.description())
+                    .unwrap();
+                assert_eq!(entry.keys, keys.join("/"), "{:?} in {:?}", action, preset);
+            }
+        }
+    }
+
+    #[test]
+    fn context_help_is_consistent() {
+        let entries = Keymap::from_preset(KeymapPreset::Default).help_entries();
+
+        // Context entries are listed once, apart from the bound actions
+        for (section, keys, description) in CONTEXT_HELP {
+            assert!(!keys.is_empty());
+            let matching: Vec<&HelpEntry> = entries
+                .iter()
+                .filter(|entry| entry.description == description)
+                .collect();
+            assert_eq!(matching.len(), 1, "{}", description);
+            assert_eq!(matching[0].section, section);
+        }
+        assert_eq!(entries.len(), all_actions().len() + CONTEXT_HELP.len());
+
+        // Sections are grouped
+        assert!(entries
+            .windows(2)
+            .all(|pair| pair[0].section <= pair[1].section));
+    }
+
+    #[test]
+    fn only_non_character_quit_keys_quit_while_typing() {
+        let keymap = Keymap::from_preset(KeymapPreset::Default);
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+        assert!(keymap.is_quit(&key(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(!keymap.is_quit_while_typing(&key(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(keymap.is_quit_while_typing(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(!keymap.is_quit_while_typing(&key(KeyCode::Char('s'), KeyModifiers::NONE)));
+    }
+
+    /// Build the default keymap with the configured keys
+    fn configured(keybindings: &[(&str, &[&str])]) -> Result<Keymap> {
+        let keybindings = keybindings
+            .iter()
+            .map(|(action, keys)| {
+                let keys = keys.iter().map(|key| key.to_string()).collect();
+                (action.to_string(), ConfiguredKeys::Many(keys))
+            })
+            .collect();
+        Keymap::new(KeymapPreset::Default, &keybindings)
+    }
+
+    fn press(keymap: &Keymap, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyAction> {
+        keymap.action(&KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn parse_key_names() {
+        let cases = [
+            ("x", KeyCode::Char('x'), KeyModifiers::NONE),
+            ("X", KeyCode::Char('X'), KeyModifiers::NONE),
+            ("ctrl+d", KeyCode::Char('d'), KeyModifiers::CONTROL),
+            ("Ctrl+D", KeyCode::Char('D'), KeyModifiers::CONTROL),
+            ("space", KeyCode::Char(' '), KeyModifiers::NONE),
+            ("PageDown", KeyCode::PageDown, KeyModifiers::NONE),
+            ("f5", KeyCode::F(5), KeyModifiers::NONE),
+            ("+", KeyCode::Char('+'), KeyModifiers::NONE),
+        ];
+        for (name, code, modifiers) in cases {
+            assert_eq!(
+                KeyBinding::parse(name).unwrap(),
+                KeyBinding { code, modifiers },
+                "{}",
+                name
+            );
+        }
+
+        for name in ["", "ctrl+", "f13", "hyper", "ab"] {
+            assert!(KeyBinding::parse(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn configured_keys_replace_the_preset_keys() {
+        let keymap = configured(&[("delete", &["X"]), ("quit", &["Q", "ctrl+c"])]).unwrap();
+
+        assert_eq!(
+            press(&keymap, KeyCode::Char('X'), KeyModifiers::NONE),
+            Some(KeyAction::Delete)
+        );
+        assert_eq!(press(&keymap, KeyCode::Char('d'), KeyModifiers::NONE), None);
+        assert_eq!(
+            press(&keymap, KeyCode::Char('Q'), KeyModifiers::NONE),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(press(&keymap, KeyCode::Char('q'), KeyModifiers::NONE), None);
+
+        let help = keymap.help_entries();
+        let delete = help
+            .iter()
+            .find(|entry| entry.description == KeyAction::Delete.description())
+            .unwrap();
+        assert_eq!(delete.keys, "X");
+    }
+
+    #[test]
+    fn configured_keys_are_taken_from_other_actions() {
+        // k also moves up, which keeps the arrow key
+        let keymap = configured(&[("restart", &["k"])]).unwrap();
+        assert_eq!(
+            press(&keymap, KeyCode::Char('k'), KeyModifiers::NONE),
+            Some(KeyAction::Restart)
+        );
+        assert_eq!(
+            press(&keymap, KeyCode::Up, KeyModifiers::NONE),
+            Some(KeyAction::Up)
+        );
+
+        // r is the only key refreshing
+        let error = configured(&[("restart", &["r"])]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Refresh has no key left, as [keybindings] gives r to Restart. Bind refresh to another key"
+        );
+        let keymap = configured(&[("restart", &["r"]), ("refresh", &["f5"])]).unwrap();
+        assert_eq!(
+            press(&keymap, KeyCode::F(5), KeyModifiers::NONE),
+            Some(KeyAction::Refresh)
+        );
+    }
+
+    #[test]
+    fn config_names_are_the_keybindings_names() {
+        for action in all_actions() {
+            let name = action.config_name();
+            let parsed = KeyAction::deserialize(name.as_str().into_deserializer())
+                .map_err(|e: serde::de::value::Error| e.to_string());
+            assert_eq!(parsed, Ok(action), "{}", name);
+        }
+        assert_eq!(KeyAction::PageUp.config_name(), "page_up");
+    }
+
+    #[test]
+    fn invalid_keybindings_are_errors() {
+        assert!(configured(&[("delete", &["X"]), ("quit", &["X"])]).is_err());
+        assert!(configured(&[("explode", &["X"])]).is_err());
+        assert!(configured(&[("delete", &["hyper"])]).is_err());
+        // The same key twice for one action is fine
+        assert!(configured(&[("delete", &["X", "X"])]).is_ok());
+    }
+}
@@ -1,18 +1,79 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
 use std::path::Path;
-use tracing_subscriber::{self, filter::LevelFilter, prelude::*, Layer};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing_appender::non_blocking::{NonBlocking, NonBlockingBuilder, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{
+    self,
+    filter::LevelFilter,
+    fmt::writer::{EitherWriter, OptionalWriter},
+    prelude::*,
+    Layer,
+};
+
+/// Whether errors are printed on stderr, false while the TUI owns the terminal
+static CONSOLE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// How often the log file is rotated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LogRotation {
+    /// Write to a new file each day, named after the log file with the UTC date appended
+    #[default]
+    Daily,
+    /// Always append to the same file
+    Never,
+}
+
+impl From<LogRotation> for Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        }
+    }
+}
+
+/// Open the log file of the current period, keeping its previous content
+///
+/// Events are written by a background thread, without dropping any of them
+/// when it falls behind. They are flushed when the returned guard is dropped.
+fn open_log_file(path: &Path, rotation: LogRotation) -> Result<(NonBlocking, WorkerGuard)> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Log file {} has no file name", path.display()))?;
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let appender = RollingFileAppender::builder()
+        .rotation(rotation.into())
+        .filename_prefix(file_name.to_string_lossy())
+        .build(directory)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+
+    Ok(NonBlockingBuilder::default().lossy(false).finish(appender))
+}
 
 /// Initialize logging for the application
 ///
 /// This function sets up tracing with different configurations:
-/// - Console output limited to ERROR level to avoid interfering with the TUI
+/// - Console output on stderr limited to ERROR level, muted while the TUI is shown
 /// - Optional file logging with configurable level for debugging
 /// - Selectable format (text or JSON) for log output
+/// - Daily rotation of the log file, or a single file appended across sessions
+///
+/// The returned guard must be kept until exiting, dropping it flushes the log file.
 pub fn init(
     log_file: Option<&str>,
     log_level: Option<&str>,
     log_format: Option<&str>,
-) -> Result<()> {
+    rotation: LogRotation,
+) -> Result<Option<WorkerGuard>> {
     // Parse the log level
     let level_str = log_level.unwrap_or("info");
     let level_filter = match level_str.to_lowercase().as_str() {
@@ -28,14 +89,14 @@ pub fn init(
     let is_json = matches!(log_format, Some(format) if format.to_lowercase() == "json");
 
     // Initialize logging based on whether a log file was provided
-    if let Some(log_path) = log_file {
+    let guard = if let Some(log_path) = log_file {
         // Create parent directory if needed
         if let Some(parent) = Path::new(log_path).parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        // Set up file logging
-        let file = std::fs::File::create(log_path)?;
+        // Set up file logging, appending to the file of the current period
+        let (file, guard) = open_log_file(Path::new(log_path), rotation)?;
 
         if is_json {
             // Initialize JSON logging
@@ -44,38 +105,73 @@ pub fn init(
             // Initialize text logging
             init_text_logging(file, level_filter);
         }
+        Some(guard)
     } else {
         // Console-only logging with ERROR level to avoid disrupting TUI
         init_console_logging();
-    }
+        None
+    };
 
     // Log initialization message
     tracing::info!("Logging initialized with level {}", level_str);
 
-    Ok(())
+    Ok(guard)
+}
+
+/// Generate a short ID correlating the log events of a user action
+///
+/// The counter keeps IDs unique within a session, the randomly seeded hasher
+/// keeps them distinct across sessions sharing a log file.
+pub fn correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+/// Print errors on stderr or not, errors are already notified in the TUI
+///
+/// Text written to stderr would be drawn over the alternate screen, and stay
+/// there until the next full redraw.
+pub fn set_console_enabled(enabled: bool) {
+    CONSOLE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Get the console writer, discarding events while the console is disabled
+fn console_writer() -> OptionalWriter<io::Stderr> {
+    if CONSOLE_ENABLED.load(Ordering::Relaxed) {
+        EitherWriter::A(io::stderr())
+    } else {
+        OptionalWriter::none()
+    }
 }
 
-/// Initialize console-only logging with ERROR level
+/// Initialize console-only logging with ERROR level on stderr
 fn init_console_logging() {
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::fmt::layer().with_filter(
-                tracing_subscriber::filter::EnvFilter::builder()
-                    .with_default_directive(LevelFilter::ERROR.into())
-                    .from_env_lossy(),
-            ),
+            tracing_subscriber::fmt::layer()
+                .with_writer(console_writer)
+                .with_filter(
+                    tracing_subscriber::filter::EnvFilter::builder()
+                        .with_default_directive(LevelFilter::ERROR.into())
+                        .from_env_lossy(),
+                ),
         )
         .init();
 }
 
 /// Initialize text format logging to file with console error logs
-fn init_text_logging(file: std::fs::File, level: LevelFilter) {
-    // Console layer with ERROR level only
-    let console_layer = tracing_subscriber::fmt::layer().with_filter(
-        tracing_subscriber::filter::EnvFilter::builder()
-            .with_default_directive(LevelFilter::ERROR.into())
-            .from_env_lossy(),
-    );
+fn init_text_logging(file: NonBlocking, level: LevelFilter) {
+    // Console layer with ERROR level only, on stderr to keep stdout clean
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_writer(console_writer)
+        .with_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(LevelFilter::ERROR.into())
+                .from_env_lossy(),
+        );
 
     // File layer with user-specified level
     let file_layer = tracing_subscriber::fmt::layer()
@@ -98,13 +194,15 @@ fn init_text_logging(file: std::fs::File, level: LevelFilter) {
 }
 
 /// Initialize JSON format logging to file with console error logs
-fn init_json_logging(file: std::fs::File, level: LevelFilter) {
-    // Console layer with ERROR level only
-    let console_layer = tracing_subscriber::fmt::layer().with_filter(
-        tracing_subscriber::filter::EnvFilter::builder()
-            .with_default_directive(LevelFilter::ERROR.into())
-            .from_env_lossy(),
-    );
+fn init_json_logging(file: NonBlocking, level: LevelFilter) {
+    // Console layer with ERROR level only, on stderr to keep stdout clean
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_writer(console_writer)
+        .with_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(LevelFilter::ERROR.into())
+                .from_env_lossy(),
+        );
 
     // File layer with user-specified level in JSON format
     let file_layer = tracing_subscriber::fmt::layer()
@@ -126,3 +224,46 @@ fn init_json_logging(file: std::fs::File, level: LevelFilter) {
         .with(file_layer)
         .init();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    /// Create an empty directory for the log files of a test
+    fn log_dir(test: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("g1c-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn daily_log_file_is_named_after_the_date() {
+        let dir = log_dir("daily");
+        let (mut file, guard) = open_log_file(&dir.join("g1c.log"), LogRotation::Daily).unwrap();
+        file.write_all(b"event\n").unwrap();
+        drop(guard);
+
+        let date = chrono::Utc::now().format("%Y-%m-%d");
+        let content = std::fs::read_to_string(dir.join(format!("g1c.log.{}", date))).unwrap();
+        assert_eq!(content, "event\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn log_file_without_rotation_is_appended() {
+        let dir = log_dir("never");
+        let path = dir.join("g1c.log");
+        std::fs::write(&path, "previous session\n").unwrap();
+
+        let (mut file, guard) = open_log_file(&path, LogRotation::Never).unwrap();
+        file.write_all(b"event\n").unwrap();
+        drop(guard);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "previous session\nevent\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
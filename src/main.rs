@@ -1,16 +1,35 @@
 use anyhow::Result;
-use clap::Parser;
-use tracing::{error, info};
+use clap::{Parser, ValueEnum};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::{backend::Backend, Terminal};
+use std::io::Write;
+use tracing::{error, info, warn};
 
 mod app;
+mod clipboard;
 mod cloud;
 mod config;
 mod error;
+mod keymap;
 mod logging;
 mod ui;
 
 use crate::app::App;
+use crate::cloud::{check_gcloud, export, CloudClient};
 use crate::config::Config;
+use crate::error::GciError;
+use crate::logging::LogRotation;
+use crate::ui::Column;
+
+/// Output format of the non-interactive instance list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+    Yaml,
+    Markdown,
+}
 
 /// Terminal UI for monitoring Google Cloud Instances
 #[derive(Parser, Debug)]
@@ -24,9 +43,17 @@ struct Args {
     #[arg(short = 'g', long)]
     region: Option<String>,
 
-    /// Auto-refresh interval in seconds
-    #[arg(short, long, default_value_t = 5)]
-    refresh: u64,
+    /// Service account impersonated by gcloud commands (short-lived credentials)
+    #[arg(long, value_name = "EMAIL")]
+    impersonate_service_account: Option<String>,
+
+    /// gcloud filter expression used to list instances (e.g. labels.env=prod)
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Auto-refresh interval in seconds, overriding refresh_interval from the config
+    #[arg(short, long)]
+    refresh: Option<u64>,
 
     /// Path to config file
     #[arg(short, long)]
@@ -43,6 +70,34 @@ struct Args {
     /// Log format (json or text)
     #[arg(short = 'F', long, default_value = "text")]
     log_format: Option<String>,
+
+    /// Log file rotation (daily appends the date to the log file name)
+    #[arg(long, value_enum, default_value_t = LogRotation::Daily)]
+    log_rotation: LogRotation,
+
+    /// Log the commands changing instances (start, stop...) without running them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Use ASCII markers instead of emoji, for terminals without emoji glyphs
+    #[arg(long)]
+    no_emoji: bool,
+
+    /// Disable colors and emoji (also set by a non-empty NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Don't capture the mouse, so text can be selected with the terminal
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// List instances to stdout and exit, without starting the TUI
+    #[arg(long, alias = "headless")]
+    list: bool,
+
+    /// Output format of the instance list
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, requires = "list")]
+    format: OutputFormat,
 }
 
 #[tokio::main]
@@ -50,69 +105,187 @@ async fn main() -> Result<()> {
     // Parse command line arguments
     let args = Args::parse();
 
-    // Initialize logging
-    logging::init(
+    // Initialize logging, keeping the guard flushing the log file until exiting
+    let log_guard = logging::init(
         args.log_file.as_deref(),
         args.log_level.as_deref(),
         args.log_format.as_deref(),
+        args.log_rotation,
     )?;
     info!("Application logging setup");
 
-    // Load configuration
-    let config = match Config::load(args.config.as_deref()) {
+    // Exit on an unreadable configuration or invalid key bindings, rather than
+    // running without settings such as dry_run or confirm_destructive
+    let config = match Config::load(args.config.as_deref(), args.project.as_deref()) {
         Ok(config) => config,
         Err(e) => {
-            error!("Failed to load configuration: {}", e);
-            Config::default()
+            eprintln!("Failed to load configuration: {:#}", e);
+            drop(log_guard);
+            std::process::exit(1);
         }
     };
+    if let Err(e) = config.keymap() {
+        eprintln!("Invalid configuration: {:#}", e);
+        drop(log_guard);
+        std::process::exit(1);
+    }
 
     // Override config with command line arguments
     let config = config
         .with_project(args.project)
         .with_region(args.region)
+        .with_impersonate_service_account(args.impersonate_service_account)
+        .with_list_filter(args.filter)
+        .with_dry_run(args.dry_run)
+        .with_no_emoji(args.no_emoji)
+        .with_no_color(args.no_color || no_color_env())
+        .with_no_mouse(args.no_mouse)
         .with_refresh_interval(args.refresh);
 
+    // Every view needs gcloud, report its absence before touching the screen
+    if let Err(e) = check_gcloud(config.credentials_path.is_none()) {
+        eprintln!("{}", gcloud_guidance(&e));
+        drop(log_guard);
+        std::process::exit(1);
+    }
+
+    // Print the instance list instead of starting the TUI
+    if args.list {
+        return list_instances(&config, args.format).await;
+    }
+
     // Setup terminal
-    let mut terminal = ui::setup_terminal()?;
+    let mut terminal = ui::setup_terminal(config.mouse)?;
+
+    // Create the application, restoring the terminal if that fails
+    let mut app = match create_app(config, &mut terminal).await {
+        Ok(Some(app)) => app,
+        Ok(None) => {
+            ui::restore_terminal()?;
+            return Ok(());
+        }
+        Err(e) => {
+            ui::restore_terminal()?;
+            return Err(e);
+        }
+    };
 
-    // Create and run the application
-    let result = App::new(config).await?.run(&mut terminal).await;
+    // Run the application
+    let result = app.run(&mut terminal).await;
 
     // Restore terminal
     ui::restore_terminal()?;
 
     // Handle application result
     if let Err(err) = result {
-        // Log to stderr via tracing
+        // Log to stderr and to the log file via tracing
         error!("Application error: {}", err);
 
-        // Also log directly to file if one is configured
-        if let Some(log_file) = args.log_file {
-            if let Ok(mut file) = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_file)
-            {
-                // Format error message based on log format
-                let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                let message = if args.log_format.as_deref() == Some("json") {
-                    format!(
-                        "{{\"timestamp\":\"{}\",\"level\":\"ERROR\",\"target\":\"g1c\",\"message\":\"Application error: {}\"}}\n",
-                        now, err
-                    )
-                } else {
-                    format!("[{} ERROR g1c] Application error: {}\n", now, err)
-                };
-
-                // Write directly to the log file
-                use std::io::Write;
-                let _ = file.write_all(message.as_bytes());
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// Create the application, guiding the user until a project is set
+///
+/// Returns `None` if the user quits from the guidance screen.
+async fn create_app<B: Backend>(config: Config, terminal: &mut Terminal<B>) -> Result<Option<App>> {
+    loop {
+        match App::new(config.clone()).await {
+            Ok(app) => return Ok(Some(app)),
+            Err(e) if is_no_default_project(&e) => {
+                info!("No project set, showing guidance screen");
+                if !wait_for_retry(terminal, config.emoji && config.color, config.color)? {
+                    return Ok(None);
+                }
             }
+            Err(e) => return Err(e),
         }
+    }
+}
 
-        return Err(err);
+/// Show the no project guidance screen until the user retries (true) or quits (false)
+fn wait_for_retry<B: Backend>(
+    terminal: &mut Terminal<B>,
+    emoji: bool,
+    color: bool,
+) -> Result<bool> {
+    loop {
+        terminal.draw(|frame| ui::render_no_project(frame, emoji, color))?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char('r') | KeyCode::Enter => return Ok(true),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(false)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Explain how to install and authenticate gcloud after a failed check
+fn gcloud_guidance(error: &anyhow::Error) -> String {
+    format!(
+        "g1c needs the gcloud CLI, installed and authenticated.\n\n\
+         Error: {:#}\n\n\
+         Install it: https://cloud.google.com/sdk/docs/install\n\
+         Then log in: gcloud auth login",
+        error
+    )
+}
+
+/// Check if an error is caused by no project being set
+fn is_no_default_project(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<GciError>(),
+            Some(GciError::NoDefaultProject)
+        )
+    })
+}
+
+/// Check if the NO_COLOR convention asks for plain output, see https://no-color.org
+fn no_color_env() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// List instances to stdout in the requested format
+async fn list_instances(config: &Config, format: OutputFormat) -> Result<()> {
+    let cloud_client = CloudClient::new(config).await?;
+    let listing = cloud_client.list_instances().await?;
+    for (scope, e) in &listing.failures {
+        warn!("Failed to list instances in {}: {:#}", scope, e);
+    }
+    let mut instances = listing.instances;
+    config.sort_key.sort(&mut instances, config.sort_ascending);
+
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        OutputFormat::Table => {
+            let columns = Column::visible(
+                &config.columns,
+                config.show_disk_column,
+                cloud_client.is_multi_project(),
+            );
+            ui::write_table(&mut stdout, &instances, &columns)?;
+        }
+        OutputFormat::Json => export::to_json(&instances, &mut stdout)?,
+        OutputFormat::Csv => export::to_csv(&instances, &mut stdout)?,
+        OutputFormat::Yaml => export::to_yaml(&instances, &mut stdout)?,
+        OutputFormat::Markdown => {
+            let columns = Column::visible(
+                &config.columns,
+                config.show_disk_column,
+                cloud_client.is_multi_project(),
+            );
+            ui::write_markdown_table(&mut stdout, &instances, &columns)?;
+        }
     }
+    stdout.flush()?;
 
     Ok(())
 }
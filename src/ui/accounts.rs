@@ -0,0 +1,123 @@
+use ratatui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::cloud::Account;
+
+/// State of the account switcher popup
+pub struct AccountsView {
+    /// Accounts gcloud has credentials for
+    accounts: Vec<Account>,
+    /// Selected account
+    selected: usize,
+    /// Error message if the accounts could not be listed
+    error: Option<String>,
+}
+
+impl AccountsView {
+    /// Create an account switcher, selecting the active account
+    pub fn new(accounts: Vec<Account>) -> Self {
+        let selected = accounts
+            .iter()
+            .position(|account| account.active)
+            .unwrap_or(0);
+
+        Self {
+            accounts,
+            selected,
+            error: None,
+        }
+    }
+
+    /// Create an account switcher showing why accounts could not be listed
+    pub fn with_error(error: String) -> Self {
+        Self {
+            accounts: Vec::new(),
+            selected: 0,
+            error: Some(error),
+        }
+    }
+
+    /// Select the previous account
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next account
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.accounts.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Get the selected account
+    pub fn selected_account(&self) -> Option<&Account> {
+        self.accounts.get(self.selected)
+    }
+}
+
+/// Render the account switcher popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &AccountsView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(50, 40, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Switch gcloud account (Enter to select, ESC to close)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let message = match &view.error {
+        Some(error) => Some((error.as_str(), Color::Red)),
+        None if view.accounts.is_empty() => Some((
+            "No gcloud account found, run gcloud auth login in a shell",
+            Color::Gray,
+        )),
+        None => None,
+    };
+    if let Some((message, color)) = message {
+        let paragraph = Paragraph::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(color),
+        )))
+        .block(block)
+        .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, popup_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = view
+        .accounts
+        .iter()
+        .map(|account| {
+            if account.active {
+                ListItem::new(Line::from(vec![
+                    Span::raw(account.email.as_str()),
+                    Span::styled(" (active)", Style::default().fg(Color::Green)),
+                ]))
+            } else {
+                ListItem::new(account.email.as_str())
+            }
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(super::icons::highlight_symbol(emoji))
+        .style(Style::default().fg(Color::White));
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(view.selected));
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+}
@@ -0,0 +1,201 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::icons::Icon;
+
+/// State of one instance of a bulk action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkItemState {
+    /// Waiting for a free slot
+    Queued,
+    InProgress,
+    Done,
+    /// Failed, or not started, with the reason
+    Failed(String),
+    /// Cancelled before it started
+    Cancelled,
+}
+
+impl BulkItemState {
+    /// Check if the action is over on the instance, whatever the result
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, Self::Queued | Self::InProgress)
+    }
+
+    /// Get the icon, label and color shown for the state
+    fn display(&self) -> (Icon, String, Color) {
+        match self {
+            Self::Queued => (Icon::Queued, "queued".to_string(), Color::Gray),
+            Self::InProgress => (Icon::InProgress, "in progress".to_string(), Color::Yellow),
+            Self::Done => (Icon::Success, "done".to_string(), Color::Green),
+            Self::Failed(reason) => (Icon::Error, format!("failed: {}", reason), Color::Red),
+            Self::Cancelled => (Icon::Stop, "cancelled".to_string(), Color::Gray),
+        }
+    }
+}
+
+/// Instance of a bulk action, as listed in the progress popup
+#[derive(Debug, Clone)]
+struct BulkItem {
+    instance_id: String,
+    instance_name: String,
+    state: BulkItemState,
+}
+
+/// State of the bulk action progress popup
+pub struct BulkProgressView {
+    /// Description of the action, e.g. "stop"
+    description: String,
+    /// Instances of the action, in the order they are started
+    items: Vec<BulkItem>,
+    /// Whether the popup is shown, the action keeps running when it is hidden
+    open: bool,
+    /// First line shown
+    scroll: u16,
+}
+
+impl BulkProgressView {
+    /// Create the progress of an action on instances given by ID and name, all queued
+    pub fn new(description: String, instances: Vec<(String, String)>) -> Self {
+        let items = instances
+            .into_iter()
+            .map(|(instance_id, instance_name)| BulkItem {
+                instance_id,
+                instance_name,
+                state: BulkItemState::Queued,
+            })
+            .collect();
+
+        Self {
+            description,
+            items,
+            open: true,
+            scroll: 0,
+        }
+    }
+
+    /// Update the state of an instance of the action
+    pub fn set_state(&mut self, instance_id: &str, state: BulkItemState) {
+        if let Some(item) = self
+            .items
+            .iter_mut()
+            .find(|item| item.instance_id == instance_id)
+        {
+            item.state = state;
+        }
+    }
+
+    /// Get the state of an instance of the action
+    #[cfg(test)]
+    pub fn state(&self, instance_id: &str) -> Option<&BulkItemState> {
+        self.items
+            .iter()
+            .find(|item| item.instance_id == instance_id)
+            .map(|item| &item.state)
+    }
+
+    /// Count the instances the action is over on
+    pub fn finished_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.state.is_finished())
+            .count()
+    }
+
+    /// Show or hide the popup
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    /// Check if the popup is shown
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Scroll up by some lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by some lines, stopping at the last instance
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.items.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the bulk action progress popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &BulkProgressView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Bulk {} ({}/{} finished)",
+            view.description,
+            view.finished_count(),
+            view.items.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Instances
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    let lines: Vec<Line> = view
+        .items
+        .iter()
+        .map(|item| {
+            let (icon, label, color) = item.state.display();
+            Line::from(vec![
+                Span::raw(icon.prefix(emoji)),
+                Span::styled(
+                    item.instance_name.as_str(),
+                    Style::default().fg(Color::White),
+                ),
+                Span::raw("  "),
+                Span::styled(label, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines).scroll((view.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Render status line
+    let mut status = vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll"),
+    ];
+    if view
+        .items
+        .iter()
+        .any(|item| item.state == BulkItemState::Queued)
+    {
+        status.extend([
+            Span::raw(", "),
+            Span::styled("c", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to cancel the queued instances"),
+        ]);
+    }
+    frame.render_widget(Paragraph::new(Line::from(status)), chunks[1]);
+}
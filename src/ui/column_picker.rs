@@ -0,0 +1,102 @@
+use ratatui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::columns::{Column, ALL_COLUMNS};
+
+/// State of the popup toggling the columns of the instance list
+pub struct ColumnPickerView {
+    /// Enabled columns
+    enabled: Vec<Column>,
+    /// Selected entry, in `ALL_COLUMNS`
+    selected: usize,
+}
+
+impl ColumnPickerView {
+    /// Create a new picker over the enabled columns
+    pub fn new(enabled: &[Column]) -> Self {
+        Self {
+            enabled: enabled.to_vec(),
+            selected: 0,
+        }
+    }
+
+    /// Select the previous column
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next column
+    pub fn next(&mut self) {
+        if self.selected + 1 < ALL_COLUMNS.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Enable or disable the selected column, keeping at least one enabled
+    pub fn toggle_selected(&mut self) {
+        let column = ALL_COLUMNS[self.selected];
+        if let Some(index) = self.enabled.iter().position(|c| *c == column) {
+            if self.enabled.len() > 1 {
+                self.enabled.remove(index);
+            }
+        } else {
+            self.enabled.push(column);
+        }
+    }
+
+    /// Get the enabled columns, in display order
+    pub fn enabled(&self) -> Vec<Column> {
+        ALL_COLUMNS
+            .into_iter()
+            .filter(|column| self.enabled.contains(column))
+            .collect()
+    }
+}
+
+/// Render the column picker popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &ColumnPickerView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(40, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Columns (Space to toggle, ESC to close)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let items: Vec<ListItem> = ALL_COLUMNS
+        .iter()
+        .map(|column| {
+            let mark = if view.enabled.contains(column) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let note = match column {
+                Column::Project => " (with several projects)",
+                _ => "",
+            };
+            ListItem::new(format!("{} {}{}", mark, column.title(), note))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(super::icons::highlight_symbol(emoji))
+        .style(Style::default().fg(Color::White));
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(view.selected));
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+}
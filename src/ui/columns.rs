@@ -0,0 +1,277 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::cloud::Instance;
+
+/// Column of the instance list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Project,
+    Name,
+    Status,
+    MachineType,
+    Zone,
+    Network,
+    InternalIp,
+    ExternalIp,
+    Disk,
+}
+
+/// Every column, in display order, all enabled by default
+pub const ALL_COLUMNS: [Column; 9] = [
+    Column::Project,
+    Column::Name,
+    Column::Status,
+    Column::MachineType,
+    Column::Zone,
+    Column::Network,
+    Column::InternalIp,
+    Column::ExternalIp,
+    Column::Disk,
+];
+
+impl Column {
+    /// Get the enabled columns to display, in display order
+    ///
+    /// PROJECT is only shown when several projects are listed, and DISK can
+    /// also be hidden with `show_disk_column`.
+    pub fn visible(enabled: &[Column], show_disk: bool, show_project: bool) -> Vec<Column> {
+        ALL_COLUMNS
+            .into_iter()
+            .filter(|column| enabled.contains(column))
+            .filter(|column| match column {
+                Column::Project => show_project,
+                Column::Disk => show_disk,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Get the name of the column in the configuration
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            Column::Project => "project",
+            Column::Name => "name",
+            Column::Status => "status",
+            Column::MachineType => "machine_type",
+            Column::Zone => "zone",
+            Column::Network => "network",
+            Column::InternalIp => "internal_ip",
+            Column::ExternalIp => "external_ip",
+            Column::Disk => "disk",
+        }
+    }
+
+    /// Get the column header
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Project => "PROJECT",
+            Column::Name => "NAME",
+            Column::Status => "STATUS",
+            Column::MachineType => "MACHINE TYPE",
+            Column::Zone => "ZONE",
+            Column::Network => "NETWORK",
+            Column::InternalIp => "INTERNAL IP",
+            Column::ExternalIp => "EXTERNAL IP",
+            Column::Disk => "DISK",
+        }
+    }
+
+    /// Get the relative width of the column
+    pub fn weight(&self) -> usize {
+        match self {
+            Column::Project => 16,
+            Column::Name => 18,
+            Column::Status => 10,
+            Column::MachineType => 18,
+            Column::Zone => 15,
+            Column::Network => 12,
+            Column::InternalIp => 14,
+            Column::ExternalIp => 13,
+            Column::Disk => 8,
+        }
+    }
+
+    /// Get the plain text value of the column for an instance
+    pub fn value(&self, instance: &Instance) -> String {
+        match self {
+            Column::Project => instance.project.clone(),
+            Column::Name => instance.name.clone(),
+            Column::Status => instance.status.clone(),
+            Column::MachineType => instance.machine_type.clone(),
+            Column::Zone => instance.zone.clone(),
+            Column::Network => instance.network.as_deref().unwrap_or("-").to_string(),
+            Column::InternalIp => instance.internal_ip.as_deref().unwrap_or("-").to_string(),
+            Column::ExternalIp => instance.external_ip.as_deref().unwrap_or("-").to_string(),
+            Column::Disk => instance
+                .total_disk_size_gb()
+                .map_or_else(|| "-".to_string(), |size| format!("{} GB", size)),
+        }
+    }
+}
+
+/// Get the suffix of a zone, e.g. "b" for "europe-west1-b"
+pub fn zone_suffix(zone: &str) -> &str {
+    zone.rsplit_once('-').map_or(zone, |(_, suffix)| suffix)
+}
+
+/// Check if all instances are in the same region, so zones can be abbreviated
+pub fn single_region(instances: &[Instance]) -> bool {
+    let mut regions = instances
+        .iter()
+        .map(|instance| instance.zone.rsplit_once('-').map(|(region, _)| region));
+    match regions.next() {
+        Some(Some(first)) => regions.all(|region| region == Some(first)),
+        _ => false,
+    }
+}
+
+/// Split the available width between columns proportionally to their weight
+pub fn widths(columns: &[Column], available_width: usize) -> Vec<usize> {
+    let total_weight: usize = columns.iter().map(Column::weight).sum();
+    columns
+        .iter()
+        .map(|column| available_width * column.weight() / total_weight.max(1))
+        .collect()
+}
+
+/// Write instances as a plain text table, each column as wide as its content
+pub fn write_table<W: Write>(
+    mut writer: W,
+    instances: &[Instance],
+    columns: &[Column],
+) -> std::io::Result<()> {
+    let rows: Vec<Vec<String>> = instances
+        .iter()
+        .map(|instance| {
+            columns
+                .iter()
+                .map(|column| column.value(instance))
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].width())
+                .chain(std::iter::once(column.title().len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let headers: Vec<String> = columns.iter().map(|c| c.title().to_string()).collect();
+    for row in std::iter::once(&headers).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| pad(value, *width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(writer, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+/// Write instances as a GitHub-flavored Markdown table
+pub fn write_markdown_table<W: Write>(
+    mut writer: W,
+    instances: &[Instance],
+    columns: &[Column],
+) -> std::io::Result<()> {
+    let headers: Vec<&str> = columns.iter().map(Column::title).collect();
+    writeln!(writer, "| {} |", headers.join(" | "))?;
+    writeln!(writer, "|{}", " --- |".repeat(columns.len()))?;
+
+    for instance in instances {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| markdown_cell(&column.value(instance)))
+            .collect();
+        writeln!(writer, "| {} |", cells.join(" | "))?;
+    }
+
+    Ok(())
+}
+
+/// Escape a Markdown table cell, pipes would otherwise split it
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Pad a value with spaces to a display width
+///
+/// Format padding counts chars, while wide glyphs such as emoji take two
+/// terminal columns, which would shift the following columns.
+pub fn pad(value: &str, width: usize) -> String {
+    let padding = width.saturating_sub(value.width());
+    format!("{}{}", value, " ".repeat(padding))
+}
+
+/// Truncate a value to fit in a display width, marking the cut with an ellipsis
+pub fn truncate(value: &str, width: usize) -> String {
+    if value.width() <= width {
+        return value.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    // Keep one column for the ellipsis
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in value.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width - 1 {
+            break;
+        }
+        truncated.push(c);
+        used += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Status cells as shown with emoji, of different display widths before padding
+    const STATUS_CELLS: [&str; 5] = [
+        "🟢 RUNNING",
+        "🔴 TERMINATED",
+        "❓ UNKNOWN",
+        "⏸ SUSPENDED",
+        "[UP] RUNNING",
+    ];
+
+    #[test]
+    fn next_column_starts_at_the_same_position_for_every_status() {
+        let width = 14;
+        let starts: Vec<usize> = STATUS_CELLS
+            .iter()
+            .map(|cell| {
+                let row = format!("{}│e2-medium", pad(&truncate(cell, width), width));
+                row.split('│').next().unwrap().width()
+            })
+            .collect();
+
+        assert_eq!(starts, vec![width; STATUS_CELLS.len()]);
+    }
+
+    #[test]
+    fn truncate_counts_double_width_characters() {
+        assert_eq!(truncate("🟢 RUNNING", 4), "🟢 …");
+        assert_eq!(truncate("🟢 RUNNING", 3), "🟢…");
+        assert_eq!(truncate("🟢 RUNNING", 2), "…");
+        assert_eq!(truncate("🟢 RUNNING", 0), "");
+        assert_eq!(truncate("RUNNING", 7), "RUNNING");
+    }
+}
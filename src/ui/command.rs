@@ -0,0 +1,146 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::cloud::CommandOutput;
+
+/// State of the popup running a gcloud command typed by the user
+#[derive(Default)]
+pub struct CommandView {
+    /// Command typed by the user, without the leading "gcloud"
+    input: String,
+    /// Output lines of the last command, stdout then stderr
+    lines: Vec<(String, bool)>,
+    /// Command line and success of the last command, if one was run
+    last_command: Option<(String, bool)>,
+    /// Vertical scroll offset of the output
+    scroll: u16,
+}
+
+impl CommandView {
+    /// Get the command typed by the user
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Append a character to the input
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+
+    /// Remove the last character of the input
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+
+    /// Show the output of a command, stderr lines being marked as such
+    pub fn set_output(&mut self, output: CommandOutput) {
+        self.lines = output
+            .stdout
+            .lines()
+            .map(|line| (line.to_string(), false))
+            .chain(output.stderr.lines().map(|line| (line.to_string(), true)))
+            .collect();
+        self.last_command = Some((output.command_line, output.success));
+        self.scroll = 0;
+    }
+
+    /// Show an error preventing the command from running
+    pub fn set_error(&mut self, error: String) {
+        self.lines = vec![(error, true)];
+        self.last_command = None;
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.lines.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the gcloud command popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &CommandView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Run gcloud command")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Input
+            Constraint::Length(1), // Last command and its result
+            Constraint::Min(1),    // Output
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    // Render the input with a cursor
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("gcloud ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{}_", view.input),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    frame.render_widget(input, chunks[0]);
+
+    // Render the last command line and whether it succeeded
+    if let Some((command_line, success)) = &view.last_command {
+        let (result, color) = if *success {
+            ("ok", Color::Green)
+        } else {
+            ("failed", Color::Red)
+        };
+        let last_command = Paragraph::new(Line::from(vec![
+            Span::styled(format!("[{}] ", result), Style::default().fg(color)),
+            Span::styled(command_line.as_str(), Style::default().fg(Color::DarkGray)),
+        ]));
+        frame.render_widget(last_command, chunks[1]);
+    }
+
+    // Render the output, stderr in red
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .map(|(line, is_stderr)| {
+            let color = if *is_stderr { Color::Red } else { Color::White };
+            Line::from(Span::styled(line.as_str(), Style::default().fg(color)))
+        })
+        .collect();
+    let output = Paragraph::new(lines).scroll((view.scroll, 0));
+    frame.render_widget(output, chunks[2]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to run, "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll"),
+    ]));
+    frame.render_widget(status_line, chunks[3]);
+}
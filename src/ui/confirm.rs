@@ -0,0 +1,129 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Render a yes/no confirmation popup, where "no" is the default answer
+pub fn render<B: Backend>(frame: &mut Frame<B>, message: &str, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(50, 20, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Confirm")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            message,
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - Yes    "),
+            Span::styled("n/Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - No (default)"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Render the popup offering to log in again once the credentials expired
+pub fn render_reauth<B: Backend>(frame: &mut Frame<B>, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(50, 25, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Authentication expired")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .style(Style::default().bg(Color::Black));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "The Google Cloud credentials expired. Log in again?",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "The UI is suspended during the login, then refreshed.",
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("l", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - gcloud auth login    "),
+            Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - gcloud auth application-default login"),
+        ]),
+        Line::from(vec![
+            Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - Not now (default)"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Render the save/discard/cancel popup shown when quitting with unsaved preferences
+pub fn render_save<B: Backend>(frame: &mut Frame<B>, settings: &[String], area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(50, 20, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Unsaved preferences")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(Color::Black));
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Save {} before quitting?", settings.join(", ")),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("s", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - Save    "),
+            Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - Discard    "),
+            Span::styled("c/Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" - Cancel (default)"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
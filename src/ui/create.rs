@@ -0,0 +1,147 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::input::TextInput;
+use crate::cloud::CreateSpec;
+
+/// Labels of the form fields, in the order of `CreateSpec`
+const FIELD_LABELS: [&str; 6] = [
+    "Name",
+    "Zone",
+    "Machine type",
+    "Image family",
+    "Image project",
+    "Network",
+];
+
+/// State of the instance creation form popup
+pub struct CreateView {
+    /// Field inputs, in the order of the labels
+    fields: [TextInput; 6],
+    /// Field being edited
+    focused: usize,
+    /// Error explaining why the instance cannot be created
+    error: Option<String>,
+}
+
+impl CreateView {
+    /// Create a form filled with the settings of an instance, editing its name
+    pub fn new(spec: &CreateSpec) -> Self {
+        Self {
+            fields: [
+                TextInput::new(&spec.name),
+                TextInput::new(&spec.zone),
+                TextInput::new(&spec.machine_type),
+                TextInput::new(&spec.image_family),
+                TextInput::new(&spec.image_project),
+                TextInput::new(&spec.network),
+            ],
+            focused: 0,
+            error: None,
+        }
+    }
+
+    /// Edit the previous field, wrapping to the last one
+    pub fn previous_field(&mut self) {
+        self.focused = (self.focused + self.fields.len() - 1) % self.fields.len();
+    }
+
+    /// Edit the next field, wrapping to the first one
+    pub fn next_field(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+    }
+
+    /// Edit the focused field, returning whether the key was used
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let handled = self.fields[self.focused].handle_key(key);
+        if handled {
+            self.error = None;
+        }
+        handled
+    }
+
+    /// Get the settings typed in the form
+    pub fn spec(&self) -> CreateSpec {
+        let [name, zone, machine_type, image_family, image_project, network] = self
+            .fields
+            .each_ref()
+            .map(|field| field.value().trim().to_string());
+        CreateSpec {
+            name,
+            zone,
+            machine_type,
+            image_family,
+            image_project,
+            network,
+        }
+    }
+
+    /// Explain why the instance cannot be created
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+}
+
+/// Render the instance creation form popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &CreateView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Create instance")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Color::Yellow);
+
+    let mut text = vec![Line::from("")];
+    for (index, (label, field)) in FIELD_LABELS.iter().zip(&view.fields).enumerate() {
+        let focused = index == view.focused;
+        let mut spans = vec![Span::styled(
+            format!("{} {:<14}", if focused { ">" } else { " " }, label),
+            if focused { bold.fg(Color::Cyan) } else { bold },
+        )];
+        if focused {
+            spans.extend(field.spans(value_style));
+        } else {
+            spans.push(Span::raw(field.value().to_string()));
+        }
+        text.push(Line::from(spans));
+    }
+    text.push(Line::from(""));
+
+    if let Some(error) = &view.error {
+        text.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    } else {
+        text.push(Line::from(vec![
+            Span::styled("Tab/↑/↓", bold),
+            Span::raw(" - Next/previous field    "),
+            Span::styled("Enter", bold),
+            Span::raw(" - Create    "),
+            Span::styled("Ctrl+y", bold),
+            Span::raw(" - Copy gcloud command    "),
+            Span::styled("Esc", bold),
+            Span::raw(" - Cancel"),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
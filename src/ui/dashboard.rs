@@ -3,23 +3,82 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Wrap},
     Frame,
 };
+use serde::{Deserialize, Serialize};
 
+use unicode_width::UnicodeWidthStr;
+
+use super::columns::{self, Column};
+use super::icons::{self, Icon};
+use super::search;
+use super::status::{StatusBucket, StatusStyle};
+use super::styles::Theme;
 use super::UiState;
+use crate::cloud::Instance;
+
+/// Frames of the spinner shown for instances in a transitional state
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner frames for terminals without Unicode glyphs
+const ASCII_SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// Maximum width of the name in the dense layout, longer names are truncated
+const DENSE_NAME_WIDTH: usize = 30;
+
+/// Width of the status marker in the dense layout, that of the ASCII markers like [R]
+const DENSE_STATUS_WIDTH: usize = 3;
+
+/// Instance count emphasized in the overview, the other one is shown in parentheses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CountFocus {
+    /// Instances in the project (or region)
+    #[default]
+    Total,
+    /// Instances currently shown, after filtering
+    Shown,
+}
+
+impl CountFocus {
+    /// Get the other focus
+    pub fn toggled(self) -> Self {
+        match self {
+            CountFocus::Total => CountFocus::Shown,
+            CountFocus::Shown => CountFocus::Total,
+        }
+    }
+}
+
+/// Progress of the first instance listing, so an empty list is not mistaken for no instances
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LoadingState {
+    /// Instances are being listed for the first time
+    #[default]
+    Loading,
+    /// Instances were listed at least once
+    Loaded,
+    /// The first listing failed, with the error
+    Error(String),
+}
 
 /// Render the main dashboard view
 pub fn render<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
+    // The dense layout drops the overview to fit more instances
+    let overview_height = if state.dense { 0 } else { 9 };
+    let toasts_height = state.notifications.toasts().count() as u16;
+
     // Create the layout
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3), // For title and filter bar
-            Constraint::Length(8), // For overview panel
-            Constraint::Min(10),   // For instance list - use all remaining space
-            Constraint::Length(1), // For status bar
+            Constraint::Length(3),               // For title and filter bar
+            Constraint::Length(overview_height), // For overview panel
+            Constraint::Min(10),                 // For instance list - use all remaining space
+            Constraint::Length(toasts_height),   // For notifications
+            Constraint::Length(1),               // For status bar
         ])
         .split(area);
 
@@ -27,13 +86,22 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
     render_title_bar(frame, state, main_chunks[0]);
 
     // Render overview panel
-    render_overview_panel(frame, state, main_chunks[1]);
+    if !state.dense {
+        render_overview_panel(frame, state, main_chunks[1]);
+    }
 
     // Render instances list - use all remaining space
-    render_instance_list(frame, state, main_chunks[2]);
+    if state.dense && !state.instances.is_empty() {
+        render_dense_instance_list(frame, state, main_chunks[2]);
+    } else {
+        render_instance_list(frame, state, main_chunks[2]);
+    }
+
+    // Render notifications, stacked above the status bar
+    render_toasts(frame, state, main_chunks[3]);
 
     // Render status bar
-    render_status_bar(frame, state, main_chunks[3]);
+    render_status_bar(frame, state, main_chunks[4]);
 }
 
 /// Render the title and filter bar
@@ -47,29 +115,94 @@ fn render_title_bar<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rec
         .split(area);
 
     // Title
-    let title = Paragraph::new("🌩️  Google Cloud Instances (G1C)").style(
+    let title = Paragraph::new(format!(
+        "{}Google Cloud Instances (G1C)",
+        Icon::Title.prefix(state.emoji)
+    ))
+    .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(state.theme.accent)
             .add_modifier(Modifier::BOLD),
     );
     frame.render_widget(title, chunks[0]);
 
-    // Filter bar
-    let filter_text = if state.filter_mode {
-        format!("🔍 Filter: {}", state.filter)
+    // Filter bar, the text being typed followed by a cursor
+    let filter_icon = Icon::Filter.prefix(state.emoji);
+    let search_icon = Icon::Search.prefix(state.emoji);
+    let typing_style = Style::default().fg(state.theme.highlight);
+    let mut filter_spans = if state.filter_mode {
+        let mut spans = vec![Span::styled(
+            format!("{}Filter: ", filter_icon),
+            typing_style,
+        )];
+        spans.extend(state.filter.spans(typing_style));
+        spans
     } else if state.search_mode {
-        format!("🔎 Search: {}", state.search)
+        let mut spans = vec![Span::styled(
+            format!("{}Search: ", search_icon),
+            typing_style,
+        )];
+        spans.extend(state.search.spans(typing_style));
+        spans
+    } else if !state.search.is_empty() {
+        vec![Span::styled(
+            format!(
+                "{}Search: {} ({} matches, n/p to jump, Esc to clear)",
+                search_icon,
+                state.search.value(),
+                state.search_matches().len()
+            ),
+            typing_style,
+        )]
+    } else if !state.filter.is_empty() {
+        vec![Span::styled(
+            format!(
+                "{}Filter: {} (f to edit)",
+                filter_icon,
+                state.filter.value()
+            ),
+            typing_style,
+        )]
     } else {
-        "🔍 Press 'f' to filter, '/' to search".to_string()
+        vec![Span::styled(
+            format!("{}Press 'f' to filter, '/' to search", filter_icon),
+            Style::default().fg(state.theme.muted),
+        )]
     };
+    if state.only_mine {
+        let only_mine_text = if state.has_ownership_info() {
+            "Only my instances"
+        } else {
+            "Only my instances (no ownership info found)"
+        };
+        filter_spans.push(Span::styled(
+            format!("  {}{}", Icon::Account.prefix(state.emoji), only_mine_text),
+            Style::default().fg(state.theme.emphasis),
+        ));
+    }
 
-    let filter_style = if state.filter_mode || state.search_mode {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    if state.only_public {
+        filter_spans.push(Span::styled(
+            format!(
+                "  {}Only instances with an external IP",
+                Icon::Region.prefix(state.emoji)
+            ),
+            Style::default().fg(state.theme.emphasis),
+        ));
+    }
+
+    if let Some(status) = &state.status_filter {
+        filter_spans.push(Span::styled(
+            format!(
+                "  {}Only {} instances",
+                state.status_styles.get(status).icon(state.emoji),
+                status
+            ),
+            Style::default().fg(state.theme.emphasis),
+        ));
+    }
 
-    let filter_bar = Paragraph::new(filter_text).style(filter_style);
+    let filter_bar = Paragraph::new(Line::from(filter_spans));
 
     frame.render_widget(filter_bar, chunks[1]);
 }
@@ -79,70 +212,264 @@ fn render_overview_panel<B: Backend>(frame: &mut Frame<B>, state: &UiState, area
     // Create a block for the overview panel
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("📈 Overview")
+        .title(format!("{}Overview", Icon::Overview.prefix(state.emoji)))
         .title_style(
             Style::default()
-                .fg(Color::White)
+                .fg(state.theme.text)
                 .add_modifier(Modifier::BOLD),
         );
 
     // Create the content
-    let instance_count = state.instances.len();
+    let total_count = state.all_instances.len();
+    let shown_count = state.instances.len();
+    let (count_label, count, other_count_text) = match state.count_focus {
+        CountFocus::Total => (
+            "Total Instances: ",
+            total_count,
+            format!(" ({} shown)", shown_count),
+        ),
+        CountFocus::Shown => (
+            "Shown Instances: ",
+            shown_count,
+            format!(" ({} total)", total_count),
+        ),
+    };
 
-    // Count instances by status
+    // Count all instances by status, then by the configured bucket of the status
+    let status_counts = state.status_counts();
     let mut running_count = 0;
     let mut stopped_count = 0;
     let mut other_count = 0;
 
-    for instance in &state.instances {
-        match instance.status.as_str() {
-            "RUNNING" => running_count += 1,
-            "TERMINATED" => stopped_count += 1,
-            _ => other_count += 1,
+    for (status, count) in &status_counts {
+        match state.status_styles.get(status).bucket {
+            StatusBucket::Running => running_count += count,
+            StatusBucket::Stopped => stopped_count += count,
+            StatusBucket::Other => other_count += count,
+        }
+    }
+
+    // Estimate the cost of running instances, omitted when none is priced
+    let running_costs: Vec<Option<f64>> = state
+        .all_instances
+        .iter()
+        .filter(|instance| instance.status == "RUNNING")
+        .map(|instance| instance.estimated_hourly_usd())
+        .collect();
+    let unpriced_count = running_costs.iter().filter(|cost| cost.is_none()).count();
+    let running_cost = running_costs
+        .iter()
+        .flatten()
+        .copied()
+        .reduce(|total, cost| total + cost);
+
+    // Break the buckets down by status, in the status colors
+    let mut breakdown_spans = vec![];
+    for (status, count) in &status_counts {
+        let style = state.status_styles.get(status);
+        if !breakdown_spans.is_empty() {
+            breakdown_spans.push(Span::raw("  "));
+        }
+        breakdown_spans.push(Span::styled(
+            format!("{} {} {}", style.icon(state.emoji), status, count),
+            Style::default().fg(style.color),
+        ));
+    }
+
+    // Flag unsupported gcloud CLI versions, the active account follows the version
+    let mut cli_spans = vec![
+        Span::styled(
+            format!("{}GCloud CLI: ", Icon::Cli.prefix(state.emoji)),
+            Style::default().fg(state.theme.label),
+        ),
+        Span::raw(&state.cli_version),
+    ];
+    if let Some(warning) = &state.cli_version_warning {
+        cli_spans.push(Span::styled(
+            format!(" {}{}", Icon::Warning.prefix(state.emoji), warning),
+            Style::default()
+                .fg(state.theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    cli_spans.extend([
+        Span::raw("  "),
+        Span::styled(
+            format!("{}Account: ", Icon::Account.prefix(state.emoji)),
+            Style::default().fg(state.theme.label),
+        ),
+        Span::styled(
+            state.account.as_deref().unwrap_or("Unknown"),
+            Style::default()
+                .fg(state.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    if let Some(impersonated) = &state.impersonated_account {
+        cli_spans.extend([
+            Span::styled(" as ", Style::default().fg(state.theme.label)),
+            Span::styled(
+                impersonated.as_str(),
+                Style::default()
+                    .fg(state.theme.emphasis)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+    }
+
+    let mut count_spans = vec![
+        Span::styled(
+            format!("{}{}", Icon::Count.prefix(state.emoji), count_label),
+            Style::default().fg(state.theme.success),
+        ),
+        Span::styled(
+            count.to_string(),
+            Style::default()
+                .fg(state.theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(other_count_text, Style::default().fg(state.theme.muted)),
+    ];
+    if let Some(cost) = running_cost {
+        count_spans.extend([
+            Span::raw("  "),
+            Span::styled(
+                format!("{}Est. running cost: ", Icon::Pricing.prefix(state.emoji)),
+                Style::default().fg(state.theme.label),
+            ),
+            Span::raw(format!("${:.2}/hr", cost)),
+        ]);
+        if unpriced_count > 0 {
+            count_spans.push(Span::styled(
+                format!(" (excl. {} unpriced)", unpriced_count),
+                Style::default().fg(state.theme.muted),
+            ));
         }
     }
 
     let content = vec![
         Line::from(vec![
-            Span::styled("🔑 Project ID: ", Style::default().fg(Color::Blue)),
+            Span::styled(
+                format!("{}Project ID: ", Icon::Project.prefix(state.emoji)),
+                Style::default().fg(state.theme.label),
+            ),
             Span::raw(&state.project_id),
         ]),
         Line::from(vec![
-            Span::styled("🌎 Region: ", Style::default().fg(Color::Blue)),
+            Span::styled(
+                format!("{}Region: ", Icon::Region.prefix(state.emoji)),
+                Style::default().fg(state.theme.label),
+            ),
             Span::raw(&state.region),
+            Span::raw("  "),
+            Span::styled("Default zone: ", Style::default().fg(state.theme.label)),
+            Span::raw(&state.default_zone),
         ]),
-        Line::from(vec![
-            Span::styled("🖥️ GCloud CLI: ", Style::default().fg(Color::Blue)),
-            Span::raw(&state.cli_version),
-        ]),
+        Line::from(cli_spans),
         Line::from(Span::raw("")),
+        Line::from(count_spans),
         Line::from(vec![
-            Span::styled("📊 Total Instances: ", Style::default().fg(Color::Green)),
             Span::styled(
-                instance_count.to_string(),
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+                format!("{}Running: ", Icon::Running.prefix(state.emoji)),
+                Style::default().fg(state.theme.success),
+            ),
+            Span::styled(
+                running_count.to_string(),
+                Style::default().fg(state.theme.success),
             ),
-        ]),
-        Line::from(vec![
-            Span::styled("🟢 Running: ", Style::default().fg(Color::Green)),
-            Span::styled(running_count.to_string(), Style::default().fg(Color::Green)),
             Span::raw("  "),
-            Span::styled("🔴 Stopped: ", Style::default().fg(Color::Red)),
-            Span::styled(stopped_count.to_string(), Style::default().fg(Color::Red)),
+            Span::styled(
+                format!("{}Stopped: ", Icon::Stopped.prefix(state.emoji)),
+                Style::default().fg(state.theme.error),
+            ),
+            Span::styled(
+                stopped_count.to_string(),
+                Style::default().fg(state.theme.error),
+            ),
             Span::raw("  "),
-            Span::styled("❓ Other: ", Style::default().fg(Color::Yellow)),
-            Span::styled(other_count.to_string(), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{}Other: ", Icon::Other.prefix(state.emoji)),
+                Style::default().fg(state.theme.highlight),
+            ),
+            Span::styled(
+                other_count.to_string(),
+                Style::default().fg(state.theme.highlight),
+            ),
         ]),
-        Line::from(Span::raw("")),
+        Line::from(breakdown_spans),
     ];
 
-    let paragraph = Paragraph::new(content)
-        .block(block)
-        .alignment(ratatui::layout::Alignment::Left);
+    let paragraph = Paragraph::new(content).alignment(ratatui::layout::Alignment::Left);
 
-    frame.render_widget(paragraph, area);
+    // Render the block around the whole panel
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Without history, the text uses the whole panel
+    if state.total_history.is_empty() {
+        frame.render_widget(paragraph, inner_area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(60), // For the overview text
+            Constraint::Percentage(40), // For the history sparklines
+        ])
+        .split(inner_area);
+
+    frame.render_widget(paragraph, chunks[0]);
+    render_history_sparklines(frame, state, chunks[1]);
+}
+
+/// Render the instance count history sparklines
+fn render_history_sparklines<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Ratio(1, 2), // For total instances
+            Constraint::Ratio(1, 2), // For running instances
+        ])
+        .split(area);
+
+    // Share the same scale so both sparklines can be compared
+    let max = state
+        .total_history
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let total_sparkline = Sparkline::default()
+        .block(Block::default().title(Span::styled(
+            format!("{}Total history", Icon::Count.prefix(state.emoji)),
+            Style::default().fg(state.theme.success),
+        )))
+        .data(&state.total_history)
+        .max(max)
+        .style(Style::default().fg(state.theme.success));
+    frame.render_widget(total_sparkline, chunks[0]);
+
+    let running_sparkline = Sparkline::default()
+        .block(Block::default().title(Span::styled(
+            format!("{}Running history", Icon::Running.prefix(state.emoji)),
+            Style::default().fg(state.theme.success),
+        )))
+        .data(&state.running_history)
+        .max(max)
+        .style(Style::default().fg(state.theme.accent));
+    frame.render_widget(running_sparkline, chunks[1]);
+}
+
+/// Get the arrow showing the sort direction
+fn sort_arrow(ascending: bool) -> &'static str {
+    if ascending {
+        "↑"
+    } else {
+        "↓"
+    }
 }
 
 /// Render the instance list
@@ -159,25 +486,50 @@ fn render_instance_list<B: Backend>(frame: &mut Frame<B>, state: &UiState, area:
     // Create a block for the list - make sure to use all available space
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("💻 Instances List")
+        .title(format!(
+            "{}Instances List (by {} {}){}",
+            Icon::Instances.prefix(state.emoji),
+            state.sort_key.label(),
+            sort_arrow(state.sort_ascending),
+            marked_count(state)
+        ))
         .title_style(
             Style::default()
-                .fg(Color::White)
+                .fg(state.theme.text)
                 .add_modifier(Modifier::BOLD),
         );
 
     // If there are no instances, show a message
     if state.instances.is_empty() {
-        let no_instances_text = vec![
-            Line::from(Span::styled(
-                "No instances found",
-                Style::default().fg(Color::Gray),
-            )),
-            Line::from(Span::styled(
-                "Press 'r' to refresh",
-                Style::default().fg(Color::DarkGray),
-            )),
-        ];
+        let no_instances_text = match &state.loading {
+            LoadingState::Loading => vec![Line::from(Span::styled(
+                format!(
+                    "{} Loading instances…",
+                    spinner_frame(state.animation_frame, state.emoji)
+                ),
+                Style::default().fg(state.theme.highlight),
+            ))],
+            LoadingState::Error(error) => vec![
+                Line::from(Span::styled(
+                    format!("Failed to list instances: {}", error),
+                    Style::default().fg(state.theme.error),
+                )),
+                Line::from(Span::styled(
+                    "Press 'r' to retry",
+                    Style::default().fg(state.theme.muted),
+                )),
+            ],
+            LoadingState::Loaded => vec![
+                Line::from(Span::styled(
+                    "No instances found",
+                    Style::default().fg(state.theme.secondary),
+                )),
+                Line::from(Span::styled(
+                    "Press 'r' to refresh",
+                    Style::default().fg(state.theme.muted),
+                )),
+            ],
+        };
 
         let paragraph = Paragraph::new(no_instances_text)
             .block(block)
@@ -189,171 +541,121 @@ fn render_instance_list<B: Backend>(frame: &mut Frame<B>, state: &UiState, area:
     }
 
     // Calculate the available width for the table
-    let available_width = area.width as usize - 20; // Subtract borders, margins, and column separators
+    // Subtract borders, margins, the highlight symbol and column separators
+    let columns = &state.columns;
+    let separators_width = 2 * columns.len().saturating_sub(1);
+    let marks_width = if state.has_marked() { 4 } else { 0 };
+    let available_width = (area.width as usize).saturating_sub(8 + separators_width + marks_width);
 
     // Define column widths proportionally to available space
-    let name_width = (available_width * 18) / 100;
-    let status_width = (available_width * 10) / 100;
-    let machine_type_width = (available_width * 18) / 100;
-    let zone_width = (available_width * 15) / 100;
-    let network_width = (available_width * 12) / 100;
-    let internal_ip_width = (available_width * 14) / 100;
-    let external_ip_width = (available_width * 13) / 100;
+    let widths = columns::widths(columns, available_width);
 
     // Create header as a separate widget
-    let header = Line::from(vec![
-        Span::styled(
-            format!("{:<width$}", "NAME", width = name_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "STATUS", width = status_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "MACHINE TYPE", width = machine_type_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "ZONE", width = zone_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "NETWORK", width = network_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "INTERNAL IP", width = internal_ip_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-        Span::raw("│ "),
-        Span::styled(
-            format!("{:<width$}", "EXTERNAL IP", width = external_ip_width),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Blue),
-        ),
-    ]);
-
-    // Create list items from instances without including header
-    let mut items = vec![];
-
-    for (_i, instance) in state.instances.iter().enumerate() {
-        // Determine status color and display text
-        let (status_color, status_display) = match instance.status.as_str() {
-            "RUNNING" => (Color::Green, "🟢 RUNNING"),
-            "TERMINATED" => (Color::Red, "🔴 TERMINATED"),
-            "STOPPING" => (Color::Yellow, "🟠 STOPPING"),
-            "PROVISIONING" => (Color::Magenta, "🟡 PROVISIONING"),
-            "STAGING" => (Color::Cyan, "🔄 STAGING"),
-            "SUSPENDED" => (Color::Gray, "💤 SUSPENDED"),
-            "REPAIRING" => (Color::Yellow, "🟡 REPAIRING"),
-            "PENDING" => (Color::Yellow, "🟡 PENDING"),
-            _ => (Color::Gray, "❓ UNKNOWN"),
-        };
-
-        // Get network name (if available)
-        let network = instance.network.as_deref().unwrap_or("-");
-
-        // Format strings to limit length and avoid overflow
-        let instance_name = if instance.name.len() > name_width {
-            format!("{}…", &instance.name[0..name_width - 1])
+    let header_style = Style::default()
+        .add_modifier(Modifier::BOLD)
+        .fg(state.theme.label);
+    let sorted_column = state.sort_key.column();
+    let mut header_spans = vec![Span::raw(" ".repeat(marks_width))];
+    for (i, (column, width)) in columns.iter().zip(&widths).enumerate() {
+        if i > 0 {
+            header_spans.push(Span::raw("│ "));
+        }
+        let title = if sorted_column == Some(*column) {
+            format!("{} {}", column.title(), sort_arrow(state.sort_ascending))
         } else {
-            instance.name.clone()
+            column.title().to_string()
         };
+        header_spans.push(Span::styled(columns::pad(&title, *width), header_style));
+    }
+    let header = Line::from(header_spans);
 
-        let instance_status = status_display.to_string();
+    // Abbreviate zones when the region is obvious, except on the selected row
+    let short_zones = state.short_zone_names && columns::single_region(&state.instances);
 
-        let machine_type = if instance.machine_type.len() > machine_type_width {
-            format!("{}…", &instance.machine_type[0..machine_type_width - 1])
-        } else {
-            instance.machine_type.clone()
-        };
+    // Matches of the search text stand out in every cell
+    let match_style = state.theme.match_style();
 
-        let zone = if instance.zone.len() > zone_width {
-            format!("{}…", &instance.zone[0..zone_width - 1])
-        } else {
-            instance.zone.clone()
-        };
+    // Create list items from instances without including header
+    let mut items = vec![];
 
-        let network_str = if network.len() > network_width {
-            format!("{}…", &network[0..network_width - 1])
-        } else {
-            network.to_string()
-        };
+    for (index, instance) in state.instances.iter().enumerate() {
+        // Create list item with dynamic width columns, checkboxes first while marking
+        let mut row_spans = vec![];
+        if state.has_marked() {
+            row_spans.push(Span::styled(
+                icons::mark_symbol(state.marked.contains(&instance.id)),
+                Style::default().fg(state.theme.highlight),
+            ));
+        }
+        for (i, (column, width)) in columns.iter().zip(&widths).enumerate() {
+            if i > 0 {
+                row_spans.push(Span::raw("│ "));
+            }
 
-        let internal_ip = instance.internal_ip.as_deref().unwrap_or("-").to_string();
-        let external_ip = instance.external_ip.as_deref().unwrap_or("-").to_string();
+            let (text, style) = match column {
+                Column::Status if state.is_vanished(&instance.id) => (
+                    columns::pad(&columns::truncate("GONE", *width), *width),
+                    Style::default().fg(state.theme.muted),
+                ),
+                Column::Status => {
+                    let (status_color, mut status_text) = status_display(
+                        state.status_styles.get(&instance.status),
+                        &instance.status,
+                        state.actions_in_flight.contains(&instance.id),
+                        state.animation_frame,
+                        state.emoji,
+                    );
+                    if instance.preemptible {
+                        status_text.push_str(icons::preemptible_marker(state.emoji));
+                    }
+                    (
+                        columns::pad(&columns::truncate(&status_text, *width), *width),
+                        Style::default().fg(status_color),
+                    )
+                }
+                Column::Zone if short_zones && index != state.selected_index => (
+                    columns::pad(
+                        &columns::truncate(columns::zone_suffix(&instance.zone), *width),
+                        *width,
+                    ),
+                    Style::default(),
+                ),
+                _ => (
+                    columns::pad(&columns::truncate(&column.value(instance), *width), *width),
+                    Style::default(),
+                ),
+            };
+            row_spans.extend(search::highlight(
+                text,
+                state.search.value(),
+                style,
+                match_style,
+            ));
+        }
 
-        // Create list item with dynamic width columns
-        let item = ListItem::new(Line::from(vec![
-            Span::raw(format!("{:<width$}", instance_name, width = name_width)),
-            Span::raw("│ "),
-            Span::styled(
-                format!("{:<width$}", instance_status, width = status_width),
-                Style::default().fg(status_color),
-            ),
-            Span::raw("│ "),
-            Span::raw(format!(
-                "{:<width$}",
-                machine_type,
-                width = machine_type_width
-            )),
-            Span::raw("│ "),
-            Span::raw(format!("{:<width$}", zone, width = zone_width)),
-            Span::raw("│ "),
-            Span::raw(format!("{:<width$}", network_str, width = network_width)),
-            Span::raw("│ "),
-            Span::raw(format!(
-                "{:<width$}",
-                internal_ip,
-                width = internal_ip_width
-            )),
-            Span::raw("│ "),
-            Span::raw(format!(
-                "{:<width$}",
-                external_ip,
-                width = external_ip_width
-            )),
-        ]));
-
-        items.push(item);
+        // Instances missing from the last listing are about to be removed
+        let item = ListItem::new(Line::from(row_spans));
+        items.push(if state.is_vanished(&instance.id) {
+            item.style(vanished_style(&state.theme))
+        } else {
+            item
+        });
     }
 
     // Render the header first
     let header_paragraph = Paragraph::new(header)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(state.theme.text))
         .alignment(ratatui::layout::Alignment::Left);
 
     // Create a List widget for just the instance items - ensure it takes all available space
     let list = List::new(items)
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("➤ ")
-        .style(Style::default().fg(Color::White)); // Add default style for all list items
+        .highlight_style(state.theme.selection_style())
+        .highlight_symbol(icons::highlight_symbol(state.emoji))
+        .style(Style::default().fg(state.theme.text)); // Add default style for all list items
 
-    // Create a ListState with the current selection
-    let mut list_state = ratatui::widgets::ListState::default();
+    // Create a ListState with the current selection, scrolled as at the last draw
+    let mut list_state =
+        ratatui::widgets::ListState::default().with_offset(state.list_offset.get());
     list_state.select(Some(state.selected_index));
 
     // Render the block around the whole area
@@ -364,33 +666,377 @@ fn render_instance_list<B: Backend>(frame: &mut Frame<B>, state: &UiState, area:
     let padded_header_area = Rect {
         x: header_area.x + 1,
         y: header_area.y + 1,
-        width: header_area.width - 2,
-        height: header_area.height - 1,
+        width: header_area.width.saturating_sub(2),
+        height: header_area.height.saturating_sub(1),
     };
     frame.render_widget(header_paragraph, padded_header_area);
 
     // Render the list with the current selection in the list area
     frame.render_stateful_widget(list, instance_chunks[1], &mut list_state);
+    // The last line of the list area is the bottom border
+    let rows_area = Rect {
+        height: instance_chunks[1].height.saturating_sub(1),
+        ..instance_chunks[1]
+    };
+    remember_list_view(state, &list_state, rows_area);
+}
+
+/// Render the instances as compact lines: name, status icon, machine type, zone and IP
+///
+/// Columns are aligned on their longest value instead of sharing the width,
+/// and there is no header, so as many instances as possible fit on screen.
+fn render_dense_instance_list<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(format!(
+        "{}Instances ({}){}",
+        Icon::Instances.prefix(state.emoji),
+        state.instances.len(),
+        marked_count(state)
+    ));
+
+    let max_width = |value: fn(&Instance) -> &str| {
+        state
+            .instances
+            .iter()
+            .map(|instance| value(instance).width())
+            .max()
+            .unwrap_or(0)
+    };
+    let name_width = max_width(|instance| &instance.name).min(DENSE_NAME_WIDTH);
+    let machine_type_width = max_width(|instance| &instance.machine_type);
+    let zone_width = max_width(|instance| &instance.zone);
+    let match_style = state.theme.match_style();
+
+    let items: Vec<ListItem> = state
+        .instances
+        .iter()
+        .map(|instance| {
+            let status_style = state.status_styles.get(&instance.status);
+            let ip = instance
+                .external_ip
+                .as_deref()
+                .or(instance.internal_ip.as_deref())
+                .unwrap_or("-");
+
+            let mut spans = vec![];
+            if state.has_marked() {
+                spans.push(Span::styled(
+                    icons::mark_symbol(state.marked.contains(&instance.id)),
+                    Style::default().fg(state.theme.highlight),
+                ));
+            }
+            let highlight = |text: String| {
+                search::highlight(text, state.search.value(), Style::default(), match_style)
+            };
+            spans.extend(highlight(columns::pad(
+                &columns::truncate(&instance.name, name_width),
+                name_width,
+            )));
+            spans.extend([
+                Span::raw("  "),
+                // Emoji are narrower than ASCII markers, pad so columns line up in both modes
+                Span::styled(
+                    columns::pad(status_style.icon(state.emoji), DENSE_STATUS_WIDTH),
+                    Style::default().fg(status_style.color),
+                ),
+                Span::raw("  "),
+            ]);
+            spans.extend(highlight(columns::pad(
+                &instance.machine_type,
+                machine_type_width,
+            )));
+            spans.push(Span::raw("  "));
+            spans.extend(highlight(columns::pad(&instance.zone, zone_width)));
+            spans.push(Span::raw("  "));
+            spans.extend(highlight(ip.to_string()));
+            let item = ListItem::new(Line::from(spans));
+            if state.is_vanished(&instance.id) {
+                item.style(vanished_style(&state.theme))
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(state.theme.selection_style())
+        .highlight_symbol(icons::highlight_symbol(state.emoji))
+        .style(Style::default().fg(state.theme.text));
+
+    let mut list_state =
+        ratatui::widgets::ListState::default().with_offset(state.list_offset.get());
+    list_state.select(Some(state.selected_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+    let rows_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    remember_list_view(state, &list_state, rows_area);
+}
+
+/// Get the number of instances marked for a bulk action, as a list title suffix
+fn marked_count(state: &UiState) -> String {
+    if state.has_marked() {
+        format!(" - {} marked", state.marked.len())
+    } else {
+        String::new()
+    }
+}
+
+/// Keep the scroll offset and rows area of the instance list for the next draw, paging and clicks
+fn remember_list_view(state: &UiState, list_state: &ratatui::widgets::ListState, rows_area: Rect) {
+    state.list_offset.set(list_state.offset());
+    state.list_height.set(rows_area.height as usize);
+    state.list_area.set(rows_area);
+}
+
+/// Get the style of instances missing from the last listing
+fn vanished_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.muted)
+        .add_modifier(Modifier::CROSSED_OUT)
+}
+
+/// Get the color and display text of a status
+fn status_display(
+    style: &StatusStyle,
+    status: &str,
+    action_in_flight: bool,
+    animation_frame: usize,
+    emoji: bool,
+) -> (Color, String) {
+    // Animate transitional states and pending actions so they stand out from steady ones
+    if action_in_flight || is_transitional(status) {
+        let spinner = spinner_frame(animation_frame, emoji);
+        return (style.color, format!("{} {}", spinner, status));
+    }
+
+    (
+        style.color,
+        format!("{} {}", style.icon(emoji), style.label),
+    )
+}
+
+/// Check if a status is a transition between steady states, shown with a spinner
+pub(super) fn is_transitional(status: &str) -> bool {
+    matches!(
+        status,
+        "PROVISIONING" | "STAGING" | "STOPPING" | "SUSPENDING" | "REPAIRING" | "PENDING"
+    )
+}
+
+/// Get the spinner frame to display for an animation frame
+fn spinner_frame(animation_frame: usize, emoji: bool) -> &'static str {
+    if emoji {
+        SPINNER_FRAMES[animation_frame % SPINNER_FRAMES.len()]
+    } else {
+        ASCII_SPINNER_FRAMES[animation_frame % ASCII_SPINNER_FRAMES.len()]
+    }
+}
+
+/// Render the recent notifications, one per line, most recent last
+fn render_toasts<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
+    let lines: Vec<Line> = state
+        .notifications
+        .toasts()
+        .map(|notification| notification.line(state.emoji))
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
 }
 
 /// Render the status bar
 fn render_status_bar<B: Backend>(frame: &mut Frame<B>, state: &UiState, area: Rect) {
-    let selected_text = if !state.instances.is_empty() {
-        let instance = &state.instances[state.selected_index];
-        format!("🔍 Selected: {} ({})", instance.name, instance.id)
-    } else {
-        "🔍 No instances selected".to_string()
+    let selected_icon = Icon::Filter.prefix(state.emoji);
+    let selected_text = match state.selected_instance() {
+        Some(instance) => format!(
+            "{}Selected: {} ({})",
+            selected_icon, instance.name, instance.id
+        ),
+        None => format!("{}No instances selected", selected_icon),
     };
 
-    let help_hint = "❓ Press '?' for help";
+    let help_hint = format!("{}Press '?' for help", Icon::Help.prefix(state.emoji));
 
-    let text = Line::from(vec![
-        Span::raw(selected_text),
-        Span::raw(" | "),
-        Span::styled(help_hint, Style::default().fg(Color::DarkGray)),
-    ]);
+    let mut spans = vec![Span::raw(selected_text), Span::raw(" | ")];
+    if !state.actions_in_flight.is_empty() {
+        spans.push(Span::styled(
+            format!(
+                "{}{} action(s) in progress",
+                Icon::InProgress.prefix(state.emoji),
+                state.actions_in_flight.len()
+            ),
+            Style::default().fg(state.theme.highlight),
+        ));
+        spans.push(Span::raw(" | "));
+    }
+    if let Some(progress) = &state.refresh_progress {
+        spans.push(Span::styled(
+            format!("{}{}", Icon::InProgress.prefix(state.emoji), progress),
+            Style::default().fg(state.theme.highlight),
+        ));
+        spans.push(Span::raw(" | "));
+    }
+    if state.copy_mode {
+        spans.push(Span::styled(
+            format!(
+                "{}Copy gcloud command: [s]tart [S]top [R]estart [d]elete [x] ssh, [i]p, or [m]arkdown table",
+                Icon::Copy.prefix(state.emoji)
+            ),
+            Style::default().fg(state.theme.accent),
+        ));
+        spans.push(Span::raw(" | "));
+    }
+    spans.push(Span::styled(
+        format!("Refresh every {}s (+/-) | ", state.refresh_interval),
+        Style::default().fg(state.theme.muted),
+    ));
+    spans.push(Span::styled(
+        help_hint,
+        Style::default().fg(state.theme.muted),
+    ));
+
+    let text = Line::from(spans);
 
-    let paragraph = Paragraph::new(text).style(Style::default().fg(Color::White));
+    let paragraph = Paragraph::new(text).style(Style::default().fg(state.theme.text));
 
     frame.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::Instance;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Render the dashboard into a buffer, returning its lines
+    fn render_lines(state: &UiState, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| render(frame, state, frame.size()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer.get(x, y).symbol.as_str())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn instances() -> Vec<Instance> {
+        vec![
+            Instance::for_test("web-1", "RUNNING", "europe-west1-b", "e2-medium"),
+            Instance::for_test("web-2", "RUNNING", "europe-west1-c", "e2-medium"),
+            Instance::for_test("batch", "TERMINATED", "europe-west1-b", "n2-standard-8"),
+        ]
+    }
+
+    #[test]
+    fn overview_counts_instances() {
+        let state = UiState::with_instances(instances());
+        let lines = render_lines(&state, 120, 40);
+
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Total Instances: 3 (3 shown)")));
+        assert!(lines.iter().any(|line| line.contains("RUNNING 2")));
+        assert!(lines.iter().any(|line| line.contains("TERMINATED 1")));
+    }
+
+    #[test]
+    fn lists_every_instance() {
+        let state = UiState::with_instances(instances());
+        let lines = render_lines(&state, 120, 40);
+
+        for name in ["web-1", "web-2", "batch"] {
+            assert!(lines.iter().any(|line| line.contains(name)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn columns_align_across_statuses() {
+        let state = UiState::with_instances(
+            ["RUNNING", "TERMINATED", "SUSPENDED", "STOPPING", "REPAIRED"]
+                .iter()
+                .enumerate()
+                .map(|(i, status)| {
+                    Instance::for_test(&format!("vm-{}", i), status, "europe-west1-b", "e2-medium")
+                })
+                .collect(),
+        );
+        let lines = render_lines(&state, 140, 40);
+
+        // Every cell is one character, wide characters being followed by a blank cell
+        let starts: Vec<usize> = lines
+            .iter()
+            .filter_map(|line| {
+                let byte = line.find("e2-medium")?;
+                Some(line[..byte].chars().count())
+            })
+            .collect();
+        assert_eq!(starts.len(), 5);
+        assert!(
+            starts.iter().all(|start| *start == starts[0]),
+            "{:?}",
+            starts
+        );
+    }
+
+    #[test]
+    fn shows_no_instances_found() {
+        let state = UiState::with_instances(Vec::new());
+        let lines = render_lines(&state, 120, 40);
+
+        assert!(lines.iter().any(|line| line.contains("No instances found")));
+        assert!(lines.iter().any(|line| line.contains("Total Instances: 0")));
+    }
+
+    #[test]
+    fn truncates_on_tiny_areas() {
+        let state = UiState::with_instances(instances());
+        for (width, height) in [(1, 1), (5, 3), (20, 10), (40, 15)] {
+            let lines = render_lines(&state, width, height);
+            assert_eq!(lines.len(), height as usize);
+        }
+    }
+
+    #[test]
+    fn truncates_long_names_to_the_column() {
+        let long_name = "a".repeat(200);
+        let state = UiState::with_instances(vec![Instance::for_test(
+            &long_name,
+            "RUNNING",
+            "europe-west1-b",
+            "e2-medium",
+        )]);
+        let lines = render_lines(&state, 80, 30);
+
+        // The name is cut with an ellipsis, leaving room for the next columns
+        let row = lines.iter().find(|line| line.contains("aaaaaaaa")).unwrap();
+        assert!(row.contains("a…"), "{}", row);
+        assert!(row.contains("e2-medium"), "{}", row);
+    }
+
+    #[test]
+    fn transitional_instances_keep_the_spinner_animated() {
+        let steady = UiState::with_instances(instances());
+        assert!(!steady.is_animating());
+
+        for status in ["STAGING", "STOPPING", "PROVISIONING"] {
+            let mut all = instances();
+            all.push(Instance::for_test(
+                "web-9",
+                status,
+                "europe-west1-b",
+                "e2-medium",
+            ));
+            let state = UiState::with_instances(all);
+            assert!(state.is_animating(), "{}", status);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::cloud::{self, Instance};
+
+/// Prefix of the filter token matching a machine family, e.g. `family:e2`
+const FAMILY_TOKEN: &str = "family:";
+
+/// Statuses the status filter cycles through, after showing every status
+const STATUS_FILTERS: [&str; 3] = ["RUNNING", "TERMINATED", "SUSPENDED"];
+
+/// Get the status filter following the given one, `None` showing every status
+pub fn next_status_filter(current: Option<&str>) -> Option<String> {
+    let next = match current {
+        None => STATUS_FILTERS.first(),
+        Some(status) => STATUS_FILTERS
+            .iter()
+            .position(|filter| *filter == status)
+            .and_then(|index| STATUS_FILTERS.get(index + 1)),
+    };
+    next.map(|status| status.to_string())
+}
+
+/// Check if an instance matches every whitespace-separated token of a filter
+///
+/// `family:<name>` tokens match the machine family, `<key>:<value>` tokens match
+/// a label, other tokens match any displayed field or label as a case-insensitive
+/// substring.
+pub fn matches(instance: &Instance, filter: &str) -> bool {
+    filter
+        .split_whitespace()
+        .all(|token| matches_token(instance, &token.to_lowercase()))
+}
+
+/// Check if an instance matches a single lowercase filter token
+fn matches_token(instance: &Instance, token: &str) -> bool {
+    if let Some(family) = token.strip_prefix(FAMILY_TOKEN) {
+        return cloud::is_machine_family(&instance.machine_type, family);
+    }
+
+    if let Some((key, value)) = token.split_once(':') {
+        return labels(instance).any(|(label_key, label_value)| {
+            label_key.to_lowercase() == key && label_value.to_lowercase().contains(value)
+        });
+    }
+
+    instance.name.to_lowercase().contains(token)
+        || instance.status.to_lowercase().contains(token)
+        || instance.machine_type.to_lowercase().contains(token)
+        || instance.zone.to_lowercase().contains(token)
+        || instance.project.to_lowercase().contains(token)
+        || instance
+            .network
+            .as_ref()
+            .is_some_and(|n| n.to_lowercase().contains(token))
+        || instance
+            .internal_ip
+            .as_ref()
+            .is_some_and(|ip| ip.to_lowercase().contains(token))
+        || labels(instance).any(|(key, value)| {
+            key.to_lowercase().contains(token) || value.to_lowercase().contains(token)
+        })
+}
+
+/// Iterate over the labels of an instance, if any
+fn labels(instance: &Instance) -> impl Iterator<Item = (&String, &String)> {
+    instance.labels.iter().flatten()
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::cloud::FirewallRule;
+
+/// State of the effective firewall rules popup
+pub struct FirewallsView {
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Zone of the instance
+    pub zone: String,
+    /// Effective rules, ingress first, by priority
+    rules: Vec<FirewallRule>,
+    /// Error message if the rules could not be read
+    error: Option<String>,
+    /// Vertical scroll offset
+    scroll: u16,
+}
+
+impl FirewallsView {
+    /// Create a new firewall rules view for an instance
+    pub fn new(instance_name: String, zone: String) -> Self {
+        Self {
+            instance_name,
+            zone,
+            rules: Vec::new(),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Replace the displayed rules
+    pub fn set_rules(&mut self, rules: Vec<FirewallRule>) {
+        self.rules = rules;
+        self.error = None;
+        self.scroll = 0;
+    }
+
+    /// Display an error instead of rules
+    pub fn set_error(&mut self, error: String) {
+        self.rules.clear();
+        self.error = Some(error);
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.rules.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the effective firewall rules popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &FirewallsView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(90, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Effective firewall rules: {} ({} rules)",
+            view.instance_name,
+            view.rules.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Rules
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    let header = Paragraph::new(Line::from(Span::styled(
+        format!(
+            "{:<8} {:<6} {:>8}  {:<30} {:<20} {:<24} {}",
+            "DIR", "ACTION", "PRIORITY", "NAME", "PROTOCOLS", "RANGES", "TARGETS"
+        ),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )));
+    frame.render_widget(header, chunks[0]);
+
+    // Render rules, or the error / empty message
+    let lines: Vec<Line> = if let Some(error) = &view.error {
+        vec![Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ))]
+    } else if view.rules.is_empty() {
+        vec![Line::from(Span::styled(
+            "No firewall rules apply to this instance",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        view.rules.iter().map(rule_line).collect()
+    };
+
+    let paragraph = Paragraph::new(lines).scroll((view.scroll, 0));
+    frame.render_widget(paragraph, chunks[1]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll, "),
+        Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to refresh"),
+    ]));
+    frame.render_widget(status_line, chunks[2]);
+}
+
+/// Format a firewall rule as a single line
+fn rule_line(rule: &FirewallRule) -> Line<'_> {
+    let (action, action_color) = if rule.allow {
+        ("ALLOW", Color::Green)
+    } else {
+        ("DENY", Color::Red)
+    };
+    let style = if rule.disabled {
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::CROSSED_OUT)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let join_or = |values: &[String], empty: &str| {
+        if values.is_empty() {
+            empty.to_string()
+        } else {
+            values.join(",")
+        }
+    };
+    let priority = rule
+        .priority
+        .map_or_else(|| "-".to_string(), |priority| priority.to_string());
+
+    Line::from(vec![
+        Span::styled(format!("{:<8} ", rule.direction), style),
+        Span::styled(
+            format!("{:<6} ", action),
+            style.fg(if rule.disabled {
+                Color::DarkGray
+            } else {
+                action_color
+            }),
+        ),
+        Span::styled(format!("{:>8}  ", priority), style),
+        Span::styled(format!("{:<30} ", rule.name), style),
+        Span::styled(format!("{:<20} ", join_or(&rule.protocols, "all")), style),
+        Span::styled(format!("{:<24} ", join_or(&rule.ranges, "-")), style),
+        Span::styled(join_or(&rule.target_tags, "all instances"), style),
+    ])
+}
@@ -0,0 +1,42 @@
+/// Score a fuzzy match of a pattern against a candidate, case-insensitively
+///
+/// Every pattern character must appear in the candidate, in order. Returns
+/// `None` when they don't, otherwise a score where higher is better:
+/// consecutive characters, matches at word starts and early matches rank first.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i64> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pattern_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (i, &c) in candidate.iter().enumerate() {
+        if pattern_index == pattern.len() {
+            break;
+        }
+        if c != pattern[pattern_index] {
+            continue;
+        }
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || matches!(candidate[i - 1], '-' | '_' | '.' | ' ' | '/') {
+            score += 3;
+        }
+        if previous_match.is_none() {
+            // Prefer matches starting early in the candidate
+            score -= i.min(10) as i64;
+        }
+
+        previous_match = Some(i);
+        pattern_index += 1;
+    }
+
+    (pattern_index == pattern.len()).then_some(score)
+}
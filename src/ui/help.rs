@@ -7,8 +7,94 @@ use ratatui::{
     Frame,
 };
 
+use crate::keymap::HelpEntry;
+
+/// Columns explained after the key bindings: header and description
+const COLUMN_HELP: [(&str, &str); 1] = [(
+    "DISK",
+    "Total size of all attached disks in GB (- when unknown)",
+)];
+
+/// State of the help popup
+pub struct HelpView {
+    /// Entries of the bound actions, by section
+    entries: Vec<HelpEntry>,
+    /// Text typed by the user to narrow the entries
+    query: String,
+    /// Vertical scroll offset
+    scroll: u16,
+}
+
+impl HelpView {
+    /// Create a new help popup listing the given entries
+    pub fn new(entries: Vec<HelpEntry>) -> Self {
+        Self {
+            entries,
+            query: String::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Append a character to the query
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.scroll = 0;
+    }
+
+    /// Remove the last character of the query
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.scroll = 0;
+    }
+
+    /// Check if the user typed a query
+    pub fn has_query(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Clear the query
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_add(lines);
+    }
+
+    /// Check if keys and their description match the query, ignoring case
+    fn matches(&self, keys: &str, description: &str) -> bool {
+        let query = self.query.to_lowercase();
+        keys.to_lowercase().contains(&query) || description.to_lowercase().contains(&query)
+    }
+}
+
+/// Build the line of a key and its description
+fn entry_line<'a>(keys: &'a str, description: &'a str) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(keys, Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!(" - {}", description)),
+    ])
+}
+
+/// Build the title line of a section
+fn section_line(title: &str) -> Line<'_> {
+    Line::from(Span::styled(
+        title,
+        Style::default()
+            .add_modifier(Modifier::BOLD)
+            .fg(Color::Cyan),
+    ))
+}
+
 /// Render the help popup
-pub fn render<B: Backend>(frame: &mut Frame<B>, area: Rect) {
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &HelpView, area: Rect) {
     // Create a centered popup
     let popup_area = create_centered_rect(60, 70, area);
 
@@ -23,102 +109,79 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, area: Rect) {
         Block::default().style(Style::default().bg(Color::Black)),
         popup_area,
     );
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
 
-    // Create the help text
-    let help_text = vec![
-        Line::from(Span::styled(
-            "GCI - Google Cloud Instances",
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Search input
+            Constraint::Length(1), // Spacer
+            Constraint::Min(1),    // Help text
+        ])
+        .split(inner_area);
+
+    // Render the search input with a cursor
+    let search = Paragraph::new(Line::from(vec![
+        Span::styled("Search: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{}_", view.query),
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Navigation",
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Cyan),
-        )),
-        Line::from(vec![
-            Span::styled("↑/k", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Move selection up"),
-        ]),
-        Line::from(vec![
-            Span::styled("↓/j", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Move selection down"),
-        ]),
-        Line::from(vec![
-            Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Show instance details"),
-        ]),
-        Line::from(vec![
-            Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Close popup or cancel action"),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Filtering and Searching",
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Cyan),
-        )),
-        Line::from(vec![
-            Span::styled("f", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Toggle filter mode"),
-        ]),
-        Line::from(vec![
-            Span::styled("/", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Toggle search mode"),
-        ]),
-        Line::from(""),
+        ),
+    ]));
+    frame.render_widget(search, chunks[0]);
+
+    // Create the help text, with the sections having a matching entry
+    let mut help_text = vec![
         Line::from(Span::styled(
-            "Instance Actions",
+            "GCI - Google Cloud Instances",
             Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Cyan),
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
         )),
-        Line::from(vec![
-            Span::styled("s", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Start selected instance"),
-        ]),
-        Line::from(vec![
-            Span::styled("S", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Stop selected instance"),
-        ]),
-        Line::from(vec![
-            Span::styled("R", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Restart selected instance"),
-        ]),
-        Line::from(vec![
-            Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Delete selected instance (with confirmation)"),
-        ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "Miscellaneous",
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Cyan),
-        )),
-        Line::from(vec![
-            Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Refresh instance data"),
-        ]),
-        Line::from(vec![
-            Span::styled("?", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Toggle this help screen"),
-        ]),
-        Line::from(vec![
-            Span::styled("q/Ctrl+c", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" - Quit application"),
-        ]),
     ];
 
+    let entries: Vec<&HelpEntry> = view
+        .entries
+        .iter()
+        .filter(|entry| view.matches(&entry.keys, entry.description))
+        .collect();
+    for (index, entry) in entries.iter().enumerate() {
+        if index == 0 || entries[index - 1].section != entry.section {
+            if index > 0 {
+                help_text.push(Line::from(""));
+            }
+            help_text.push(section_line(entry.section.title()));
+        }
+        help_text.push(entry_line(&entry.keys, entry.description));
+    }
+
+    let columns: Vec<Line> = COLUMN_HELP
+        .iter()
+        .filter(|(header, description)| view.matches(header, description))
+        .map(|(header, description)| entry_line(header, description))
+        .collect();
+    if entries.is_empty() && columns.is_empty() {
+        help_text.push(Line::from(Span::styled(
+            format!("No key binding matching \"{}\"", view.query),
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else if !columns.is_empty() {
+        if !entries.is_empty() {
+            help_text.push(Line::from(""));
+        }
+        help_text.push(section_line("Columns"));
+        help_text.extend(columns);
+    }
+
     let paragraph = Paragraph::new(help_text)
-        .block(block)
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((view.scroll, 0));
 
-    frame.render_widget(paragraph, popup_area);
+    frame.render_widget(paragraph, chunks[2]);
 }
 
 /// Helper function to create a centered rect
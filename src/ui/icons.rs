@@ -0,0 +1,102 @@
+/// Icon shown before a label, as an emoji or, for terminals without emoji
+/// glyphs, as an ASCII marker (or nothing when it is only decorative)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Title,
+    Filter,
+    Search,
+    Account,
+    Overview,
+    Count,
+    Cli,
+    Warning,
+    Project,
+    Region,
+    Running,
+    Stopped,
+    Other,
+    Instances,
+    Help,
+    InProgress,
+    Queued,
+    Copy,
+    Info,
+    Success,
+    Error,
+    Pricing,
+    Stop,
+}
+
+impl Icon {
+    /// Get the icon followed by its separator, empty for decorative icons without emoji
+    pub fn prefix(self, emoji: bool) -> &'static str {
+        if emoji {
+            match self {
+                Icon::Title => "🌩️  ",
+                Icon::Filter => "🔍 ",
+                Icon::Search => "🔎 ",
+                Icon::Account => "👤 ",
+                Icon::Overview => "📈 ",
+                Icon::Count => "📊 ",
+                Icon::Cli => "🖥️ ",
+                Icon::Warning => "⚠️ ",
+                Icon::Project => "🔑 ",
+                Icon::Region => "🌎 ",
+                Icon::Running => "🟢 ",
+                Icon::Stopped => "🔴 ",
+                Icon::Other => "❓ ",
+                Icon::Instances => "💻 ",
+                Icon::Help => "❓ ",
+                Icon::InProgress => "⏳ ",
+                Icon::Queued => "🕒 ",
+                Icon::Copy => "📋 ",
+                Icon::Info => "ℹ️ ",
+                Icon::Success => "✅ ",
+                Icon::Error => "❌ ",
+                Icon::Pricing => "💲 ",
+                Icon::Stop => "🛑 ",
+            }
+        } else {
+            match self {
+                Icon::Warning | Icon::Stop => "! ",
+                Icon::Running => "[R] ",
+                Icon::Stopped => "[T] ",
+                Icon::Other => "[?] ",
+                Icon::InProgress => "* ",
+                Icon::Queued => "- ",
+                Icon::Info => "[i] ",
+                Icon::Success => "[ok] ",
+                Icon::Error => "[x] ",
+                Icon::Pricing => "$ ",
+                _ => "",
+            }
+        }
+    }
+}
+
+/// Get the marker appended to the status of preemptible and spot instances
+pub fn preemptible_marker(emoji: bool) -> &'static str {
+    if emoji {
+        " ⚡"
+    } else {
+        " [spot]"
+    }
+}
+
+/// Get the checkbox shown before instances while some are marked for a bulk action
+pub fn mark_symbol(marked: bool) -> &'static str {
+    if marked {
+        "[x] "
+    } else {
+        "[ ] "
+    }
+}
+
+/// Get the symbol marking the selected row of a list
+pub fn highlight_symbol(emoji: bool) -> &'static str {
+    if emoji {
+        "➤ "
+    } else {
+        "> "
+    }
+}
@@ -0,0 +1,111 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+/// Single-line text input with a cursor, e.g. the filter and search text
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    /// Typed text
+    value: String,
+    /// Byte position of the cursor in the text, on a character boundary
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Create an input holding a text, with the cursor at its end
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.len(),
+        }
+    }
+
+    /// Get the typed text
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Check if no text is typed
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Remove the text
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    /// Edit the text or move the cursor, returning whether the key was used
+    ///
+    /// Typed characters are inserted at the cursor, Backspace and Delete remove
+    /// the character before and after it, arrows, Home and End move it, and
+    /// Ctrl+U clears the text.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('u') if control => self.clear(),
+            KeyCode::Char(c) if !control => {
+                self.value.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Backspace => {
+                if let Some(previous) = self.previous_boundary() {
+                    self.value.remove(previous);
+                    self.cursor = previous;
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < self.value.len() {
+                    self.value.remove(self.cursor);
+                }
+            }
+            KeyCode::Left => {
+                if let Some(previous) = self.previous_boundary() {
+                    self.cursor = previous;
+                }
+            }
+            KeyCode::Right => {
+                if let Some(next) = self.next_boundary() {
+                    self.cursor = next;
+                }
+            }
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Get the spans of the text, with the cursor shown as a reversed character
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        let cursor_style = style.add_modifier(Modifier::REVERSED);
+        let (before, after) = self.value.split_at(self.cursor);
+        let mut chars = after.chars();
+        let under_cursor = chars.next().map_or(" ".to_string(), String::from);
+
+        vec![
+            Span::styled(before.to_string(), style),
+            Span::styled(under_cursor, cursor_style),
+            Span::styled(chars.as_str().to_string(), style),
+        ]
+    }
+
+    /// Get the position of the character before the cursor
+    fn previous_boundary(&self) -> Option<usize> {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map(|(index, _)| index)
+    }
+
+    /// Get the position after the character under the cursor
+    fn next_boundary(&self) -> Option<usize> {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map(|c| self.cursor + c.len_utf8())
+    }
+}
@@ -1,16 +1,85 @@
 use ratatui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
     Frame,
 };
 
-use crate::cloud::Instance;
+use std::time::{Duration, Instant};
+
+use super::status::StatusStyle;
+use super::styles::Theme;
+use super::UiState;
+use crate::cloud::{DiskInfo, Instance, MachineSpec, NetworkInterfaceInfo};
+
+/// Most disks shown in the disks table, the others being listed in the raw JSON
+const MAX_DISK_ROWS: usize = 4;
+
+/// Time an instance entered its current status, tracked during the session
+#[derive(Debug, Clone, Copy)]
+pub struct StatusAge {
+    /// When the status was first seen
+    pub since: Instant,
+    /// Whether the status change was seen, or the status was already set when first listed
+    pub exact: bool,
+}
+
+impl StatusAge {
+    /// Describe the age, e.g. "for 2h14m" or "for at least 5m" when the change wasn't seen
+    pub fn describe(&self) -> String {
+        let elapsed = format_elapsed(self.since.elapsed());
+        if self.exact {
+            format!("for {}", elapsed)
+        } else {
+            format!("for at least {}", elapsed)
+        }
+    }
+}
+
+/// Format a duration with its two most significant units, e.g. "2h14m", "3d4h", "45s"
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{}d{}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format an uptime down to the minute, e.g. "3d 4h 12m", "4h 12m", "12m"
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Placeholder shown instead of sensitive metadata values
+const REDACTED: &str = "•••••••• (hidden, press v to reveal)";
+
+/// Render the instance details popup, with an indicator while it is refreshed
+pub fn render<B: Backend>(frame: &mut Frame<B>, state: &UiState, instance: &Instance, area: Rect) {
+    let theme = &state.theme;
 
-/// Render the instance details popup
-pub fn render<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area: Rect) {
     // Create a centered popup
     let popup_area = create_centered_rect(80, 80, area);
 
@@ -18,56 +87,88 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area: Rect)
     let _block = Block::default()
         .title(format!("Instance Details: {}", instance.name))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.accent));
 
     // Render the block
     frame.render_widget(
-        Block::default().style(Style::default().bg(Color::Black)),
+        Block::default().style(Style::default().bg(theme.background)),
         popup_area,
     );
 
+    // The accelerators row is only shown for instances with accelerators
+    let basic_info_height = if instance.accelerators.is_empty() {
+        12
+    } else {
+        13
+    };
+
+    // One row per disk under the header, or a line saying there is none
+    let disks_height = instance.disks.len().clamp(1, MAX_DISK_ROWS) as u16
+        + if instance.disks.is_empty() { 2 } else { 3 };
+
     // Split the popup into sections
     let popup_chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(1),  // Title
-            Constraint::Length(12), // Basic info table
-            Constraint::Min(3),     // Description and metadata
-            Constraint::Length(1),  // Status line
+            Constraint::Length(1),                 // Title
+            Constraint::Length(basic_info_height), // Basic info table
+            Constraint::Length(7),                 // Network interfaces table
+            Constraint::Length(disks_height),      // Disks table
+            Constraint::Min(3),                    // Description and metadata
+            Constraint::Length(1),                 // Status line
         ])
         .split(popup_area);
 
     // Render title
-    // Get status emoji
-    let status_emoji = match instance.status.as_str() {
-        "RUNNING" => "🟢",
-        "TERMINATED" => "🔴",
-        "STOPPING" => "🟠",
-        "PROVISIONING" => "🟡",
-        "STAGING" => "🔄",
-        "SUSPENDED" => "💤",
-        "REPAIRING" => "🟡",
-        "PENDING" => "🟡",
-        _ => "❓",
-    };
+    let status_emoji = state.status_styles.get(&instance.status).icon(state.emoji);
 
-    let title = Paragraph::new(Line::from(vec![Span::styled(
+    let mut title_spans = vec![Span::styled(
         format!(
             "Instance: {} ({}) {}",
             instance.name, instance.id, status_emoji
         ),
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.accent)
             .add_modifier(Modifier::BOLD),
-    )]));
+    )];
+    if state.details_refreshing {
+        title_spans.push(Span::styled(
+            if state.emoji {
+                " ⟳ refreshing…"
+            } else {
+                " (refreshing...)"
+            },
+            Style::default().fg(theme.highlight),
+        ));
+    }
+    let title = Paragraph::new(Line::from(title_spans));
     frame.render_widget(title, popup_chunks[0]);
 
     // Render basic info table
-    render_basic_info(frame, instance, popup_chunks[1]);
+    render_basic_info(
+        frame,
+        instance,
+        state.status_styles.get(&instance.status),
+        state.status_ages.get(&instance.id),
+        state.emoji,
+        theme,
+        popup_chunks[1],
+    );
+
+    // Render network interfaces
+    render_network_interfaces(
+        frame,
+        &instance.network_interfaces,
+        state.interfaces_scroll,
+        popup_chunks[2],
+    );
+
+    // Render disks
+    render_disks(frame, &instance.disks, theme, popup_chunks[3]);
 
-    // Render description and metadata
-    render_metadata(frame, instance, popup_chunks[2]);
+    // Render description, labels and metadata
+    render_metadata(frame, state, instance, popup_chunks[4]);
 
     // Render status line
     let status_line = Paragraph::new(Line::from(vec![
@@ -79,67 +180,130 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area: Rect)
         Span::styled("S", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(" to stop, "),
         Span::styled("R", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" to restart"),
+        Span::raw(" to restart, "),
+        Span::styled("l", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" for logs, "),
+        Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to refresh, "),
+        Span::styled("v", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(if state.reveal_sensitive {
+            " to hide secrets"
+        } else {
+            " to reveal secrets"
+        }),
+        Span::raw(", "),
+        Span::styled("J", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" for raw JSON, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll interfaces"),
     ]));
-    frame.render_widget(status_line, popup_chunks[3]);
+    frame.render_widget(status_line, popup_chunks[5]);
 }
 
 /// Render the basic information table
-fn render_basic_info<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area: Rect) {
-    // Get status emoji
-    let status_emoji = match instance.status.as_str() {
-        "RUNNING" => "🟢",
-        "TERMINATED" => "🔴",
-        "STOPPING" => "🟠",
-        "PROVISIONING" => "🟡",
-        "STAGING" => "🔄",
-        "SUSPENDED" => "💤",
-        "REPAIRING" => "🟡",
-        "PENDING" => "🟡",
-        _ => "❓",
-    };
+fn render_basic_info<B: Backend>(
+    frame: &mut Frame<B>,
+    instance: &Instance,
+    status_style: &StatusStyle,
+    status_age: Option<&StatusAge>,
+    emoji: bool,
+    theme: &Theme,
+    area: Rect,
+) {
+    // The exact status is shown, even when its label is customized or unknown
+    let mut status_spans = vec![Span::styled(
+        format!("{} {}", status_style.icon(emoji), instance.status.clone()),
+        Style::default().fg(status_style.color),
+    )];
+    if let Some(age) = status_age {
+        status_spans.push(Span::styled(
+            format!(" ({})", age.describe()),
+            Style::default().fg(theme.secondary),
+        ));
+    }
 
-    let rows = vec![
+    let mut rows = vec![
         Row::new(vec![
             Cell::from("Status"),
-            Cell::from(Span::styled(
-                format!("{} {}", status_emoji, instance.status.clone()),
-                status_style(&instance.status),
-            )),
+            Cell::from(Line::from(status_spans)),
         ]),
         Row::new(vec![
             Cell::from("Machine Type"),
-            Cell::from(instance.machine_type.clone()),
+            Cell::from(match MachineSpec::parse(&instance.machine_type) {
+                Some(spec) => format!("{} ({})", instance.machine_type, spec),
+                None => instance.machine_type.clone(),
+            }),
         ]),
+    ];
+    if let Some(cost) = instance.estimated_hourly_usd() {
+        rows.push(Row::new(vec![
+            Cell::from("Est. Cost"),
+            Cell::from(format!("~${:.2}/hr (on-demand list price estimate)", cost)),
+        ]));
+    }
+    if !instance.accelerators.is_empty() {
+        let accelerators = instance
+            .accelerators
+            .iter()
+            .map(|accelerator| format!("{}× {}", accelerator.count, accelerator.accelerator_type))
+            .collect::<Vec<_>>()
+            .join(", ");
+        rows.push(Row::new(vec![
+            Cell::from("Accelerators"),
+            Cell::from(accelerators),
+        ]));
+    }
+    rows.extend([
         Row::new(vec![Cell::from("Zone"), Cell::from(instance.zone.clone())]),
         Row::new(vec![
-            Cell::from("External IP"),
+            Cell::from("Provisioning"),
             Cell::from(
-                instance
-                    .external_ip
-                    .clone()
-                    .unwrap_or_else(|| "None".into()),
+                match (instance.provisioning_model.as_deref(), instance.preemptible) {
+                    (Some("SPOT"), _) => "Spot (preemptible)",
+                    (_, true) => "Preemptible",
+                    _ => "Standard",
+                },
             ),
         ]),
         Row::new(vec![
-            Cell::from("Internal IP"),
+            Cell::from("Created"),
             Cell::from(
                 instance
-                    .internal_ip
+                    .creation_timestamp
                     .clone()
-                    .unwrap_or_else(|| "None".into()),
+                    .unwrap_or_else(|| "Unknown".into()),
             ),
         ]),
         Row::new(vec![
-            Cell::from("Created"),
+            Cell::from("Uptime"),
             Cell::from(
                 instance
-                    .creation_timestamp
-                    .clone()
-                    .unwrap_or_else(|| "Unknown".into()),
+                    .uptime()
+                    .map(format_uptime)
+                    .unwrap_or_else(|| "-".into()),
             ),
         ]),
-    ];
+        Row::new(vec![
+            Cell::from("Secure Boot"),
+            shielded_cell(
+                instance.shielded.map(|shielded| shielded.secure_boot),
+                theme,
+            ),
+        ]),
+        Row::new(vec![
+            Cell::from("vTPM"),
+            shielded_cell(instance.shielded.map(|shielded| shielded.vtpm), theme),
+        ]),
+        Row::new(vec![
+            Cell::from("Integrity Monitoring"),
+            shielded_cell(
+                instance
+                    .shielded
+                    .map(|shielded| shielded.integrity_monitoring),
+                theme,
+            ),
+        ]),
+    ]);
 
     let table = Table::new(rows)
         .block(Block::default().borders(Borders::ALL).title("Basic Info"))
@@ -159,16 +323,169 @@ fn render_basic_info<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area
     frame.render_widget(table, area);
 }
 
-/// Render metadata and description
-fn render_metadata<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area: Rect) {
-    // Split area into description and metadata
+/// Render the network interfaces table, starting at the `scroll` interface
+fn render_network_interfaces<B: Backend>(
+    frame: &mut Frame<B>,
+    interfaces: &[NetworkInterfaceInfo],
+    scroll: usize,
+    area: Rect,
+) {
+    let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "None".into());
+
+    let rows: Vec<Row> = interfaces
+        .iter()
+        .skip(scroll)
+        .map(|iface| {
+            Row::new(vec![
+                Cell::from(iface.name.clone()),
+                Cell::from(value(&iface.network)),
+                Cell::from(value(&iface.subnetwork)),
+                Cell::from(value(&iface.internal_ip)),
+                Cell::from(value(&iface.external_ip)),
+                Cell::from(value(&iface.stack_type)),
+            ])
+        })
+        .collect();
+
+    // Show the position when some interfaces are scrolled out of view
+    let title = if interfaces.len() > 1 {
+        format!(
+            "Network Interfaces ({}/{})",
+            scroll.min(interfaces.len() - 1) + 1,
+            interfaces.len()
+        )
+    } else {
+        "Network Interfaces".to_string()
+    };
+
+    let header = Row::new(
+        [
+            "Name",
+            "Network",
+            "Subnetwork",
+            "Internal IP",
+            "External IP",
+            "Stack Type",
+        ]
+        .map(|title| {
+            Cell::from(Span::styled(
+                title,
+                Style::default().add_modifier(Modifier::BOLD),
+            ))
+        }),
+    );
+
+    let table = Table::new(rows)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .header(header)
+        .widths(&[
+            Constraint::Percentage(10),
+            Constraint::Percentage(18),
+            Constraint::Percentage(22),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(18),
+        ])
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
+/// Render the attached disks table
+fn render_disks<B: Backend>(frame: &mut Frame<B>, disks: &[DiskInfo], theme: &Theme, area: Rect) {
+    if disks.is_empty() {
+        let block = Block::default().borders(Borders::ALL).title("Disks");
+        let paragraph = Paragraph::new(Span::styled(
+            "No disk information",
+            Style::default().fg(theme.muted),
+        ))
+        .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let rows: Vec<Row> = disks
+        .iter()
+        .map(|disk| {
+            Row::new(vec![
+                Cell::from(disk.device_name.clone()),
+                Cell::from(
+                    disk.size_gb
+                        .map(|size| format!("{} GB", size))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(disk.disk_type.clone()),
+                if disk.boot {
+                    Cell::from(Span::styled("boot", Style::default().fg(theme.success)))
+                } else {
+                    Cell::from("")
+                },
+            ])
+        })
+        .collect();
+
+    let header = Row::new(["Device", "Size", "Type", "Boot"].map(|title| {
+        Cell::from(Span::styled(
+            title,
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+    }));
+
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Disks ({})", disks.len())),
+        )
+        .header(header)
+        .widths(&[
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+            Constraint::Percentage(25),
+            Constraint::Percentage(15),
+        ])
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
+/// Get the cell of a Shielded VM setting, `None` when not configured
+fn shielded_cell(enabled: Option<bool>, theme: &Theme) -> Cell<'static> {
+    match enabled {
+        Some(true) => Cell::from(Span::styled("enabled", Style::default().fg(theme.success))),
+        Some(false) => Cell::from(Span::styled(
+            "disabled",
+            Style::default().fg(theme.highlight),
+        )),
+        None => Cell::from(Span::styled(
+            "not configured",
+            Style::default().fg(theme.muted),
+        )),
+    }
+}
+
+/// Render the description, labels and metadata
+fn render_metadata<B: Backend>(
+    frame: &mut Frame<B>,
+    state: &UiState,
+    instance: &Instance,
+    area: Rect,
+) {
+    // Split area into description, then labels and metadata side by side
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(4), // Description
-            Constraint::Min(0),    // Metadata
+            Constraint::Min(0),    // Labels and metadata
         ])
         .split(area);
+    let bottom_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40), // Labels
+            Constraint::Percentage(60), // Metadata
+        ])
+        .split(chunks[1]);
 
     // Render description if available
     let description = instance
@@ -180,32 +497,49 @@ fn render_metadata<B: Backend>(frame: &mut Frame<B>, instance: &Instance, area:
         .wrap(Wrap { trim: true });
     frame.render_widget(description_paragraph, chunks[0]);
 
+    // Render labels if available
+    let labels_text = match &instance.labels {
+        Some(labels) if !labels.is_empty() => {
+            let mut labels: Vec<_> = labels.iter().collect();
+            labels.sort();
+            labels
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        _ => "No labels".to_string(),
+    };
+
+    let labels_paragraph = Paragraph::new(labels_text)
+        .block(Block::default().borders(Borders::ALL).title("Labels"))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(labels_paragraph, bottom_chunks[0]);
+
     // Render metadata if available
-    let metadata_text = if let Some(metadata) = &instance.metadata {
-        format!("{:#?}", metadata)
-    } else {
-        "No metadata available".to_string()
+    let metadata_text = match &instance.metadata {
+        Some(metadata) if !metadata.is_empty() => {
+            let mut keys: Vec<&String> = metadata.keys().collect();
+            keys.sort();
+            keys.iter()
+                .map(|key| {
+                    let value = if !state.reveal_sensitive && state.is_sensitive_key(key) {
+                        REDACTED
+                    } else {
+                        metadata[*key].as_str()
+                    };
+                    format!("{}: {}", key, value)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        _ => "No metadata available".to_string(),
     };
 
     let metadata_paragraph = Paragraph::new(metadata_text)
         .block(Block::default().borders(Borders::ALL).title("Metadata"))
         .wrap(Wrap { trim: true });
-    frame.render_widget(metadata_paragraph, chunks[1]);
-}
-
-/// Get the style for a status
-fn status_style(status: &str) -> Style {
-    match status {
-        "RUNNING" => Style::default().fg(Color::Green),
-        "TERMINATED" => Style::default().fg(Color::Red),
-        "STOPPING" => Style::default().fg(Color::Yellow),
-        "PROVISIONING" => Style::default().fg(Color::Yellow),
-        "STAGING" => Style::default().fg(Color::Cyan),
-        "SUSPENDED" => Style::default().fg(Color::Gray),
-        "REPAIRING" => Style::default().fg(Color::Yellow),
-        "PENDING" => Style::default().fg(Color::Yellow),
-        _ => Style::default(),
-    }
+    frame.render_widget(metadata_paragraph, bottom_chunks[1]);
 }
 
 /// Helper function to create a centered rect
@@ -0,0 +1,175 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::cloud::LogEntry;
+
+/// Default number of log entries to fetch
+pub const DEFAULT_LOG_LIMIT: usize = 50;
+
+/// Step used when adjusting the number of log entries
+const LOG_LIMIT_STEP: usize = 50;
+
+/// Maximum number of log entries to fetch
+const MAX_LOG_LIMIT: usize = 1000;
+
+/// State of the instance logs popup
+pub struct LogsView {
+    /// Instance ID the logs belong to
+    pub instance_id: String,
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Number of entries to fetch
+    pub limit: usize,
+    /// Fetched log entries, most recent first
+    entries: Vec<LogEntry>,
+    /// Error message if the logs could not be read
+    error: Option<String>,
+    /// Vertical scroll offset
+    scroll: u16,
+}
+
+impl LogsView {
+    /// Create a new logs view for an instance
+    pub fn new(instance_id: String, instance_name: String, limit: usize) -> Self {
+        Self {
+            instance_id,
+            instance_name,
+            limit,
+            entries: Vec::new(),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Replace the displayed entries
+    pub fn set_entries(&mut self, entries: Vec<LogEntry>) {
+        self.entries = entries;
+        self.error = None;
+        self.scroll = 0;
+    }
+
+    /// Display an error instead of entries
+    pub fn set_error(&mut self, error: String) {
+        self.entries.clear();
+        self.error = Some(error);
+        self.scroll = 0;
+    }
+
+    /// Fetch more entries next time
+    pub fn increase_limit(&mut self) {
+        self.limit = (self.limit + LOG_LIMIT_STEP).min(MAX_LOG_LIMIT);
+    }
+
+    /// Fetch fewer entries next time
+    pub fn decrease_limit(&mut self) {
+        self.limit = self
+            .limit
+            .saturating_sub(LOG_LIMIT_STEP)
+            .max(LOG_LIMIT_STEP);
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.entries.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the instance logs popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &LogsView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Logs: {} ({} entries, limit {})",
+            view.instance_name,
+            view.entries.len(),
+            view.limit
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Log entries
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    // Render entries, or the error / empty message
+    let lines: Vec<Line> = if let Some(error) = &view.error {
+        vec![Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ))]
+    } else if view.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No log entries found",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        view.entries.iter().map(entry_line).collect()
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((view.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll, "),
+        Span::styled("+/-", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to change the entry count, "),
+        Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to refresh"),
+    ]));
+    frame.render_widget(status_line, chunks[1]);
+}
+
+/// Format a log entry as a single line
+fn entry_line(entry: &LogEntry) -> Line<'_> {
+    let severity = entry.severity.as_deref().unwrap_or("DEFAULT");
+    let severity_color = match severity {
+        "EMERGENCY" | "ALERT" | "CRITICAL" | "ERROR" => Color::Red,
+        "WARNING" => Color::Yellow,
+        "NOTICE" | "INFO" => Color::Green,
+        _ => Color::Gray,
+    };
+
+    Line::from(vec![
+        Span::styled(
+            entry.timestamp.as_deref().unwrap_or("-"),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:<8}", severity),
+            Style::default().fg(severity_color),
+        ),
+        Span::raw(" "),
+        Span::raw(entry.message()),
+    ])
+}
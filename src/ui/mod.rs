@@ -5,122 +5,615 @@ use crossterm::{
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
     Terminal,
 };
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
 use std::io;
 
+mod accounts;
+mod bulk_progress;
+mod column_picker;
+mod columns;
+mod command;
+mod confirm;
+mod create;
 mod dashboard;
+mod filter;
+mod firewalls;
+mod fuzzy;
 mod help;
+mod icons;
+mod input;
 mod instance_details;
+mod logs;
+mod no_project;
+mod notifications;
+mod palette;
+mod raw_json;
+mod regions;
+mod resize;
+mod search;
+mod serial_console;
+mod sort;
+mod status;
 mod styles;
+mod tags;
 
+use self::input::TextInput;
+use self::notifications::NotificationHistory;
+use self::styles::{PlainColors, Theme};
 use crate::cloud::Instance;
+use crate::config::Config;
+use crate::keymap::HelpEntry;
+use crate::logging;
+
+pub use self::accounts::AccountsView;
+pub use self::bulk_progress::{BulkItemState, BulkProgressView};
+pub use self::column_picker::ColumnPickerView;
+pub use self::columns::{write_markdown_table, write_table, Column, ALL_COLUMNS};
+pub use self::command::CommandView;
+pub use self::create::CreateView;
+pub use self::dashboard::{CountFocus, LoadingState};
+pub use self::firewalls::FirewallsView;
+pub use self::help::HelpView;
+pub use self::instance_details::StatusAge;
+pub use self::logs::{LogsView, DEFAULT_LOG_LIMIT};
+pub use self::notifications::NotificationLevel;
+pub use self::palette::PaletteView;
+pub use self::raw_json::RawJsonView;
+pub use self::regions::{RegionScope, RegionsView};
+pub use self::resize::ResizeView;
+pub use self::serial_console::SerialConsoleView;
+pub use self::sort::SortKey;
+pub use self::status::{StatusStyleConfig, StatusStyles};
+pub use self::tags::TagsView;
 
 /// UI state and action types
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     Start,
     Stop,
     Restart,
+    Delete,
+    /// Change the machine type, stopping and starting the instance with `restart`
+    Resize {
+        machine_type: String,
+        restart: bool,
+    },
+    /// Add and remove network tags
+    UpdateTags {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+impl Action {
+    /// Check if the action stops, restarts or deletes the instance
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Action::Stop | Action::Restart | Action::Delete)
+    }
+
+    /// Describe the action for notifications
+    pub fn description(&self) -> String {
+        match self {
+            Action::Start => "start".to_string(),
+            Action::Stop => "stop".to_string(),
+            Action::Restart => "restart".to_string(),
+            Action::Delete => "delete".to_string(),
+            Action::Resize { machine_type, .. } => format!("resize to {}", machine_type),
+            Action::UpdateTags { add, remove } => {
+                let changes: Vec<String> = add
+                    .iter()
+                    .map(|tag| format!("+{}", tag))
+                    .chain(remove.iter().map(|tag| format!("-{}", tag)))
+                    .collect();
+                format!("update tags ({})", changes.join(", "))
+            }
+        }
+    }
+}
+
+/// Action waiting for confirmation, on the instances selected or marked when it was requested
+///
+/// The instances are kept by ID, so a refresh reordering the list can't change
+/// the instances the action applies to.
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub action: Action,
+    pub instance_ids: Vec<String>,
+    pub instance_names: Vec<String>,
+    /// Whether the action applies to the marked instances
+    pub bulk: bool,
+}
+
+impl Confirmation {
+    /// Get the question asked to the user
+    fn message(&self) -> String {
+        let names = self.instance_names.join(", ");
+        match (&self.action, self.bulk) {
+            (Action::Delete, _) => format!("Delete instance {}? This cannot be undone", names),
+            (_, false) => format!("Really {} instance {}?", self.action.description(), names),
+            (_, true) => format!(
+                "Really {} {} marked instance(s): {}?",
+                self.action.description(),
+                self.instance_names.len(),
+                names
+            ),
+        }
+    }
 }
 
 /// UI state that manages all UI components
 pub struct UiState {
+    /// All instances, before filtering
+    all_instances: Vec<Instance>,
+    /// Instances missing from the last listing, kept marked for one refresh before removal
+    vanished: HashSet<String>,
     /// The list of instances
     instances: Vec<Instance>,
     /// Currently selected instance index
     selected_index: usize,
-    /// Whether to show the help popup
-    show_help: bool,
+    /// IDs of the instances marked for a bulk action
+    marked: HashSet<String>,
+    /// First instance shown in the list, kept between draws so the list only scrolls when needed
+    list_offset: Cell<usize>,
+    /// Number of instances that fit in the list at the last draw
+    list_height: Cell<usize>,
+    /// Area of the instance rows at the last draw, to find the instance under the mouse
+    list_area: Cell<Rect>,
+    /// Help popup, if open
+    help: Option<HelpView>,
     /// Whether to show instance details
     show_details: bool,
     /// Whether we're in filter mode
     filter_mode: bool,
     /// Current filter text
-    filter: String,
-    /// Whether we're in search mode
+    filter: TextInput,
+    /// Whether we're in search mode, typing the search text
     search_mode: bool,
-    /// Current search text
-    search: String,
-    /// Current popup confirmation state
-    confirmation: Option<Action>,
+    /// Current search text, highlighted in the list until cleared
+    search: TextInput,
+    /// Action waiting for the user's confirmation
+    confirmation: Option<Confirmation>,
     /// Project ID from cloud client
     project_id: String,
     /// Region from cloud client
     region: String,
+    /// Zone used by default, e.g. to create instances
+    default_zone: String,
     /// gcloud CLI version
     cli_version: String,
+    /// Warning shown when the gcloud CLI version is not supported
+    cli_version_warning: Option<String>,
+    /// Active gcloud account
+    account: Option<String>,
+    /// Service account impersonated by gcloud commands
+    impersonated_account: Option<String>,
+    /// Whether to only show instances created by the active account
+    only_mine: bool,
+    /// Whether only instances with an external IP are shown
+    only_public: bool,
+    /// Status of the only instances shown, if any
+    status_filter: Option<String>,
+    /// Smoothed total instance count history
+    total_history: Vec<u64>,
+    /// Smoothed running instance count history
+    running_history: Vec<u64>,
+    /// Instance logs popup, if open
+    logs: Option<LogsView>,
+    /// Effective firewall rules popup, if open
+    firewalls: Option<FirewallsView>,
+    /// Machine type change popup, if open
+    resize: Option<ResizeView>,
+    /// Network tags popup, if open
+    tags: Option<TagsView>,
+    /// Quick-jump instance picker, if open
+    palette: Option<PaletteView>,
+    /// Region switcher, if open
+    regions: Option<RegionsView>,
+    /// Account switcher, if open
+    accounts: Option<AccountsView>,
+    /// Progress of the last bulk action, kept to be shown again once it is hidden
+    bulk_progress: Option<BulkProgressView>,
+    /// Instance creation form, if open
+    create: Option<CreateView>,
+    /// Raw describe JSON popup, if open
+    raw_json: Option<RawJsonView>,
+    /// Serial console popup, if open
+    serial_console: Option<SerialConsoleView>,
+    /// gcloud command popup, if open
+    command: Option<CommandView>,
+    /// Columns of the instance list, in display order
+    columns: Vec<Column>,
+    /// Columns enabled by the user, some only shown in some cases
+    enabled_columns: Vec<Column>,
+    /// Whether the DISK column can be shown
+    show_disk_column: bool,
+    /// Whether the PROJECT column can be shown, when several projects are listed
+    show_project_column: bool,
+    /// Column picker popup, if open
+    column_picker: Option<ColumnPickerView>,
+    /// Whether zones are abbreviated to their suffix when a single region is listed
+    short_zone_names: bool,
+    /// Whether instances are shown as compact lines, without the overview
+    dense: bool,
+    /// IDs of the instances with an action still running, and names of the ones being created
+    actions_in_flight: HashSet<String>,
+    /// Progress of the first instance listing
+    loading: LoadingState,
+    /// Seconds between automatic refreshes
+    refresh_interval: u64,
+    /// Whether to ask before quitting with actions in flight
+    quit_confirmation: bool,
+    /// Whether to offer logging in again, once the credentials expired
+    reauth_prompt: bool,
+    /// Preferences changed during the session, asked to be saved before quitting
+    save_confirmation: Option<Vec<String>>,
+    /// Current animation frame, advanced by the main loop
+    animation_frame: usize,
+    /// Whether the instance shown in the details popup is being refreshed
+    details_refreshing: bool,
+    /// Lowercase patterns of metadata keys whose values are hidden
+    sensitive_patterns: Vec<String>,
+    /// Whether sensitive metadata values are shown
+    reveal_sensitive: bool,
+    /// How long each instance has been in its current status, by instance ID
+    status_ages: HashMap<String, StatusAge>,
+    /// First network interface shown in the details popup
+    interfaces_scroll: usize,
+    /// Whether the next key picks a gcloud command to copy
+    copy_mode: bool,
+    /// History of in-app notifications
+    notifications: NotificationHistory,
+    /// Whether to show the notification history popup
+    show_notifications: bool,
+    /// Order of the instance list
+    sort_key: SortKey,
+    /// Whether the instance list is sorted in ascending order
+    sort_ascending: bool,
+    /// Instance count emphasized in the overview
+    count_focus: CountFocus,
+    /// Whether to use emoji, or ASCII markers for terminals without emoji glyphs
+    emoji: bool,
+    /// Color, icon and label of each instance status
+    status_styles: StatusStyles,
+    /// Colors of the dashboard and details views
+    theme: Theme,
+    /// Progress of a manual refresh, shown in the status bar
+    refresh_progress: Option<String>,
 }
 
 impl UiState {
     /// Create a new UI state
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
+            all_instances: Vec::new(),
+            vanished: HashSet::new(),
             instances: Vec::new(),
             selected_index: 0,
-            show_help: false,
+            marked: HashSet::new(),
+            list_offset: Cell::new(0),
+            list_height: Cell::new(0),
+            list_area: Cell::new(Rect::default()),
+            help: None,
             show_details: false,
             filter_mode: false,
-            filter: String::new(),
+            filter: TextInput::default(),
             search_mode: false,
-            search: String::new(),
+            search: TextInput::default(),
             confirmation: None,
             project_id: String::new(),
             region: String::new(),
+            default_zone: String::new(),
             cli_version: String::new(),
+            cli_version_warning: None,
+            account: None,
+            impersonated_account: None,
+            only_mine: false,
+            only_public: false,
+            status_filter: None,
+            total_history: Vec::new(),
+            running_history: Vec::new(),
+            logs: None,
+            firewalls: None,
+            resize: None,
+            tags: None,
+            palette: None,
+            regions: None,
+            accounts: None,
+            bulk_progress: None,
+            create: None,
+            raw_json: None,
+            serial_console: None,
+            command: None,
+            columns: Column::visible(
+                &config.columns,
+                config.show_disk_column,
+                config.projects.len() > 1,
+            ),
+            enabled_columns: config.columns.clone(),
+            show_disk_column: config.show_disk_column,
+            show_project_column: config.projects.len() > 1,
+            column_picker: None,
+            short_zone_names: config.short_zone_names,
+            dense: config.dense,
+            actions_in_flight: HashSet::new(),
+            loading: LoadingState::Loading,
+            refresh_interval: config.refresh_interval,
+            quit_confirmation: false,
+            reauth_prompt: false,
+            save_confirmation: None,
+            animation_frame: 0,
+            details_refreshing: false,
+            sensitive_patterns: config
+                .sensitive_metadata_patterns
+                .iter()
+                .map(|pattern| pattern.to_lowercase())
+                .collect(),
+            reveal_sensitive: false,
+            status_ages: HashMap::new(),
+            interfaces_scroll: 0,
+            copy_mode: false,
+            notifications: NotificationHistory::default(),
+            show_notifications: false,
+            sort_key: config.sort_key,
+            sort_ascending: config.sort_ascending,
+            count_focus: config.count_focus,
+            status_styles: StatusStyles::new(&config.status_styles),
+            theme: if config.color {
+                Theme::from_name(&config.theme)
+            } else {
+                Theme::plain()
+            },
+            emoji: config.emoji && config.color,
+            refresh_progress: None,
         }
     }
 
+    /// Create a UI state with listed instances and the default configuration, for tests
+    #[cfg(test)]
+    pub fn with_instances(instances: Vec<Instance>) -> Self {
+        let mut state = Self::new(&Config::default());
+        state.update_instances(instances);
+        state.set_loading_state(LoadingState::Loaded);
+        state
+    }
+
     /// Update cloud information
-    pub fn update_cloud_info(&mut self, project_id: String, region: String, cli_version: String) {
+    pub fn update_cloud_info(
+        &mut self,
+        project_id: String,
+        region: String,
+        cli_version: String,
+        account: Option<String>,
+    ) {
         self.project_id = project_id;
         self.region = region;
         self.cli_version = cli_version;
+        self.account = account;
+    }
+
+    /// Set the zone used by default, shown next to the region
+    pub fn set_default_zone(&mut self, zone: String) {
+        self.default_zone = zone;
+    }
+
+    /// Set the service account impersonated by gcloud commands
+    pub fn set_impersonated_account(&mut self, account: Option<String>) {
+        self.impersonated_account = account;
+    }
+
+    /// Set the warning shown next to an unsupported gcloud CLI version
+    pub fn set_cli_version_warning(&mut self, warning: Option<String>) {
+        self.cli_version_warning = warning;
+    }
+
+    /// Update the instance count history shown in the overview
+    pub fn update_count_history(&mut self, total: Vec<u64>, running: Vec<u64>) {
+        self.total_history = total;
+        self.running_history = running;
+    }
+
+    /// Update how long each instance has been in its current status
+    pub fn set_status_ages(&mut self, status_ages: HashMap<String, StatusAge>) {
+        self.status_ages = status_ages;
     }
 
     /// Update the list of instances
-    pub fn update_instances(&mut self, instances: Vec<Instance>) {
-        let _old_len = self.instances.len();
-        self.instances = instances;
+    ///
+    /// Instances being deleted (or listed inconsistently while created) can be
+    /// missing from a single listing. They stay for one more refresh, marked as
+    /// vanished, so the list doesn't shift under the selection at once.
+    pub fn update_instances(&mut self, mut instances: Vec<Instance>) {
+        let mut vanished = HashSet::new();
+        for instance in std::mem::take(&mut self.all_instances) {
+            let listed = instances.iter().any(|listed| listed.id == instance.id);
+            if !listed && !self.vanished.contains(&instance.id) {
+                vanished.insert(instance.id.clone());
+                instances.push(instance);
+            }
+        }
+        self.vanished = vanished;
+        self.all_instances = instances;
 
         // Apply any active filters
-        if !self.filter.is_empty() {
-            self.apply_filter();
-        }
+        self.apply_filter();
 
         // Adjust selected index if needed
         self.ensure_valid_selection();
     }
 
-    /// Apply the current filter to the instances
+    /// Replace a single instance with a freshly described one
+    pub fn update_instance(&mut self, instance: Instance) {
+        if let Some(existing) = self
+            .all_instances
+            .iter_mut()
+            .find(|existing| existing.id == instance.id)
+        {
+            *existing = instance;
+            self.apply_filter();
+        }
+    }
+
+    /// Apply the current filters to the instances
     fn apply_filter(&mut self) {
-        let filter = self.filter.to_lowercase();
-        self.instances.retain(|instance| {
-            instance.name.to_lowercase().contains(&filter)
-                || instance.status.to_lowercase().contains(&filter)
-                || instance.machine_type.to_lowercase().contains(&filter)
-                || instance.zone.to_lowercase().contains(&filter)
-                || instance
-                    .network
-                    .as_ref()
-                    .map_or(false, |n| n.to_lowercase().contains(&filter))
-                || instance
-                    .internal_ip
-                    .as_ref()
-                    .map_or(false, |ip| ip.to_lowercase().contains(&filter))
-        });
+        // Keep the highlight on the same instance when the list changes
+        let selected_id = self.selected_instance_id();
+
+        self.instances = self.all_instances.clone();
+
+        // Keep only instances created by the active account
+        if self.only_mine {
+            match &self.account {
+                Some(account) => self
+                    .instances
+                    .retain(|instance| instance.is_owned_by(account)),
+                None => self.instances.clear(),
+            }
+        }
+
+        // Keep only instances reachable from the internet
+        if self.only_public {
+            self.instances.retain(Instance::has_external_ip);
+        }
+
+        // Keep only instances in the filtered status
+        if let Some(status) = &self.status_filter {
+            self.instances.retain(|instance| &instance.status == status);
+        }
+
+        // Keep only instances matching every token of the filter text
+        if !self.filter.is_empty() {
+            let filter = self.filter.value();
+            self.instances
+                .retain(|instance| filter::matches(instance, filter));
+        }
+
+        self.sort_key.sort(&mut self.instances, self.sort_ascending);
+
+        if let Some(index) =
+            selected_id.and_then(|id| self.instances.iter().position(|instance| instance.id == id))
+        {
+            self.selected_index = index;
+        }
 
         // Make sure selected index is still valid after filtering
         self.ensure_valid_selection();
     }
 
-    /// Toggle help popup
-    pub fn toggle_help(&mut self) {
-        self.show_help = !self.show_help;
+    /// Switch between the dense and the full instance list layouts
+    pub fn toggle_dense(&mut self) -> bool {
+        self.dense = !self.dense;
+        self.dense
+    }
+
+    /// Switch between the dark and light themes, returning the new theme name
+    ///
+    /// Returns `None` without colors, the plain theme being kept.
+    pub fn toggle_theme(&mut self) -> Option<&'static str> {
+        if self.theme.is_plain() {
+            return None;
+        }
+        self.theme = self.theme.toggled();
+        Some(self.theme.name)
+    }
+
+    /// Open the column picker
+    pub fn open_column_picker(&mut self) {
+        self.column_picker = Some(ColumnPickerView::new(&self.enabled_columns));
+    }
+
+    /// Close the column picker
+    pub fn close_column_picker(&mut self) {
+        self.column_picker = None;
+    }
+
+    /// Check if the column picker is open
+    pub fn is_column_picker_open(&self) -> bool {
+        self.column_picker.is_some()
+    }
+
+    /// Get the column picker, if open
+    pub fn column_picker_view_mut(&mut self) -> Option<&mut ColumnPickerView> {
+        self.column_picker.as_mut()
+    }
+
+    /// Show the enabled columns in the instance list
+    pub fn set_enabled_columns(&mut self, enabled: Vec<Column>) {
+        self.columns = Column::visible(&enabled, self.show_disk_column, self.show_project_column);
+        self.enabled_columns = enabled;
+    }
+
+    /// Sort the instance list by the next sort key
+    pub fn cycle_sort(&mut self) -> SortKey {
+        self.sort_key = self.sort_key.next();
+        self.apply_filter();
+        self.sort_key
+    }
+
+    /// Reverse the order of the instance list
+    pub fn toggle_sort_direction(&mut self) -> bool {
+        self.sort_ascending = !self.sort_ascending;
+        self.apply_filter();
+        self.sort_ascending
+    }
+
+    /// Switch the overview emphasis between the total and shown counts
+    pub fn toggle_count_focus(&mut self) -> CountFocus {
+        self.count_focus = self.count_focus.toggled();
+        self.count_focus
+    }
+
+    /// Open the help popup listing the given key bindings
+    pub fn open_help(&mut self, entries: Vec<HelpEntry>) {
+        self.help = Some(HelpView::new(entries));
         self.filter_mode = false;
         self.search_mode = false;
     }
 
+    /// Close the help popup
+    pub fn close_help(&mut self) {
+        self.help = None;
+    }
+
+    /// Check if the help popup is open
+    pub fn is_help_open(&self) -> bool {
+        self.help.is_some()
+    }
+
+    /// Get the help popup, if open
+    pub fn help_view_mut(&mut self) -> Option<&mut HelpView> {
+        self.help.as_mut()
+    }
+
+    /// Toggle showing only the instances created by the active account
+    pub fn toggle_only_mine(&mut self) {
+        self.only_mine = !self.only_mine;
+        self.apply_filter();
+    }
+
+    /// Toggle showing only the instances with an external IP
+    pub fn toggle_only_public(&mut self) {
+        self.only_public = !self.only_public;
+        self.apply_filter();
+    }
+
+    /// Show only the instances in the next status: RUNNING, TERMINATED, SUSPENDED, then all
+    pub fn cycle_status_filter(&mut self) {
+        self.status_filter = filter::next_status_filter(self.status_filter.as_deref());
+        self.apply_filter();
+    }
+
+    /// Check if any instance carries ownership information
+    fn has_ownership_info(&self) -> bool {
+        self.all_instances
+            .iter()
+            .any(|instance| instance.owner.is_some())
+    }
+
     /// Toggle filter mode
     pub fn toggle_filter_mode(&mut self) {
         self.filter_mode = !self.filter_mode;
@@ -128,16 +621,66 @@ impl UiState {
         if !self.filter_mode {
             // Reset filter when leaving filter mode
             self.filter.clear();
+            self.apply_filter();
         }
     }
 
-    /// Toggle search mode
+    /// Toggle search mode, starting a new search or clearing the current one
     pub fn toggle_search_mode(&mut self) {
         self.search_mode = !self.search_mode;
         self.filter_mode = false;
-        if !self.search_mode {
-            // Reset search when leaving search mode
-            self.search.clear();
+        self.search.clear();
+    }
+
+    /// Stop typing the filter or search text, keeping it applied
+    pub fn finish_input(&mut self) {
+        self.filter_mode = false;
+        self.search_mode = false;
+    }
+
+    /// Get the indices of the shown instances with a column matching the search text
+    ///
+    /// Matching is case-insensitive and only looks at the shown columns, so
+    /// every match is highlighted. An empty search matches nothing.
+    pub fn search_matches(&self) -> Vec<usize> {
+        if self.search.is_empty() {
+            return Vec::new();
+        }
+
+        self.instances
+            .iter()
+            .enumerate()
+            .filter(|(_, instance)| {
+                self.columns.iter().any(|column| {
+                    search::find(&column.value(instance), self.search.value()).is_some()
+                })
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Select the next instance matching the search text, wrapping around
+    pub fn select_next_match(&mut self) {
+        let matches = self.search_matches();
+        if let Some(index) = matches
+            .iter()
+            .find(|index| **index > self.selected_index)
+            .or(matches.first())
+        {
+            self.selected_index = *index;
+        }
+    }
+
+    /// Select the previous instance matching the search text, wrapping around
+    pub fn select_previous_match(&mut self) {
+        let matches = self.search_matches();
+        if let Some(index) = matches
+            .iter()
+            .rev()
+            .find(|index| **index < self.selected_index)
+            .or(matches.last())
+        {
+            self.selected_index = *index;
         }
     }
 
@@ -146,32 +689,20 @@ impl UiState {
         self.filter_mode || self.search_mode
     }
 
-    /// Handle input in filter or search mode
-    pub fn handle_input(&mut self, key: crossterm::event::KeyEvent) {
-        use crossterm::event::KeyCode;
-
-        let input_str = match key.code {
-            KeyCode::Char(c) => Some(c.to_string()),
-            KeyCode::Backspace => {
-                let input = if self.filter_mode {
-                    &mut self.filter
-                } else {
-                    &mut self.search
-                };
-                if !input.is_empty() {
-                    input.pop();
-                }
-                None
-            }
-            _ => None,
-        };
-
-        if let Some(s) = input_str {
-            if self.filter_mode {
-                self.filter.push_str(&s);
-            } else if self.search_mode {
-                self.search.push_str(&s);
+    /// Handle a key in filter or search mode, returning whether it edited the text
+    ///
+    /// The list is filtered again as the filter text changes.
+    pub fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        if self.filter_mode {
+            let handled = self.filter.handle_key(key);
+            if handled {
+                self.apply_filter();
             }
+            handled
+        } else if self.search_mode {
+            self.search.handle_key(key)
+        } else {
+            false
         }
     }
 
@@ -179,16 +710,757 @@ impl UiState {
     pub fn show_details(&mut self) {
         if !self.instances.is_empty() {
             self.show_details = true;
+            self.interfaces_scroll = 0;
         }
     }
 
-    /// Close any open popup
+    /// Scroll the network interfaces of the details popup up by one
+    pub fn scroll_interfaces_up(&mut self) {
+        self.interfaces_scroll = self.interfaces_scroll.saturating_sub(1);
+    }
+
+    /// Scroll the network interfaces of the details popup down by one
+    pub fn scroll_interfaces_down(&mut self) {
+        let count = self
+            .selected_instance()
+            .map_or(0, |instance| instance.network_interfaces.len());
+        self.interfaces_scroll = (self.interfaces_scroll + 1).min(count.saturating_sub(1));
+    }
+
+    /// Check if the details popup is shown
+    pub fn is_details_open(&self) -> bool {
+        self.show_details
+    }
+
+    /// Toggle revealing sensitive metadata values in the details popup
+    pub fn toggle_reveal_sensitive(&mut self) {
+        if self.show_details {
+            self.reveal_sensitive = !self.reveal_sensitive;
+        }
+    }
+
+    /// Check if a metadata key matches a sensitive pattern
+    fn is_sensitive_key(&self, key: &str) -> bool {
+        let key = key.to_lowercase();
+        self.sensitive_patterns
+            .iter()
+            .any(|pattern| key.contains(pattern.as_str()))
+    }
+
+    /// Mark the instance shown in the details popup as being refreshed
+    pub fn set_details_refreshing(&mut self, refreshing: bool) {
+        self.details_refreshing = refreshing;
+    }
+
+    /// Close any open popup, and unmark the instances marked for a bulk action
     pub fn close_popup(&mut self) {
-        self.show_help = false;
+        self.marked.clear();
+        self.help = None;
         self.show_details = false;
+        self.reveal_sensitive = false;
+        self.interfaces_scroll = 0;
         self.filter_mode = false;
         self.search_mode = false;
+        self.search.clear();
         self.confirmation = None;
+        self.logs = None;
+        self.firewalls = None;
+        self.resize = None;
+        self.tags = None;
+        self.palette = None;
+        self.regions = None;
+        self.accounts = None;
+        if let Some(view) = self.bulk_progress.as_mut() {
+            view.set_open(false);
+        }
+        self.create = None;
+        self.command = None;
+        self.column_picker = None;
+        self.raw_json = None;
+        self.serial_console = None;
+        self.show_notifications = false;
+        self.quit_confirmation = false;
+        self.reauth_prompt = false;
+    }
+
+    /// Check if a popup or prompt covers the instance list
+    pub fn has_popup(&self) -> bool {
+        self.help.is_some()
+            || self.show_details
+            || self.filter_mode
+            || self.search_mode
+            || self.copy_mode
+            || self.confirmation.is_some()
+            || self.logs.is_some()
+            || self.firewalls.is_some()
+            || self.resize.is_some()
+            || self.tags.is_some()
+            || self.palette.is_some()
+            || self.regions.is_some()
+            || self.accounts.is_some()
+            || self.is_bulk_progress_open()
+            || self.create.is_some()
+            || self.command.is_some()
+            || self.column_picker.is_some()
+            || self.raw_json.is_some()
+            || self.serial_console.is_some()
+            || self.show_notifications
+            || self.quit_confirmation
+            || self.save_confirmation.is_some()
+            || self.reauth_prompt
+    }
+
+    /// Wait for the key picking the gcloud command to copy
+    pub fn start_copy_mode(&mut self) {
+        if self.selected_instance().is_some() {
+            self.copy_mode = true;
+        }
+    }
+
+    /// Stop waiting for a gcloud command to copy
+    pub fn end_copy_mode(&mut self) {
+        self.copy_mode = false;
+    }
+
+    /// Check if the next key picks a gcloud command to copy
+    pub fn is_copy_mode(&self) -> bool {
+        self.copy_mode
+    }
+
+    /// Record a notification and show it on the dashboard for a few seconds
+    pub fn notify(&mut self, level: NotificationLevel, message: String) {
+        self.notifications.push(level, message);
+    }
+
+    /// Stop showing the notifications whose time is up on the dashboard
+    ///
+    /// Returns whether any notification was hidden.
+    pub fn expire_toasts(&mut self) -> bool {
+        self.notifications.expire_toasts()
+    }
+
+    /// Toggle the notification history popup
+    pub fn toggle_notifications(&mut self) {
+        self.show_notifications = !self.show_notifications;
+        self.notifications.reset_scroll();
+    }
+
+    /// Check if the notification history popup is shown
+    pub fn is_notifications_open(&self) -> bool {
+        self.show_notifications
+    }
+
+    /// Get the notification history
+    pub fn notifications_mut(&mut self) -> &mut NotificationHistory {
+        &mut self.notifications
+    }
+
+    /// Set the animation frame used by animated widgets
+    pub fn set_animation_frame(&mut self, frame: usize) {
+        self.animation_frame = frame;
+    }
+
+    /// Check if a spinner is shown, so frames must be drawn even without changes
+    pub fn is_animating(&self) -> bool {
+        (self.loading == LoadingState::Loading && self.instances.is_empty())
+            || !self.actions_in_flight.is_empty()
+            || self
+                .instances
+                .iter()
+                .any(|instance| dashboard::is_transitional(&instance.status))
+    }
+
+    /// Set the progress of a manual refresh, `None` once it is done
+    pub fn set_refresh_progress(&mut self, progress: Option<String>) {
+        self.refresh_progress = progress;
+    }
+
+    /// Update the progress of the first instance listing
+    pub fn set_loading_state(&mut self, loading: LoadingState) {
+        self.loading = loading;
+    }
+
+    /// Check if instances were listed at least once
+    pub fn is_loaded(&self) -> bool {
+        self.loading == LoadingState::Loaded
+    }
+
+    /// Update the instances with an action still running, shown with a spinner
+    pub fn set_actions_in_flight(&mut self, instance_ids: HashSet<String>) {
+        self.actions_in_flight = instance_ids;
+    }
+
+    /// Update the refresh interval shown in the status bar
+    pub fn set_refresh_interval(&mut self, seconds: u64) {
+        self.refresh_interval = seconds;
+    }
+
+    /// Ask the user to confirm an action on the selected instance
+    pub fn request_confirmation(&mut self, action: Action, instance_id: String) {
+        self.confirm(action, vec![instance_id], false);
+    }
+
+    /// Ask the user to confirm an action on every marked instance
+    pub fn request_bulk_confirmation(&mut self, action: Action) {
+        let instance_ids = self.marked_instance_ids();
+        self.confirm(action, instance_ids, true);
+    }
+
+    /// Open the confirmation of an action on the given instances
+    fn confirm(&mut self, action: Action, instance_ids: Vec<String>, bulk: bool) {
+        let instance_names = instance_ids
+            .iter()
+            .map(|id| self.instance_name(id).unwrap_or(id).to_string())
+            .collect();
+        self.confirmation = Some(Confirmation {
+            action,
+            instance_ids,
+            instance_names,
+            bulk,
+        });
+    }
+
+    /// Close the action confirmation, returning the confirmed action
+    pub fn take_confirmation(&mut self) -> Option<Confirmation> {
+        self.confirmation.take()
+    }
+
+    /// Ask the user to confirm quitting while actions are in flight
+    pub fn request_quit_confirmation(&mut self) {
+        self.quit_confirmation = true;
+    }
+
+    /// Dismiss the quit confirmation
+    pub fn cancel_quit_confirmation(&mut self) {
+        self.quit_confirmation = false;
+    }
+
+    /// Check if the quit confirmation is shown
+    pub fn is_quit_confirmation_open(&self) -> bool {
+        self.quit_confirmation
+    }
+
+    /// Offer to log in again, once the credentials expired
+    pub fn request_reauth(&mut self) {
+        self.reauth_prompt = true;
+    }
+
+    /// Close the login popup
+    pub fn cancel_reauth(&mut self) {
+        self.reauth_prompt = false;
+    }
+
+    /// Check if the login popup is open
+    pub fn is_reauth_open(&self) -> bool {
+        self.reauth_prompt
+    }
+
+    /// Ask the user to save the preferences changed during the session before quitting
+    pub fn request_save_confirmation(&mut self, settings: Vec<String>) {
+        self.save_confirmation = Some(settings);
+    }
+
+    /// Dismiss the save confirmation
+    pub fn cancel_save_confirmation(&mut self) {
+        self.save_confirmation = None;
+    }
+
+    /// Check if the save confirmation is shown
+    pub fn is_save_confirmation_open(&self) -> bool {
+        self.save_confirmation.is_some()
+    }
+
+    /// Open the logs popup for the selected instance
+    pub fn open_logs(&mut self, limit: usize) {
+        if let Some(instance) = self.selected_instance() {
+            self.logs = Some(LogsView::new(
+                instance.id.clone(),
+                instance.name.clone(),
+                limit,
+            ));
+        }
+    }
+
+    /// Close the logs popup, going back to the previous view
+    pub fn close_logs(&mut self) {
+        self.logs = None;
+    }
+
+    /// Check if the logs popup is open
+    pub fn is_logs_open(&self) -> bool {
+        self.logs.is_some()
+    }
+
+    /// Get the logs popup state, if open
+    pub fn logs_view_mut(&mut self) -> Option<&mut LogsView> {
+        self.logs.as_mut()
+    }
+
+    /// Open the firewall rules popup for the selected instance
+    pub fn open_firewalls(&mut self) {
+        if let Some(instance) = self.selected_instance() {
+            self.firewalls = Some(FirewallsView::new(
+                instance.name.clone(),
+                instance.zone.clone(),
+            ));
+        }
+    }
+
+    /// Close the firewall rules popup, going back to the previous view
+    pub fn close_firewalls(&mut self) {
+        self.firewalls = None;
+    }
+
+    /// Check if the firewall rules popup is open
+    pub fn is_firewalls_open(&self) -> bool {
+        self.firewalls.is_some()
+    }
+
+    /// Get the firewall rules popup state, if open
+    pub fn firewalls_view_mut(&mut self) -> Option<&mut FirewallsView> {
+        self.firewalls.as_mut()
+    }
+
+    /// Open the serial console popup for the selected instance
+    pub fn open_serial_console(&mut self) {
+        if let Some(instance) = self.selected_instance() {
+            self.serial_console = Some(SerialConsoleView::new(
+                instance.name.clone(),
+                instance.zone.clone(),
+            ));
+        }
+    }
+
+    /// Close the serial console popup, going back to the previous view
+    pub fn close_serial_console(&mut self) {
+        self.serial_console = None;
+    }
+
+    /// Check if the serial console popup is open
+    pub fn is_serial_console_open(&self) -> bool {
+        self.serial_console.is_some()
+    }
+
+    /// Get the serial console popup state, if open
+    pub fn serial_console_view_mut(&mut self) -> Option<&mut SerialConsoleView> {
+        self.serial_console.as_mut()
+    }
+
+    /// Open the raw describe popup for the selected instance
+    pub fn open_raw_json(&mut self) {
+        if let Some(instance) = self.selected_instance() {
+            self.raw_json = Some(RawJsonView::new(instance.name.clone()));
+        }
+    }
+
+    /// Show the raw describe JSON, hiding sensitive metadata values unless revealed
+    pub fn set_raw_json(&mut self, json: &str) {
+        let json = if self.reveal_sensitive {
+            json.to_string()
+        } else {
+            self.redact_metadata(json)
+        };
+        if let Some(view) = &mut self.raw_json {
+            view.set_json(&json);
+        }
+    }
+
+    /// Hide the values of sensitive metadata items in describe JSON
+    fn redact_metadata(&self, json: &str) -> String {
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(json) else {
+            return json.to_string();
+        };
+
+        if let Some(items) = value
+            .pointer_mut("/metadata/items")
+            .and_then(serde_json::Value::as_array_mut)
+        {
+            for item in items {
+                let sensitive = item
+                    .get("key")
+                    .and_then(serde_json::Value::as_str)
+                    .is_some_and(|key| self.is_sensitive_key(key));
+                if sensitive {
+                    item["value"] = serde_json::Value::from(raw_json::REDACTED);
+                }
+            }
+        }
+
+        serde_json::to_string_pretty(&value).unwrap_or_else(|_| json.to_string())
+    }
+
+    /// Close the raw describe popup, going back to the instance details
+    pub fn close_raw_json(&mut self) {
+        self.raw_json = None;
+    }
+
+    /// Check if the raw describe popup is open
+    pub fn is_raw_json_open(&self) -> bool {
+        self.raw_json.is_some()
+    }
+
+    /// Get the raw describe popup state, if open
+    pub fn raw_json_view_mut(&mut self) -> Option<&mut RawJsonView> {
+        self.raw_json.as_mut()
+    }
+
+    /// Open the gcloud command popup
+    pub fn open_command(&mut self) {
+        self.command = Some(CommandView::default());
+    }
+
+    /// Close the gcloud command popup
+    pub fn close_command(&mut self) {
+        self.command = None;
+    }
+
+    /// Check if the gcloud command popup is open
+    pub fn is_command_open(&self) -> bool {
+        self.command.is_some()
+    }
+
+    /// Get the gcloud command popup state
+    pub fn command_view_mut(&mut self) -> Option<&mut CommandView> {
+        self.command.as_mut()
+    }
+
+    /// Open the machine type change popup for the selected instance
+    pub fn open_resize(&mut self) {
+        if let Some(instance) = self.selected_instance() {
+            self.resize = Some(ResizeView::new(instance));
+        }
+    }
+
+    /// Close the machine type change popup, going back to the previous view
+    pub fn close_resize(&mut self) {
+        self.resize = None;
+    }
+
+    /// Check if the machine type change popup is open
+    pub fn is_resize_open(&self) -> bool {
+        self.resize.is_some()
+    }
+
+    /// Get the machine type change popup state, if open
+    pub fn resize_view_mut(&mut self) -> Option<&mut ResizeView> {
+        self.resize.as_mut()
+    }
+
+    /// Open the network tags popup for the selected instance
+    pub fn open_tags(&mut self) {
+        if let Some(instance) = self.selected_instance() {
+            self.tags = Some(TagsView::new(instance));
+        }
+    }
+
+    /// Close the network tags popup, going back to the previous view
+    pub fn close_tags(&mut self) {
+        self.tags = None;
+    }
+
+    /// Check if the network tags popup is open
+    pub fn is_tags_open(&self) -> bool {
+        self.tags.is_some()
+    }
+
+    /// Get the network tags popup state, if open
+    pub fn tags_view_mut(&mut self) -> Option<&mut TagsView> {
+        self.tags.as_mut()
+    }
+
+    /// Open the quick-jump instance picker over all instances
+    pub fn open_palette(&mut self) {
+        self.palette = Some(PaletteView::new(&self.all_instances));
+    }
+
+    /// Close the quick-jump instance picker
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+    }
+
+    /// Check if the quick-jump instance picker is open
+    pub fn is_palette_open(&self) -> bool {
+        self.palette.is_some()
+    }
+
+    /// Get the quick-jump instance picker state, if open
+    pub fn palette_view_mut(&mut self) -> Option<&mut PaletteView> {
+        self.palette.as_mut()
+    }
+
+    /// Get the name of an instance by ID
+    pub fn instance_name(&self, instance_id: &str) -> Option<&str> {
+        self.instance(instance_id)
+            .map(|instance| instance.name.as_str())
+    }
+
+    /// Get the ID of an instance by name and zone, whatever is shown
+    pub fn instance_id_by_name(&self, name: &str, zone: &str) -> Option<String> {
+        self.all_instances
+            .iter()
+            .find(|instance| instance.name == name && instance.zone == zone)
+            .map(|instance| instance.id.clone())
+    }
+
+    /// Drop the instances of the previous listing on the next refresh, e.g. when switching region
+    pub fn forget_vanished_instances(&mut self) {
+        self.vanished = self
+            .all_instances
+            .iter()
+            .map(|instance| instance.id.clone())
+            .collect();
+    }
+
+    /// Count all instances by status, whatever is shown, sorted by status name
+    pub fn status_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for instance in &self.all_instances {
+            *counts.entry(instance.status.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Get the shown instances, filtered and sorted
+    pub fn shown_instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    /// Format the shown instances, filtered and sorted, as a Markdown table of the visible columns
+    pub fn markdown_table(&self) -> String {
+        let mut table = Vec::new();
+        // Writing to a Vec can't fail
+        let _ = write_markdown_table(&mut table, &self.instances, &self.columns);
+        String::from_utf8_lossy(&table).into_owned()
+    }
+
+    /// Check if an instance was missing from the last listing
+    pub fn is_vanished(&self, instance_id: &str) -> bool {
+        self.vanished.contains(instance_id)
+    }
+
+    /// Get an instance by ID, even if hidden by the filters
+    pub fn instance(&self, instance_id: &str) -> Option<&Instance> {
+        self.all_instances
+            .iter()
+            .find(|instance| instance.id == instance_id)
+    }
+
+    /// Open the region switcher
+    pub fn open_regions(&mut self, view: RegionsView) {
+        self.regions = Some(view);
+    }
+
+    /// Close the region switcher
+    pub fn close_regions(&mut self) {
+        self.regions = None;
+    }
+
+    /// Check if the region switcher is open
+    pub fn is_regions_open(&self) -> bool {
+        self.regions.is_some()
+    }
+
+    /// Get the region switcher state, if open
+    pub fn regions_view_mut(&mut self) -> Option<&mut RegionsView> {
+        self.regions.as_mut()
+    }
+
+    /// Open the account switcher
+    pub fn open_accounts(&mut self, view: AccountsView) {
+        self.accounts = Some(view);
+    }
+
+    /// Close the account switcher
+    pub fn close_accounts(&mut self) {
+        self.accounts = None;
+    }
+
+    /// Check if the account switcher is open
+    pub fn is_accounts_open(&self) -> bool {
+        self.accounts.is_some()
+    }
+
+    /// Get the account switcher state, if open
+    pub fn accounts_view_mut(&mut self) -> Option<&mut AccountsView> {
+        self.accounts.as_mut()
+    }
+
+    /// Show the progress of a new bulk action on instances given by ID, all queued
+    pub fn start_bulk_progress(&mut self, description: String, instance_ids: &[String]) {
+        let instances = instance_ids
+            .iter()
+            .map(|id| {
+                let name = self.instance_name(id).unwrap_or(id).to_string();
+                (id.clone(), name)
+            })
+            .collect();
+        self.bulk_progress = Some(BulkProgressView::new(description, instances));
+    }
+
+    /// Update the state of an instance of the bulk action
+    pub fn set_bulk_state(&mut self, instance_id: &str, state: BulkItemState) {
+        if let Some(view) = self.bulk_progress.as_mut() {
+            view.set_state(instance_id, state);
+        }
+    }
+
+    /// Show the progress of the last bulk action again, returning false if there is none
+    pub fn open_bulk_progress(&mut self) -> bool {
+        match self.bulk_progress.as_mut() {
+            Some(view) => {
+                view.set_open(true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Hide the bulk action progress, which keeps running
+    pub fn close_bulk_progress(&mut self) {
+        if let Some(view) = self.bulk_progress.as_mut() {
+            view.set_open(false);
+        }
+    }
+
+    /// Check if the bulk action progress is shown
+    pub fn is_bulk_progress_open(&self) -> bool {
+        self.bulk_progress
+            .as_ref()
+            .is_some_and(|view| view.is_open())
+    }
+
+    /// Get the bulk action progress state, if any
+    pub fn bulk_progress_mut(&mut self) -> Option<&mut BulkProgressView> {
+        self.bulk_progress.as_mut()
+    }
+
+    /// Open the instance creation form
+    pub fn open_create(&mut self, view: CreateView) {
+        self.create = Some(view);
+    }
+
+    /// Close the instance creation form
+    pub fn close_create(&mut self) {
+        self.create = None;
+    }
+
+    /// Check if the instance creation form is open
+    pub fn is_create_open(&self) -> bool {
+        self.create.is_some()
+    }
+
+    /// Get the instance creation form state, if open
+    pub fn create_view_mut(&mut self) -> Option<&mut CreateView> {
+        self.create.as_mut()
+    }
+
+    /// Select an instance by ID, clearing the filters if they hide it
+    pub fn jump_to_instance(&mut self, instance_id: &str) {
+        if !self
+            .instances
+            .iter()
+            .any(|instance| instance.id == instance_id)
+        {
+            self.filter.clear();
+            self.filter_mode = false;
+            self.only_mine = false;
+            self.only_public = false;
+            self.status_filter = None;
+            self.apply_filter();
+        }
+
+        if let Some(index) = self
+            .instances
+            .iter()
+            .position(|instance| instance.id == instance_id)
+        {
+            self.selected_index = index;
+        }
+    }
+
+    /// Select the first item in the list
+    pub fn select_first(&mut self) {
+        self.selected_index = 0;
+    }
+
+    /// Select the last item in the list
+    pub fn select_last(&mut self) {
+        self.selected_index = self.instances.len().saturating_sub(1);
+    }
+
+    /// Mark the selected instance for a bulk action, or unmark it
+    pub fn toggle_marked(&mut self) {
+        let Some(instance_id) = self.selected_instance_id() else {
+            return;
+        };
+
+        if !self.marked.remove(&instance_id) {
+            self.marked.insert(instance_id);
+        }
+    }
+
+    /// Check if any instance is marked for a bulk action
+    pub fn has_marked(&self) -> bool {
+        !self.marked.is_empty()
+    }
+
+    /// Get the IDs of the marked instances still listed, in list order
+    pub fn marked_instance_ids(&self) -> Vec<String> {
+        self.all_instances
+            .iter()
+            .filter(|instance| self.marked.contains(&instance.id))
+            .map(|instance| instance.id.clone())
+            .collect()
+    }
+
+    /// Unmark every instance
+    pub fn clear_marked(&mut self) {
+        self.marked.clear();
+    }
+
+    /// Move the selection one page up, a page being the height of the list at the last draw
+    pub fn page_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(self.page_size());
+    }
+
+    /// Move the selection one page down, stopping at the last item
+    pub fn page_down(&mut self) {
+        if !self.instances.is_empty() {
+            self.selected_index =
+                (self.selected_index + self.page_size()).min(self.instances.len() - 1);
+        }
+    }
+
+    /// Select the instance clicked in the list, opening its details if it was already selected
+    ///
+    /// Returns whether an instance row was clicked.
+    pub fn click_instance(&mut self, column: u16, row: u16) -> bool {
+        let area = self.list_area.get();
+        if column < area.x || column >= area.right() || row < area.y || row >= area.bottom() {
+            return false;
+        }
+
+        let index = self.list_offset.get() + usize::from(row - area.y);
+        if index >= self.instances.len() {
+            return false;
+        }
+        if index == self.selected_index {
+            self.show_details();
+        } else {
+            self.selected_index = index;
+        }
+        true
+    }
+
+    /// Shrink the list height kept for paging to a resized terminal, until the next draw measures it
+    pub fn resize_terminal(&mut self, height: u16) {
+        self.list_height
+            .set(self.list_height.get().min(height as usize));
+    }
+
+    /// Get the number of items a page moves the selection by
+    fn page_size(&self) -> usize {
+        self.list_height.get().max(1)
     }
 
     /// Navigate to previous item in the list
@@ -223,36 +1495,97 @@ impl UiState {
 
     /// Reset selection to the first item if possible
     pub fn reset_selection(&mut self) {
-        self.selected_index = if self.instances.is_empty() { 0 } else { 0 };
+        self.selected_index = 0;
+    }
+
+    /// Get the currently selected instance
+    pub fn selected_instance(&self) -> Option<&Instance> {
+        self.instances.get(self.selected_index)
     }
 
     /// Get the ID of the currently selected instance
     pub fn selected_instance_id(&self) -> Option<String> {
-        if self.instances.is_empty() {
-            None
-        } else {
-            Some(self.instances[self.selected_index].id.clone())
-        }
+        self.selected_instance().map(|instance| instance.id.clone())
     }
 }
 
-/// Setup the terminal for TUI
-pub fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+/// Setup the terminal for TUI, capturing the mouse if enabled
+pub fn setup_terminal(mouse: bool) -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    enter_terminal(mouse)?;
+    let backend = CrosstermBackend::new(io::stdout());
+    Terminal::new(backend)
+}
+
+/// Enable raw mode and enter the alternate screen, capturing the mouse if enabled
+///
+/// Errors are no longer printed on stderr until the terminal is restored.
+fn enter_terminal(mouse: bool) -> io::Result<()> {
+    logging::set_console_enabled(false);
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    Terminal::new(backend)
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    Ok(())
 }
 
 /// Restore terminal settings
+///
+/// Disabling mouse capture is harmless when it was never enabled, so this works
+/// whether or not the mouse was captured.
 pub fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    logging::set_console_enabled(true);
     Ok(())
 }
 
+/// Suspend the process to the shell (Ctrl-Z), restoring the terminal first
+///
+/// Raw mode disables the terminal's own SIGTSTP handling, so the key is caught
+/// by the application instead. The terminal is restored, the process stops
+/// itself, and raw mode and the alternate screen are re-entered on SIGCONT.
+#[cfg(unix)]
+pub fn suspend_terminal(mouse: bool) -> io::Result<()> {
+    restore_terminal()?;
+
+    // SAFETY: raising a signal on the current process has no memory safety requirements
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+
+    // Execution resumes here once the shell sends SIGCONT
+    enter_terminal(mouse)
+}
+
+/// Job control is not available on this platform
+#[cfg(not(unix))]
+pub fn suspend_terminal(_mouse: bool) -> io::Result<()> {
+    Ok(())
+}
+
+/// Run a task with the terminal restored, e.g. an interactive command
+///
+/// Raw mode and the alternate screen are re-entered afterwards, even if the
+/// task failed, so the caller must clear the terminal to repaint it.
+pub async fn run_outside_terminal<T>(mouse: bool, task: impl Future<Output = T>) -> io::Result<T> {
+    restore_terminal()?;
+    let result = task.await;
+    enter_terminal(mouse)?;
+    Ok(result)
+}
+
+/// Render the guidance screen shown when no project is set
+pub fn render_no_project<B: Backend>(frame: &mut ratatui::Frame<B>, emoji: bool, color: bool) {
+    let size = frame.size();
+    no_project::render(frame, emoji, size);
+    if !color {
+        frame.render_widget(PlainColors, size);
+    }
+}
+
 /// Main render function that delegates to the appropriate view
 pub fn render<B: Backend>(frame: &mut ratatui::Frame<B>, state: &UiState) {
     let size = frame.size();
@@ -261,10 +1594,94 @@ pub fn render<B: Backend>(frame: &mut ratatui::Frame<B>, state: &UiState) {
     dashboard::render(frame, state, size);
 
     // Render popups if needed
-    if state.show_help {
-        help::render(frame, size);
-    } else if state.show_details && !state.instances.is_empty() {
-        let instance = &state.instances[state.selected_index];
-        instance_details::render(frame, instance, size);
+    if let Some(view) = &state.help {
+        help::render(frame, view, size);
+    } else if state.show_details {
+        if let Some(instance) = state.selected_instance() {
+            instance_details::render(frame, state, instance, size);
+        }
+    }
+
+    // The raw describe, logs, firewall and serial console popups are opened on top of the other views
+    if let Some(view) = &state.raw_json {
+        raw_json::render(frame, view, size);
+    }
+    if let Some(view) = &state.command {
+        command::render(frame, view, size);
+    }
+    if let Some(view) = &state.logs {
+        logs::render(frame, view, size);
+    }
+    if let Some(view) = &state.firewalls {
+        firewalls::render(frame, view, size);
+    }
+    if let Some(view) = &state.serial_console {
+        serial_console::render(frame, view, size);
+    }
+    if let Some(view) = &state.resize {
+        resize::render(frame, view, state.emoji, size);
+    }
+    if let Some(view) = &state.tags {
+        tags::render(frame, view, size);
+    }
+    if let Some(view) = &state.palette {
+        palette::render(frame, view, state.emoji, size);
+    }
+    if let Some(view) = &state.regions {
+        regions::render(frame, view, state.emoji, size);
+    }
+    if let Some(view) = &state.accounts {
+        accounts::render(frame, view, state.emoji, size);
+    }
+    if let Some(view) = &state.create {
+        create::render(frame, view, size);
     }
+    if let Some(view) = &state.column_picker {
+        column_picker::render(frame, view, state.emoji, size);
+    }
+    if let Some(view) = state.bulk_progress.as_ref().filter(|view| view.is_open()) {
+        bulk_progress::render(frame, view, state.emoji, size);
+    }
+    if state.show_notifications {
+        notifications::render(frame, &state.notifications, state.emoji, size);
+    }
+
+    if let Some(confirmation) = &state.confirmation {
+        confirm::render(frame, &confirmation.message(), size);
+    }
+
+    // The quit confirmations always come last
+    if state.quit_confirmation {
+        confirm::render(frame, "Action in progress — quit anyway?", size);
+    } else if let Some(settings) = &state.save_confirmation {
+        confirm::render_save(frame, settings, size);
+    } else if state.reauth_prompt {
+        confirm::render_reauth(frame, size);
+    }
+
+    // Without colors, reset those of every view, including popups with fixed colors
+    if state.theme.is_plain() {
+        frame.render_widget(PlainColors, size);
+    }
+}
+
+/// Helper function to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
 }
@@ -0,0 +1,62 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::icons::Icon;
+
+/// Render the full-screen guidance shown when no Google Cloud project is set
+pub fn render<B: Backend>(frame: &mut Frame<B>, emoji: bool, area: Rect) {
+    let block = Block::default()
+        .title("g1c - No project set")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let command = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{}No Google Cloud project is set",
+                Icon::Warning.prefix(emoji)
+            ),
+            bold.fg(Color::Yellow),
+        )),
+        Line::from(""),
+        Line::from("g1c needs a project to list instances. Either set a default project:"),
+        Line::from(""),
+        Line::from(Span::styled(
+            "gcloud config set project PROJECT_ID",
+            command,
+        )),
+        Line::from(""),
+        Line::from("or pass one on the command line:"),
+        Line::from(""),
+        Line::from(Span::styled("g1c --project PROJECT_ID", command)),
+        Line::from(""),
+        Line::from("It can also be set with project = \"PROJECT_ID\" in the configuration file."),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled("r", bold),
+            Span::raw(" to retry once the project is set, "),
+            Span::styled("q", bold),
+            Span::raw(" to quit"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, area);
+}
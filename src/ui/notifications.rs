@@ -0,0 +1,220 @@
+use chrono::{DateTime, Local};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::icons::Icon;
+
+/// Maximum number of notifications kept in the history
+const MAX_NOTIFICATIONS: usize = 100;
+
+/// Maximum number of notifications shown at once on the dashboard
+pub const MAX_TOASTS: usize = 3;
+
+/// Severity of a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Get the icon shown before the message
+    fn icon(&self) -> Icon {
+        match self {
+            NotificationLevel::Info => Icon::Info,
+            NotificationLevel::Success => Icon::Success,
+            NotificationLevel::Error => Icon::Error,
+        }
+    }
+
+    /// Get how long a notification stays on the dashboard, longer for errors
+    fn toast_duration(&self) -> Duration {
+        match self {
+            NotificationLevel::Info | NotificationLevel::Success => Duration::from_secs(5),
+            NotificationLevel::Error => Duration::from_secs(10),
+        }
+    }
+
+    /// Get the color of the message
+    fn color(&self) -> Color {
+        match self {
+            NotificationLevel::Info => Color::Cyan,
+            NotificationLevel::Success => Color::Green,
+            NotificationLevel::Error => Color::Red,
+        }
+    }
+}
+
+/// In-app notification
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// When the notification was raised
+    pub timestamp: DateTime<Local>,
+    /// Severity
+    pub level: NotificationLevel,
+    /// Message
+    pub message: String,
+    /// When the notification stops being shown on the dashboard
+    pub expires_at: Instant,
+}
+
+impl Notification {
+    /// Format the notification as a single line
+    pub fn line(&self, emoji: bool) -> Line<'_> {
+        Line::from(vec![
+            Span::styled(
+                self.timestamp.format("%H:%M:%S").to_string(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(format!(" {}", self.level.icon().prefix(emoji))),
+            Span::styled(
+                self.message.as_str(),
+                Style::default().fg(self.level.color()),
+            ),
+        ])
+    }
+}
+
+/// Bounded history of notifications, most recent last
+#[derive(Default)]
+pub struct NotificationHistory {
+    /// Notifications, oldest first
+    entries: VecDeque<Notification>,
+    /// Notifications still shown on the dashboard, oldest first
+    toasts: VecDeque<Notification>,
+    /// Vertical scroll offset of the history popup
+    scroll: u16,
+}
+
+impl NotificationHistory {
+    /// Record a notification, dropping the oldest one when full, and show it on the dashboard
+    pub fn push(&mut self, level: NotificationLevel, message: String) {
+        let notification = Notification {
+            timestamp: Local::now(),
+            level,
+            message,
+            expires_at: Instant::now() + level.toast_duration(),
+        };
+
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(notification.clone());
+
+        if self.entries.len() == MAX_NOTIFICATIONS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(notification);
+    }
+
+    /// Get the notifications shown on the dashboard, oldest first
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.toasts.iter()
+    }
+
+    /// Stop showing the notifications whose time is up on the dashboard
+    ///
+    /// Returns whether any notification was hidden.
+    pub fn expire_toasts(&mut self) -> bool {
+        let now = Instant::now();
+        let shown = self.toasts.len();
+        self.toasts.retain(|toast| toast.expires_at > now);
+        self.toasts.len() != shown
+    }
+
+    /// Remove all notifications
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.toasts.clear();
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.entries.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+
+    /// Go back to the most recent notifications
+    pub fn reset_scroll(&mut self) {
+        self.scroll = 0;
+    }
+}
+
+/// Render the notification history popup, most recent first
+pub fn render<B: Backend>(
+    frame: &mut Frame<B>,
+    history: &NotificationHistory,
+    emoji: bool,
+    area: Rect,
+) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Notifications ({}/{})",
+            history.entries.len(),
+            MAX_NOTIFICATIONS
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Notifications
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    let lines: Vec<Line> = if history.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No notifications",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        history
+            .entries
+            .iter()
+            .rev()
+            .map(|notification| notification.line(emoji))
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((history.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll, "),
+        Span::styled("c", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to clear"),
+    ]));
+    frame.render_widget(status_line, chunks[1]);
+}
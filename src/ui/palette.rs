@@ -0,0 +1,151 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::fuzzy::fuzzy_score;
+use crate::cloud::Instance;
+
+/// State of the quick-jump instance picker
+pub struct PaletteView {
+    /// Text typed by the user
+    query: String,
+    /// Instance IDs and names over the full list
+    candidates: Vec<(String, String)>,
+    /// Indexes of the candidates matching the query, best first
+    matches: Vec<usize>,
+    /// Selected match
+    selected: usize,
+}
+
+impl PaletteView {
+    /// Create a new picker over all instances
+    pub fn new(instances: &[Instance]) -> Self {
+        let mut view = Self {
+            query: String::new(),
+            candidates: instances
+                .iter()
+                .map(|instance| (instance.id.clone(), instance.name.clone()))
+                .collect(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        view.update_matches();
+        view
+    }
+
+    /// Append a character to the query
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.update_matches();
+    }
+
+    /// Remove the last character of the query
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.update_matches();
+    }
+
+    /// Select the previous match
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next match
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Get the ID of the selected instance
+    pub fn selected_instance_id(&self) -> Option<&str> {
+        self.matches
+            .get(self.selected)
+            .map(|&index| self.candidates[index].0.as_str())
+    }
+
+    /// Rank the candidates against the query
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, name))| {
+                fuzzy_score(&self.query, name).map(|score| (score, index))
+            })
+            .collect();
+
+        // Best score first, then by name for a stable order
+        scored.sort_by(|(score_a, a), (score_b, b)| {
+            score_b
+                .cmp(score_a)
+                .then_with(|| self.candidates[*a].1.cmp(&self.candidates[*b].1))
+        });
+
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+}
+
+/// Render the quick-jump instance picker
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &PaletteView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(50, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Jump to instance ({}/{})",
+            view.matches.len(),
+            view.candidates.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Min(1),    // Matches
+        ])
+        .split(inner_area);
+
+    let query = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            format!("{}▏", view.query),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+    frame.render_widget(query, chunks[0]);
+
+    let items: Vec<ListItem> = view
+        .matches
+        .iter()
+        .map(|&index| ListItem::new(view.candidates[index].1.as_str()))
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(super::icons::highlight_symbol(emoji))
+        .style(Style::default().fg(Color::White));
+
+    let mut list_state = ListState::default();
+    if !view.matches.is_empty() {
+        list_state.select(Some(view.selected));
+    }
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+}
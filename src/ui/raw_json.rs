@@ -0,0 +1,138 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Value shown instead of sensitive metadata values
+pub const REDACTED: &str = "•••••••• (hidden, press v in details to reveal)";
+
+/// State of the raw describe popup, showing the full gcloud JSON of an instance
+pub struct RawJsonView {
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Pretty-printed JSON lines
+    lines: Vec<String>,
+    /// Error message if the instance could not be described
+    error: Option<String>,
+    /// Vertical scroll offset
+    scroll: u16,
+}
+
+impl RawJsonView {
+    /// Create a new raw describe view for an instance, waiting for its JSON
+    pub fn new(instance_name: String) -> Self {
+        Self {
+            instance_name,
+            lines: Vec::new(),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Replace the displayed JSON
+    pub fn set_json(&mut self, json: &str) {
+        self.lines = json.lines().map(str::to_string).collect();
+        self.error = None;
+        self.scroll = 0;
+    }
+
+    /// Display an error instead of the JSON
+    pub fn set_error(&mut self, error: String) {
+        self.lines.clear();
+        self.error = Some(error);
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.lines.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the raw describe popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &RawJsonView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Raw describe: {}", view.instance_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // JSON
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    // Render the JSON, or the error / loading message
+    let lines: Vec<Line> = if let Some(error) = &view.error {
+        vec![Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ))]
+    } else if view.lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "Loading...",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        view.lines.iter().map(|line| json_line(line)).collect()
+    };
+
+    let paragraph = Paragraph::new(lines).scroll((view.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to scroll"),
+    ]));
+    frame.render_widget(status_line, chunks[1]);
+}
+
+/// Color a pretty-printed JSON line: keys, then string or other scalar values
+fn json_line(line: &str) -> Line<'_> {
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, content) = line.split_at(indent_len);
+
+    // Pretty-printed keys are always a quoted string followed by ": "
+    let (key, value) = match content.find("\": ") {
+        Some(index) if content.starts_with('"') => content.split_at(index + 3),
+        _ => ("", content),
+    };
+
+    let value_color = if value.starts_with('"') {
+        Color::Green
+    } else if value.starts_with(['{', '}', '[', ']']) {
+        Color::White
+    } else {
+        Color::Yellow
+    };
+
+    Line::from(vec![
+        Span::raw(indent),
+        Span::styled(key, Style::default().fg(Color::Cyan)),
+        Span::styled(value, Style::default().fg(value_color)),
+    ])
+}
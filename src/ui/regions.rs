@@ -0,0 +1,196 @@
+use ratatui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+/// Label of the entry listing instances in all regions
+const ALL_REGIONS: &str = "All regions";
+
+/// Scope picked in the region switcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionScope {
+    /// Instances of every region
+    All,
+    /// Instances of a region
+    Region(String),
+    /// Instances of a zone
+    Zone(String),
+}
+
+impl RegionScope {
+    /// Get the text of the scope entry, zones indented under their region
+    fn label(&self) -> String {
+        match self {
+            Self::All => ALL_REGIONS.to_string(),
+            Self::Region(region) => region.clone(),
+            Self::Zone(zone) => format!("  {}", zone),
+        }
+    }
+}
+
+/// State of the region switcher popup
+pub struct RegionsView {
+    /// Available regions
+    regions: Vec<String>,
+    /// Available zones, listed under their region
+    zones: Vec<String>,
+    /// Region whose zones are listed, unless all zones are
+    current_region: Option<String>,
+    /// Whether the zones of every region are listed
+    all_zones: bool,
+    /// Entries of the list, all regions first
+    entries: Vec<RegionScope>,
+    /// Selected entry
+    selected: usize,
+    /// Error message if the regions could not be listed
+    error: Option<String>,
+}
+
+impl RegionsView {
+    /// Create a region switcher, selecting the current scope
+    ///
+    /// Only the zones of the current region are listed until all zones are shown.
+    pub fn new(regions: Vec<String>, zones: Vec<String>, current: RegionScope) -> Self {
+        let current_region = match &current {
+            RegionScope::All => None,
+            RegionScope::Region(region) => Some(region.clone()),
+            RegionScope::Zone(zone) => zone_region(zone).map(str::to_string),
+        };
+
+        let mut view = Self {
+            regions,
+            zones,
+            current_region,
+            all_zones: false,
+            entries: Vec::new(),
+            selected: 0,
+            error: None,
+        };
+        view.build_entries();
+        view.select(&current);
+        view
+    }
+
+    /// Create a region switcher showing why regions could not be listed
+    pub fn with_error(error: String) -> Self {
+        Self {
+            regions: Vec::new(),
+            zones: Vec::new(),
+            current_region: None,
+            all_zones: false,
+            entries: Vec::new(),
+            selected: 0,
+            error: Some(error),
+        }
+    }
+
+    /// List the regions, each followed by its zones when they are shown
+    fn build_entries(&mut self) {
+        self.entries = vec![RegionScope::All];
+        for region in &self.regions {
+            self.entries.push(RegionScope::Region(region.clone()));
+            if self.all_zones || self.current_region.as_ref() == Some(region) {
+                self.entries.extend(
+                    self.zones
+                        .iter()
+                        .filter(|zone| zone_region(zone) == Some(region.as_str()))
+                        .map(|zone| RegionScope::Zone(zone.clone())),
+                );
+            }
+        }
+    }
+
+    /// Select an entry, or the first one if not listed
+    fn select(&mut self, scope: &RegionScope) {
+        self.selected = self
+            .entries
+            .iter()
+            .position(|entry| entry == scope)
+            .unwrap_or(0);
+    }
+
+    /// Toggle listing the zones of every region, rather than of the current one
+    pub fn toggle_all_zones(&mut self) {
+        let selected = self.selected_scope();
+        self.all_zones = !self.all_zones;
+        self.build_entries();
+        self.select(&selected);
+    }
+
+    /// Select the previous entry
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next entry
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Get the selected scope
+    pub fn selected_scope(&self) -> RegionScope {
+        self.entries
+            .get(self.selected)
+            .cloned()
+            .unwrap_or(RegionScope::All)
+    }
+}
+
+/// Get the region of a zone, e.g. us-central1 for us-central1-a
+fn zone_region(zone: &str) -> Option<&str> {
+    zone.rsplit_once('-').map(|(region, _)| region)
+}
+
+/// Render the region switcher popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &RegionsView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(if view.all_zones {
+            "Switch region or zone (Enter to select, a for current region zones, ESC to close)"
+        } else {
+            "Switch region or zone (Enter to select, a for all zones, ESC to close)"
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    if let Some(error) = &view.error {
+        let paragraph = Paragraph::new(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )))
+        .block(block)
+        .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, popup_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = view
+        .entries
+        .iter()
+        .map(|entry| ListItem::new(entry.label()))
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(super::icons::highlight_symbol(emoji))
+        .style(Style::default().fg(Color::White));
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(view.selected));
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+}
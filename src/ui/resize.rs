@@ -0,0 +1,199 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::icons::Icon;
+use crate::cloud::{Instance, MachineSpec};
+
+/// State of the machine type change popup
+pub struct ResizeView {
+    /// Instance ID to resize
+    pub instance_id: String,
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Instance status when the popup was opened
+    pub status: String,
+    /// Current machine type
+    pub current_type: String,
+    /// Target machine type being typed
+    input: String,
+    /// Whether the stop-resize-start sequence awaits confirmation
+    confirming: bool,
+    /// Error explaining why the resize was refused
+    error: Option<String>,
+}
+
+impl ResizeView {
+    /// Create a new resize view for an instance
+    pub fn new(instance: &Instance) -> Self {
+        Self {
+            instance_id: instance.id.clone(),
+            instance_name: instance.name.clone(),
+            status: instance.status.clone(),
+            current_type: instance.machine_type.clone(),
+            input: String::new(),
+            confirming: false,
+            error: None,
+        }
+    }
+
+    /// Get the target machine type
+    pub fn target(&self) -> &str {
+        self.input.trim()
+    }
+
+    /// Get the target machine type, or explain why it cannot be applied
+    pub fn checked_target(&mut self) -> Option<String> {
+        let target = self.target().to_string();
+        if target.is_empty() || target == self.current_type {
+            self.set_error("Enter a machine type different from the current one".to_string());
+            return None;
+        }
+        Some(target)
+    }
+
+    /// Append a character to the target machine type
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+        self.error = None;
+    }
+
+    /// Remove the last character of the target machine type
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+        self.error = None;
+    }
+
+    /// Ask to confirm the stop-resize-start sequence
+    pub fn request_confirmation(&mut self) {
+        self.confirming = true;
+    }
+
+    /// Go back to editing the target machine type
+    pub fn cancel_confirmation(&mut self) {
+        self.confirming = false;
+    }
+
+    /// Check if the stop-resize-start sequence awaits confirmation
+    pub fn is_confirming(&self) -> bool {
+        self.confirming
+    }
+
+    /// Explain why the resize was refused
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+}
+
+/// Render the machine type change popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &ResizeView, emoji: bool, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Change machine type: {}", view.instance_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let target = view.target();
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Current: ", bold),
+            Span::raw(format!(
+                "{} ({})",
+                view.current_type,
+                spec_text(&view.current_type)
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Target:  ", bold),
+            Span::styled(format!("{}▏", target), Style::default().fg(Color::Yellow)),
+            Span::raw(if target.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", spec_text(target))
+            }),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "{}The machine type can only be changed while the instance is TERMINATED.",
+                Icon::Warning.prefix(emoji)
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{}Pricing changes with the machine type.",
+                Icon::Pricing.prefix(emoji)
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+    ];
+
+    if let Some(error) = &view.error {
+        text.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    } else if view.confirming {
+        text.push(Line::from(Span::styled(
+            format!(
+                "Stop {}, change it to {} and start it again?",
+                view.instance_name, target
+            ),
+            bold.fg(Color::White),
+        )));
+        text.push(Line::from(vec![
+            Span::styled("y", bold),
+            Span::raw(" - Yes    "),
+            Span::styled("n/Esc", bold),
+            Span::raw(" - No (default)"),
+        ]));
+    } else {
+        text.push(Line::from(status_hint(&view.status, emoji)));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Describe the vCPU and memory of a machine type
+fn spec_text(machine_type: &str) -> String {
+    MachineSpec::parse(machine_type)
+        .map_or_else(|| "unknown spec".to_string(), |spec| spec.to_string())
+}
+
+/// Explain what Enter does for the instance status
+fn status_hint(status: &str, emoji: bool) -> String {
+    match status {
+        "TERMINATED" => {
+            "Press Enter to change the machine type, Ctrl+y to copy the gcloud command, Esc to cancel"
+                .to_string()
+        }
+        "RUNNING" => format!(
+            "{}The instance is RUNNING and cannot be resized directly. Press Enter to stop it, change the machine type and start it again, or Ctrl+y to copy the gcloud commands",
+            Icon::Stop.prefix(emoji)
+        ),
+        _ => format!(
+            "{}The instance is {}: wait for it to be RUNNING or TERMINATED before resizing",
+            Icon::InProgress.prefix(emoji),
+            status
+        ),
+    }
+}
@@ -0,0 +1,122 @@
+use ratatui::{style::Style, text::Span};
+
+/// Find the byte range of the first case-insensitive match of `query` in `text`
+///
+/// Only ASCII letters are folded, so the range is valid in `text` itself.
+pub fn find(text: &str, query: &str) -> Option<(usize, usize)> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let start = text
+        .to_ascii_lowercase()
+        .find(&query.to_ascii_lowercase())?;
+    Some((start, start + query.len()))
+}
+
+/// Split a cell text into spans, the matches of `query` styled with `match_style`
+pub fn highlight(
+    text: String,
+    query: &str,
+    style: Style,
+    match_style: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = text.as_str();
+
+    while let Some((start, end)) = find(rest, query) {
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), style));
+        }
+        spans.push(Span::styled(rest[start..end].to_string(), match_style));
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() || spans.is_empty() {
+        spans.push(Span::styled(rest.to_string(), style));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud::Instance;
+    use crate::ui::UiState;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use pretty_assertions::assert_eq;
+
+    /// Type a search text in the UI state
+    fn search(state: &mut UiState, text: &str) {
+        state.toggle_search_mode();
+        for c in text.chars() {
+            state.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn state() -> UiState {
+        UiState::with_instances(vec![
+            Instance::for_test("Web-1", "RUNNING", "europe-west1-b", "e2-medium"),
+            Instance::for_test("batch", "TERMINATED", "europe-west1-c", "n2-standard-8"),
+            Instance::for_test("WEB-2", "RUNNING", "us-east1-b", "e2-small"),
+        ])
+    }
+
+    #[test]
+    fn find_is_case_insensitive() {
+        assert_eq!(find("Web-1", "web"), Some((0, 3)));
+        assert_eq!(find("my-WEB", "Web"), Some((3, 6)));
+        assert_eq!(find("batch", "web"), None);
+    }
+
+    #[test]
+    fn find_empty_query_matches_nothing() {
+        assert_eq!(find("Web-1", ""), None);
+        assert_eq!(find("", ""), None);
+    }
+
+    #[test]
+    fn highlight_splits_every_match() {
+        let style = Style::default();
+        let match_style = Style::default().fg(ratatui::style::Color::Yellow);
+        let spans = highlight("web-WEB".to_string(), "web", style, match_style);
+
+        let texts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, vec!["web", "-", "WEB"]);
+        assert_eq!(spans[2].style, match_style);
+    }
+
+    #[test]
+    fn search_matches_ignore_case() {
+        let mut state = state();
+        search(&mut state, "wEb");
+
+        let mut names: Vec<&str> = state
+            .search_matches()
+            .into_iter()
+            .map(|index| state.instances[index].name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["WEB-2", "Web-1"]);
+    }
+
+    #[test]
+    fn search_matches_other_columns() {
+        let mut state = state();
+        search(&mut state, "TERMINATED");
+        assert_eq!(state.search_matches().len(), 1);
+
+        let mut state = self::state();
+        search(&mut state, "E2-");
+        assert_eq!(state.search_matches().len(), 2);
+    }
+
+    #[test]
+    fn empty_search_matches_nothing() {
+        let mut state = state();
+        assert_eq!(state.search_matches(), Vec::<usize>::new());
+
+        search(&mut state, "");
+        assert_eq!(state.search_matches(), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,135 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Message shown when an instance has no serial output to read
+const NO_SERIAL_OUTPUT: &str = "No serial output available";
+
+/// State of the serial console popup, showing the serial port output of an instance
+pub struct SerialConsoleView {
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Zone of the instance
+    pub zone: String,
+    /// Serial port output lines, oldest first
+    lines: Vec<String>,
+    /// Message shown instead of the output, e.g. while loading
+    message: Option<String>,
+    /// Error message if the output could not be read
+    error: Option<String>,
+    /// Vertical scroll offset
+    scroll: u16,
+}
+
+impl SerialConsoleView {
+    /// Create a new serial console view for an instance, waiting for its output
+    pub fn new(instance_name: String, zone: String) -> Self {
+        Self {
+            instance_name,
+            zone,
+            lines: Vec::new(),
+            message: Some("Loading...".to_string()),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Replace the displayed output, empty when the instance has none
+    pub fn set_output(&mut self, output: &str) {
+        self.lines = output.lines().map(str::to_string).collect();
+        self.message = self.lines.is_empty().then(|| NO_SERIAL_OUTPUT.to_string());
+        self.error = None;
+        self.scroll = 0;
+    }
+
+    /// Display an error instead of the output
+    pub fn set_error(&mut self, error: String) {
+        self.lines.clear();
+        self.message = None;
+        self.error = Some(error);
+        self.scroll = 0;
+    }
+
+    /// Scroll up by the given number of lines
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by the given number of lines
+    pub fn scroll_down(&mut self, lines: u16) {
+        let max_scroll = self.lines.len().saturating_sub(1) as u16;
+        self.scroll = self.scroll.saturating_add(lines).min(max_scroll);
+    }
+}
+
+/// Render the serial console popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &SerialConsoleView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(90, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Serial console: {} ({} lines)",
+            view.instance_name,
+            view.lines.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Output
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area);
+
+    // Render the output, or the error / loading message
+    let lines: Vec<Line> = if let Some(error) = &view.error {
+        vec![Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        ))]
+    } else if let Some(message) = &view.message {
+        vec![Line::from(Span::styled(
+            message.as_str(),
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        view.lines
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect()
+    };
+
+    // Long lines are wrapped, so the scroll offset counts wrapped lines
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((view.scroll, 0));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Render status line
+    let status_line = Paragraph::new(Line::from(vec![
+        Span::raw("Press "),
+        Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to close, "),
+        Span::styled(
+            "↑/↓/PgUp/PgDn",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" to scroll, "),
+        Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" to refresh"),
+    ]));
+    frame.render_widget(status_line, chunks[1]);
+}
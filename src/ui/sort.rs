@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::columns::Column;
+use crate::cloud::Instance;
+
+/// Sort keys cycled through in the UI, in order
+const CYCLE: [SortKey; 6] = [
+    SortKey::StatusPriority,
+    SortKey::Name,
+    SortKey::Zone,
+    SortKey::Created,
+    SortKey::ExternalIpFirst,
+    SortKey::Project,
+];
+
+/// Order of the instance list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// Running first, then transitional states, then stopped, each by name
+    #[default]
+    StatusPriority,
+    /// By name
+    Name,
+    /// By zone, then by name
+    Zone,
+    /// Instances with an external IP first, then by status priority
+    ExternalIpFirst,
+    /// Grouped by project, then by status priority
+    Project,
+    /// By creation time, oldest first, then by name
+    Created,
+}
+
+impl SortKey {
+    /// Sort instances in place, reversing the order unless `ascending`
+    ///
+    /// The sort is stable, so instances comparing equal keep their relative
+    /// order across refreshes.
+    pub fn sort(&self, instances: &mut [Instance], ascending: bool) {
+        if ascending {
+            instances.sort_by(|a, b| self.compare(a, b));
+        } else {
+            instances.sort_by(|a, b| self.compare(b, a));
+        }
+    }
+
+    /// Get the next sort key, wrapping around
+    pub fn next(&self) -> SortKey {
+        let index = CYCLE.iter().position(|key| key == self).unwrap_or(0);
+        CYCLE[(index + 1) % CYCLE.len()]
+    }
+
+    /// Get the column holding the sorted value, if any
+    pub fn column(&self) -> Option<Column> {
+        match self {
+            SortKey::StatusPriority => Some(Column::Status),
+            SortKey::Name => Some(Column::Name),
+            SortKey::Zone => Some(Column::Zone),
+            SortKey::ExternalIpFirst => Some(Column::ExternalIp),
+            SortKey::Project => Some(Column::Project),
+            SortKey::Created => None,
+        }
+    }
+
+    /// Get a short description, for the instance list title
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::StatusPriority => "status",
+            SortKey::Name => "name",
+            SortKey::Zone => "zone",
+            SortKey::ExternalIpFirst => "external IP first",
+            SortKey::Project => "project",
+            SortKey::Created => "creation time",
+        }
+    }
+
+    /// Get the name of the key in the configuration
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            SortKey::StatusPriority => "status_priority",
+            SortKey::Name => "name",
+            SortKey::Zone => "zone",
+            SortKey::ExternalIpFirst => "external_ip_first",
+            SortKey::Project => "project",
+            SortKey::Created => "created",
+        }
+    }
+
+    /// Compare two instances
+    fn compare(&self, a: &Instance, b: &Instance) -> Ordering {
+        let primary = match self {
+            SortKey::StatusPriority => status_priority(&a.status).cmp(&status_priority(&b.status)),
+            SortKey::Name => Ordering::Equal,
+            SortKey::Zone => a.zone.cmp(&b.zone),
+            SortKey::Project => a
+                .project
+                .cmp(&b.project)
+                .then_with(|| status_priority(&a.status).cmp(&status_priority(&b.status))),
+            SortKey::ExternalIpFirst => b
+                .has_external_ip()
+                .cmp(&a.has_external_ip())
+                .then_with(|| status_priority(&a.status).cmp(&status_priority(&b.status))),
+            // RFC 3339 timestamps in the same offset sort chronologically, unknown first
+            SortKey::Created => a.creation_timestamp.cmp(&b.creation_timestamp),
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    }
+}
+
+/// Get the sort group of an instance status: running, transitional, then stopped
+fn status_priority(status: &str) -> u8 {
+    match status {
+        "RUNNING" => 0,
+        "TERMINATED" | "STOPPED" | "SUSPENDED" => 2,
+        // PROVISIONING, STAGING, STOPPING, SUSPENDING, REPAIRING...
+        _ => 1,
+    }
+}
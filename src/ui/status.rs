@@ -0,0 +1,158 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::warn;
+
+/// Status whose style is used for statuses without their own
+const UNKNOWN_STATUS: &str = "UNKNOWN";
+
+/// Default status styles: status, color, emoji, ASCII marker and overview bucket
+const DEFAULT_STATUS_STYLES: [(&str, Color, &str, &str, StatusBucket); 9] = [
+    ("RUNNING", Color::Green, "🟢", "[R]", StatusBucket::Running),
+    ("TERMINATED", Color::Red, "🔴", "[T]", StatusBucket::Stopped),
+    ("STOPPING", Color::Yellow, "🟠", "[S]", StatusBucket::Other),
+    (
+        "PROVISIONING",
+        Color::Magenta,
+        "🟡",
+        "[P]",
+        StatusBucket::Other,
+    ),
+    ("STAGING", Color::Cyan, "🔄", "[G]", StatusBucket::Other),
+    ("SUSPENDED", Color::Gray, "💤", "[Z]", StatusBucket::Stopped),
+    ("REPAIRING", Color::Yellow, "🟡", "[!]", StatusBucket::Other),
+    ("PENDING", Color::Yellow, "🟡", "[.]", StatusBucket::Other),
+    (
+        UNKNOWN_STATUS,
+        Color::Gray,
+        "❓",
+        "[?]",
+        StatusBucket::Other,
+    ),
+];
+
+/// Overview count an instance status is added to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBucket {
+    /// Counted as running
+    Running,
+    /// Counted as stopped, e.g. terminated or suspended
+    Stopped,
+    /// Counted as other, e.g. transitional states
+    Other,
+}
+
+/// Customization of a status display in the configuration, unset fields keep the default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StatusStyleConfig {
+    /// Color name (e.g. "green", "light-red") or hex code ("#ff8800")
+    pub color: Option<String>,
+    /// Emoji shown before the status
+    pub emoji: Option<String>,
+    /// ASCII marker shown before the status without emoji
+    pub ascii: Option<String>,
+    /// Text shown in the instance list
+    pub label: Option<String>,
+    /// Overview count the status is added to: running, stopped or other
+    pub bucket: Option<StatusBucket>,
+}
+
+/// Display of an instance status
+#[derive(Debug, Clone)]
+pub struct StatusStyle {
+    /// Color of the status
+    pub color: Color,
+    /// Emoji shown before the status
+    emoji: String,
+    /// ASCII marker shown before the status without emoji
+    ascii: String,
+    /// Text shown in the instance list
+    pub label: String,
+    /// Overview count the status is added to
+    pub bucket: StatusBucket,
+}
+
+impl StatusStyle {
+    /// Get the icon of the status, without separator
+    pub fn icon(&self, emoji: bool) -> &str {
+        if emoji {
+            &self.emoji
+        } else {
+            &self.ascii
+        }
+    }
+}
+
+/// Styles of every known status, with a fallback for unknown ones
+#[derive(Debug, Clone)]
+pub struct StatusStyles {
+    /// Styles by status
+    styles: HashMap<String, StatusStyle>,
+}
+
+impl StatusStyles {
+    /// Build the styles from the defaults and the configured customizations
+    ///
+    /// Statuses are matched case-insensitively, so new GCE statuses can be
+    /// added from the configuration. The UNKNOWN entry styles all others.
+    pub fn new(overrides: &HashMap<String, StatusStyleConfig>) -> Self {
+        let mut styles: HashMap<String, StatusStyle> = DEFAULT_STATUS_STYLES
+            .iter()
+            .map(|(status, color, emoji, ascii, bucket)| {
+                let style = StatusStyle {
+                    color: *color,
+                    emoji: emoji.to_string(),
+                    ascii: ascii.to_string(),
+                    label: status.to_string(),
+                    bucket: *bucket,
+                };
+                (status.to_string(), style)
+            })
+            .collect();
+
+        for (status, config) in overrides {
+            let status = status.to_uppercase();
+            let fallback = styles[UNKNOWN_STATUS].clone();
+            let style = styles.entry(status.clone()).or_insert_with(|| StatusStyle {
+                label: status.clone(),
+                ..fallback
+            });
+
+            if let Some(color) = &config.color {
+                match Color::from_str(color) {
+                    Ok(color) => style.color = color,
+                    Err(_) => warn!("Invalid color {:?} for status {}, ignored", color, status),
+                }
+            }
+            if let Some(emoji) = &config.emoji {
+                style.emoji = emoji.clone();
+            }
+            if let Some(ascii) = &config.ascii {
+                style.ascii = ascii.clone();
+            }
+            if let Some(label) = &config.label {
+                style.label = label.clone();
+            }
+            if let Some(bucket) = config.bucket {
+                style.bucket = bucket;
+            }
+        }
+
+        Self { styles }
+    }
+
+    /// Get the style of a status, the UNKNOWN one for statuses without their own
+    pub fn get(&self, status: &str) -> &StatusStyle {
+        self.styles
+            .get(status)
+            .unwrap_or_else(|| &self.styles[UNKNOWN_STATUS])
+    }
+}
+
+impl Default for StatusStyles {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
@@ -1,2 +1,147 @@
-// This file is intentionally kept empty as styles have been moved elsewhere.
-// It's maintained for compatibility with existing imports in the codebase.
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+/// Colors of the dashboard and details views, by role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Name of the theme in the configuration
+    pub name: &'static str,
+    /// Regular text
+    pub text: Color,
+    /// Secondary text, e.g. how long an instance has been in its status
+    pub secondary: Color,
+    /// Hints and placeholders
+    pub muted: Color,
+    /// Titles and borders
+    pub accent: Color,
+    /// Labels of values and column headers
+    pub label: Color,
+    /// Text being typed and values needing attention
+    pub highlight: Color,
+    /// Active modes, e.g. only showing my instances
+    pub emphasis: Color,
+    /// Counts and enabled settings
+    pub success: Color,
+    /// Warnings and stopped instance counts
+    pub error: Color,
+    /// Background of the selected instance
+    pub selection: Color,
+    /// Background of popups
+    pub background: Color,
+}
+
+impl Theme {
+    /// Light text on a dark terminal, the default
+    pub fn dark() -> Self {
+        Self {
+            name: "dark",
+            text: Color::White,
+            secondary: Color::Gray,
+            muted: Color::DarkGray,
+            accent: Color::Cyan,
+            label: Color::Blue,
+            highlight: Color::Yellow,
+            emphasis: Color::Magenta,
+            success: Color::Green,
+            error: Color::Red,
+            selection: Color::DarkGray,
+            background: Color::Black,
+        }
+    }
+
+    /// Dark text on a light terminal
+    pub fn light() -> Self {
+        Self {
+            name: "light",
+            text: Color::Black,
+            secondary: Color::DarkGray,
+            muted: Color::Gray,
+            accent: Color::Rgb(0, 110, 140),
+            label: Color::Blue,
+            highlight: Color::Rgb(175, 95, 0),
+            emphasis: Color::Magenta,
+            success: Color::Rgb(0, 128, 0),
+            error: Color::Red,
+            selection: Color::Rgb(210, 210, 210),
+            background: Color::White,
+        }
+    }
+
+    /// Terminal default colors everywhere, for monochrome terminals
+    pub fn plain() -> Self {
+        Self {
+            name: "plain",
+            text: Color::Reset,
+            secondary: Color::Reset,
+            muted: Color::Reset,
+            accent: Color::Reset,
+            label: Color::Reset,
+            highlight: Color::Reset,
+            emphasis: Color::Reset,
+            success: Color::Reset,
+            error: Color::Reset,
+            selection: Color::Reset,
+            background: Color::Reset,
+        }
+    }
+
+    /// Get a theme by its configuration name, "default" and unknown names being dark
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "light" => Self::light(),
+            _ => Self::dark(),
+        }
+    }
+
+    /// Get the style of the selected instance, reversed without colors to stay visible
+    pub fn selection_style(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        if self.is_plain() {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style.bg(self.selection)
+        }
+    }
+
+    /// Get the style of search matches, reversed without colors to stay visible
+    pub fn match_style(&self) -> Style {
+        if self.is_plain() {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.background).bg(self.highlight)
+        }
+    }
+
+    /// Check if this is the colorless theme
+    pub fn is_plain(&self) -> bool {
+        self.name == "plain"
+    }
+
+    /// Get the other theme, to switch between dark and light
+    pub fn toggled(&self) -> Self {
+        if *self == Self::light() {
+            Self::dark()
+        } else {
+            Self::light()
+        }
+    }
+}
+
+/// Widget resetting the colors drawn below it to the terminal defaults
+///
+/// Drawn last without colors, it also covers the popups using fixed colors.
+pub struct PlainColors;
+
+impl Widget for PlainColors {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y).set_fg(Color::Reset).set_bg(Color::Reset);
+            }
+        }
+    }
+}
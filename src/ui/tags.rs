@@ -0,0 +1,170 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::input::TextInput;
+use crate::cloud::{is_valid_name, Instance};
+
+/// State of the network tags popup, editing the tags of an instance
+pub struct TagsView {
+    /// Instance ID whose tags are edited
+    pub instance_id: String,
+    /// Instance name, for display
+    pub instance_name: String,
+    /// Tags of the instance when the popup was opened
+    current: Vec<String>,
+    /// Comma-separated tags being typed
+    input: TextInput,
+    /// Error explaining why the tags cannot be applied
+    error: Option<String>,
+}
+
+impl TagsView {
+    /// Create a tags view for an instance, starting from its current tags
+    pub fn new(instance: &Instance) -> Self {
+        Self {
+            instance_id: instance.id.clone(),
+            instance_name: instance.name.clone(),
+            current: instance.tags.clone(),
+            input: TextInput::new(&instance.tags.join(", ")),
+            error: None,
+        }
+    }
+
+    /// Edit the tag list, returning whether the key was used
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let handled = self.input.handle_key(key);
+        if handled {
+            self.error = None;
+        }
+        handled
+    }
+
+    /// Get the typed tags, without blanks or duplicates
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.input.value().split(',').map(str::trim) {
+            if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    /// Get the tags to add and to remove to go from the current tags to the typed ones
+    pub fn changes(&self) -> (Vec<String>, Vec<String>) {
+        let tags = self.tags();
+        let added = tags
+            .iter()
+            .filter(|tag| !self.current.contains(tag))
+            .cloned()
+            .collect();
+        let removed = self
+            .current
+            .iter()
+            .filter(|tag| !tags.contains(tag))
+            .cloned()
+            .collect();
+        (added, removed)
+    }
+
+    /// Get the changes, or explain why a tag to add is invalid
+    pub fn checked_changes(&mut self) -> Option<(Vec<String>, Vec<String>)> {
+        let (added, removed) = self.changes();
+        if let Some(tag) = added.iter().find(|tag| !is_valid_name(tag)) {
+            self.set_error(format!(
+                "Invalid tag {}: use up to 63 lowercase letters, digits and dashes, starting with a letter",
+                tag
+            ));
+            return None;
+        }
+        Some((added, removed))
+    }
+
+    /// Explain why the tags cannot be applied
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+}
+
+/// Render the network tags popup
+pub fn render<B: Backend>(frame: &mut Frame<B>, view: &TagsView, area: Rect) {
+    // Create a centered popup
+    let popup_area = super::centered_rect(60, 40, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Network tags: {}", view.instance_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let (added, removed) = view.changes();
+
+    let current = if view.current.is_empty() {
+        "(none)".to_string()
+    } else {
+        view.current.join(", ")
+    };
+    let mut tags_spans = vec![Span::styled("Tags:    ", bold)];
+    tags_spans.extend(view.input.spans(Style::default().fg(Color::Yellow)));
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled("Current: ", bold), Span::raw(current)]),
+        Line::from(tags_spans),
+        Line::from(""),
+    ];
+
+    // Preview the add-tags and remove-tags calls
+    if !added.is_empty() {
+        text.push(Line::from(Span::styled(
+            format!("+ {}", added.join(", ")),
+            Style::default().fg(Color::Green),
+        )));
+    }
+    if !removed.is_empty() {
+        text.push(Line::from(Span::styled(
+            format!("- {}", removed.join(", ")),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if added.is_empty() && removed.is_empty() {
+        text.push(Line::from(Span::styled(
+            "No changes",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    text.push(Line::from(""));
+
+    if let Some(error) = &view.error {
+        text.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    } else {
+        text.push(Line::from(vec![
+            Span::raw("Separate tags with commas. "),
+            Span::styled("Enter", bold),
+            Span::raw(" - Apply    "),
+            Span::styled("Ctrl+y", bold),
+            Span::raw(" - Copy gcloud command    "),
+            Span::styled("Esc", bold),
+            Span::raw(" - Cancel"),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}